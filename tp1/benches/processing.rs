@@ -0,0 +1,140 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::env::temp_dir;
+use std::fs::metadata;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::synthetic_data::synthetic_dataset::SyntheticDataset;
+
+/// Thread counts measured for every dataset and strategy.
+const THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+/// Strategies measured for every dataset and thread count, with the name used in the report.
+const STRATEGIES: [(&str, ProcessingStrategy); 5] = [
+    ("reduce", ProcessingStrategy::Reduce),
+    ("local-maps", ProcessingStrategy::LocalMaps),
+    ("channel", ProcessingStrategy::Channel),
+    ("pipeline", ProcessingStrategy::Pipeline),
+    ("sharded", ProcessingStrategy::Sharded),
+];
+
+/// Strategies compared on the high-cardinality dataset, where almost every killer is a different player.
+const HIGH_CARDINALITY_STRATEGIES: [(&str, ProcessingStrategy); 2] = [
+    ("reduce", ProcessingStrategy::Reduce),
+    ("sharded", ProcessingStrategy::Sharded),
+];
+
+/// Number of different players of the high-cardinality dataset.
+const HIGH_CARDINALITY_PLAYERS: usize = 10_000_000;
+
+/// Seed of the generated datasets, so that every run measures the same files.
+const SEED: u64 = 43;
+
+/// Number of different players of the generated datasets.
+const PLAYERS: usize = 20_000;
+
+/// The file-size distributions measured, as the number of rows of each file.
+fn file_size_distributions() -> Vec<(&'static str, Vec<usize>)> {
+    vec![
+        ("uniform", vec![25_000; 8]),
+        ("skewed", [vec![120_000], vec![10_000; 8]].concat()),
+        ("many-small", vec![2_000; 100]),
+    ]
+}
+
+/// Writes a dataset to a directory of the system temporary directory, unless it was written by a previous run.
+fn prepare_dataset(name: &str, players: usize, file_rows: Vec<usize>) -> PathBuf {
+    let dir_path = temp_dir().join(format!("tp1-bench-{}-{}", name, SEED));
+    if !dir_path.exists() {
+        let dataset = SyntheticDataset {
+            seed: SEED,
+            players,
+            file_rows,
+            ..SyntheticDataset::default()
+        };
+        dataset
+            .write_to(&dir_path)
+            .expect("Failed to write the benchmark dataset");
+    }
+    dir_path
+}
+
+/// Adds up the size of the files of a directory.
+fn dataset_bytes(dir_path: &Path) -> u64 {
+    dir_path
+        .read_dir()
+        .expect("Failed to read the benchmark dataset")
+        .flatten()
+        .filter_map(|entry| metadata(entry.path()).ok())
+        .map(|file_metadata| file_metadata.len())
+        .sum()
+}
+
+fn bench_process_data_in_parallel(c: &mut Criterion) {
+    for (name, file_rows) in file_size_distributions() {
+        let dir_path = prepare_dataset(name, PLAYERS, file_rows);
+        bench_strategies(c, name, &dir_path, &STRATEGIES);
+    }
+}
+
+fn bench_high_cardinality(c: &mut Criterion) {
+    let dir_path = prepare_dataset(
+        "high-cardinality",
+        HIGH_CARDINALITY_PLAYERS,
+        vec![50_000; 8],
+    );
+    bench_strategies(
+        c,
+        "high-cardinality",
+        &dir_path,
+        &HIGH_CARDINALITY_STRATEGIES,
+    );
+}
+
+/// Measures `process_data_in_parallel` on a dataset with each strategy and thread count.
+fn bench_strategies(
+    c: &mut Criterion,
+    name: &str,
+    dir_path: &Path,
+    strategies: &[(&str, ProcessingStrategy)],
+) {
+    let dir_path_str = dir_path.to_str().expect("Non UTF-8 temporary directory");
+
+    let mut group = c.benchmark_group(format!("process_data_in_parallel/{}", name));
+    group
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(5))
+        .throughput(Throughput::Bytes(dataset_bytes(dir_path)));
+
+    for &(strategy_name, strategy) in strategies {
+        let mut config = ProcessingConfig::default();
+        config.strategy = strategy;
+        for threads in THREAD_COUNTS {
+            group.bench_with_input(
+                BenchmarkId::new(strategy_name, threads),
+                &threads,
+                |b, &threads| {
+                    b.iter(|| {
+                        process_data_in_parallel(
+                            dir_path_str,
+                            threads,
+                            &config,
+                            &RunProgress::default(),
+                        )
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_process_data_in_parallel,
+    bench_high_cardinality
+);
+criterion_main!(benches);
@@ -0,0 +1,378 @@
+use crate::cli::output_format::OutputFormat;
+use crate::cohort_analysis::cohort_spec::CohortSpec;
+use crate::config::config_error::ConfigError;
+use crate::config::map_bounds::MapBounds;
+use crate::config::outlier_policy::OutlierPolicy;
+use crate::config::player_names::{NameNormalization, PlayerNames};
+use crate::config::processing_config::ProcessingConfig;
+use crate::config::processing_strategy::ProcessingStrategy;
+use crate::config::range_bands::RangeBands;
+use crate::config::sampling::{SampleUnit, Sampling};
+use crate::config::text_encoding::TextEncoding;
+use crate::config::weapon_taxonomy::WeaponTaxonomy;
+use crate::data_query::top_filter::TopFilter;
+use crate::data_query::top_metric::TopMetric;
+use crate::metadata::active_filters::ActiveFilters;
+use crate::metadata::output_metadata::DEFAULT_PRODUCER_ID;
+use crate::synthetic_data::synthetic_dataset::SyntheticDataset;
+
+use clap::{Args, Parser, Subcommand};
+use std::thread::available_parallelism;
+
+/// Names of the subcommands, used to detect the legacy positional invocation.
+const SUBCOMMANDS: [&str; 11] = [
+    "summary", "player", "weapon", "top", "diff", "serve", "generate", "trend", "validate",
+    "cohort", "help",
+];
+
+/// Aggregates PUBG death statistics from CSV dumps.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// The subcommands of the tool.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Writes the summary of the top killers and weapons to a JSON file.
+    Summary {
+        #[command(flatten)]
+        ingest: IngestArgs,
+        #[command(flatten)]
+        sample: SampleArgs,
+        /// Path of the JSON summary file.
+        #[arg(long, short)]
+        output: String,
+        /// Path of a JSON Lines file where one record per match is written.
+        #[arg(long)]
+        matches_output: Option<String>,
+        /// Path of a SQLite database where the full player, weapon and player-weapon tables are written. The tables
+        /// hold the counts read, so it cannot be combined with --sample.
+        #[arg(long, conflicts_with = "sample")]
+        sqlite_output: Option<String>,
+        /// Identifies who or what produced the summary, written to its metadata block.
+        #[arg(long, default_value = DEFAULT_PRODUCER_ID)]
+        producer_id: String,
+        /// Skip the SHA-256 of every processed file in the metadata block. Hashing reads the input again after
+        /// processing it, and is always skipped if the run is cancelled.
+        #[arg(long)]
+        no_hash_input: bool,
+        /// Keep watching the input directory and rewrite the outputs as new CSV files arrive.
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between two scans of the input directory in watch mode.
+        #[arg(long, default_value_t = 5, requires = "watch")]
+        watch_interval: u64,
+    },
+    /// Shows the full weapon breakdown of a player.
+    Player {
+        /// Name of the player. Aliases and name normalisation are applied to it.
+        name: String,
+        #[command(flatten)]
+        ingest: IngestArgs,
+        /// Format of the result.
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Shows the stats of a weapon and its top players.
+    Weapon {
+        /// Name of the weapon. The weapon mapping is applied to it.
+        name: String,
+        #[command(flatten)]
+        ingest: IngestArgs,
+        /// Format of the result.
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Ranks players or weapons by a metric.
+    Top {
+        /// Metric used to rank the entries.
+        #[arg(long, value_enum)]
+        by: TopMetric,
+        /// Number of entries shown.
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        #[command(flatten)]
+        filter: TopFilter,
+        #[command(flatten)]
+        ingest: IngestArgs,
+        /// Format of the result.
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Processes the CSV files once and serves queries over them through a local HTTP API.
+    Serve {
+        #[command(flatten)]
+        ingest: IngestArgs,
+        /// Address the server listens on.
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: String,
+    },
+    /// Reports how the weapons and the top players evolve over dated subdirectories of the input directory.
+    Trend {
+        #[command(flatten)]
+        ingest: IngestArgs,
+        /// Number of top players by kills taken from each period.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Format of the report.
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Checks the headers, the encoding and a sample of the rows of every input file before a long run, and estimates
+    /// its runtime. Exits with an error if any problem is found.
+    Validate {
+        #[command(flatten)]
+        ingest: IngestArgs,
+        /// Number of rows checked from the start of each CSV file.
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+        sample_rows: u64,
+        /// Format of the report.
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Compares the weapon mix and the kill distances of two cohorts of players, defined by their rank by kills or
+    /// by a file with their names.
+    Cohort {
+        #[command(flatten)]
+        ingest: IngestArgs,
+        /// First cohort, as rank:<first>-<last> or placement:<first>-<last>, either of them without <last>, or
+        /// players:<file>.
+        #[arg(long)]
+        cohort_a: CohortSpec,
+        /// Second cohort, in the same format as the first.
+        #[arg(long)]
+        cohort_b: CohortSpec,
+        /// Number of weapons by kills compared on their own. The rest are pooled as Other.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Format of the report.
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Writes a synthetic deaths dataset, and the aggregates expected from it, for testing.
+    Generate(GenerateArgs),
+    /// Compares two summary files written by the `summary` subcommand.
+    Diff {
+        /// Summary of the old run.
+        old: String,
+        /// Summary of the new run.
+        new: String,
+        /// Number of entries compared in the rankings.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Format of the report.
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+/// Options shared by every subcommand that reads the deaths CSV files.
+#[derive(Args, Debug)]
+pub struct IngestArgs {
+    /// Directory containing the deaths CSV, Parquet or Arrow IPC files.
+    pub input_path: String,
+    /// Number of threads used to process the files.
+    #[arg(long, short, default_value_t = default_threads())]
+    pub threads: usize,
+    /// JSON file with the size of each map, for example {"ERANGEL": 800000}.
+    #[arg(long)]
+    pub map_sizes: Option<String>,
+    /// What to do with rows whose positions are not valid for their map: drop, clamp or count.
+    #[arg(long, default_value = "count")]
+    pub outlier_policy: OutlierPolicy,
+    /// JSON file with the canonical name, aliases and category of each weapon.
+    #[arg(long)]
+    pub weapon_mapping: Option<String>,
+    /// JSON file with the canonical name of each player and the list of their aliases.
+    #[arg(long)]
+    pub player_aliases: Option<String>,
+    /// Normalisation applied to player names: none, case, unicode or all.
+    #[arg(long, default_value = "none")]
+    pub normalize_names: NameNormalization,
+    /// How the files are spread over the threads and how their stats are combined.
+    #[arg(long, value_enum, default_value_t)]
+    pub strategy: ProcessingStrategy,
+    /// Upper limits of the close, mid and long range bands of the kills, in centimeters.
+    #[arg(long, default_value = "5000,20000,50000")]
+    pub range_bands: RangeBands,
+    /// Encoding of the CSV files. auto reads the lines that are not valid UTF-8 as Latin-1.
+    #[arg(long, value_enum, default_value_t)]
+    pub encoding: TextEncoding,
+}
+
+/// Options of the `summary` subcommand that process only a sample of the input. The other subcommands report the
+/// counts they read, so they always process the whole input.
+#[derive(Args, Debug)]
+pub struct SampleArgs {
+    /// Process only this fraction of the input, between 0 and 1, scaling the counts and reporting confidence intervals.
+    #[arg(long)]
+    pub sample: Option<f64>,
+    /// Whether rows or whole files are sampled.
+    #[arg(long, value_enum, default_value_t, requires = "sample")]
+    pub sample_by: SampleUnit,
+    /// Seed of the sampling. The same seed always processes the same rows.
+    #[arg(long, default_value_t = 0, requires = "sample")]
+    pub sample_seed: u64,
+}
+
+/// Options of the synthetic dataset written by the `generate` subcommand.
+#[derive(Args, Debug)]
+pub struct GenerateArgs {
+    /// Directory where the CSV files and the expected aggregates are written.
+    pub output_dir: String,
+    /// Seed of the random number generator. The same seed and options always write the same files.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// Number of different players.
+    #[arg(long, default_value_t = 1000)]
+    pub players: usize,
+    /// Number of different weapons, up to 16.
+    #[arg(long, default_value_t = 16)]
+    pub weapons: usize,
+    /// Number of different maps, up to 4.
+    #[arg(long, default_value_t = 4)]
+    pub maps: usize,
+    /// Number of CSV files.
+    #[arg(long, default_value_t = 4)]
+    pub files: usize,
+    /// Number of rows of each file.
+    #[arg(long, default_value_t = 100_000)]
+    pub rows_per_file: usize,
+    /// Number of rows of each match.
+    #[arg(long, default_value_t = 90)]
+    pub deaths_per_match: usize,
+    /// Probability of a row being malformed, between 0 and 1.
+    #[arg(long, default_value_t = 0.0)]
+    pub malformed_rate: f64,
+    /// Probability of a row being an environment death, a suicide or a team kill, between 0 and 1.
+    #[arg(long, default_value_t = 0.05)]
+    pub special_death_rate: f64,
+    /// Exponent of the Zipf distribution of the killers and weapons. 0 picks them uniformly.
+    #[arg(long, default_value_t = 0.0)]
+    pub skew: f64,
+}
+
+impl GenerateArgs {
+    /// Builds the `SyntheticDataset` described by the options.
+    ///
+    /// # Returns
+    ///
+    /// The `SyntheticDataset` to write.
+    pub fn to_dataset(&self) -> SyntheticDataset {
+        SyntheticDataset {
+            seed: self.seed,
+            players: self.players,
+            weapons: self.weapons,
+            maps: self.maps,
+            deaths_per_match: self.deaths_per_match,
+            file_rows: vec![self.rows_per_file; self.files],
+            malformed_rate: self.malformed_rate,
+            special_death_rate: self.special_death_rate,
+            skew: self.skew,
+        }
+    }
+}
+
+impl IngestArgs {
+    /// Lists the options that change which rows are counted and how, for the metadata of the output.
+    ///
+    /// # Returns
+    ///
+    /// The `ActiveFilters` of the options.
+    pub fn active_filters(&self) -> ActiveFilters {
+        ActiveFilters {
+            outlier_policy: self.outlier_policy,
+            map_sizes: self.map_sizes.clone(),
+            weapon_mapping: self.weapon_mapping.clone(),
+            player_aliases: self.player_aliases.clone(),
+            normalize_names: self.normalize_names.to_string(),
+            range_bands: self.range_bands.to_string(),
+            sampling: None,
+            encoding: self.encoding,
+        }
+    }
+
+    /// Builds the `ProcessingConfig` from the options, loading the configuration files.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ProcessingConfig`, or a `ConfigError` if a file cannot be loaded.
+    pub fn to_config(&self) -> Result<ProcessingConfig, ConfigError> {
+        let map_bounds = match &self.map_sizes {
+            Some(path) => MapBounds::from_file(path)?,
+            None => MapBounds::default(),
+        };
+        let weapon_taxonomy = match &self.weapon_mapping {
+            Some(path) => WeaponTaxonomy::from_file(path)?,
+            None => WeaponTaxonomy::default(),
+        };
+        let player_names = PlayerNames::new(self.player_aliases.as_deref(), self.normalize_names)?;
+
+        Ok(ProcessingConfig {
+            map_bounds,
+            outlier_policy: self.outlier_policy,
+            weapon_taxonomy,
+            player_names,
+            track_matches: false,
+            track_killer_placements: false,
+            strategy: self.strategy,
+            range_bands: self.range_bands,
+            sampling: None,
+            encoding: self.encoding,
+        })
+    }
+}
+
+impl SampleArgs {
+    /// Builds the `Sampling` of the options.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Sampling`, or `None` if the input is not sampled, or a `ConfigError` if the
+    /// fraction is not valid.
+    pub fn sampling(&self) -> Result<Option<Sampling>, ConfigError> {
+        self.sample
+            .map(|fraction| Sampling::new(fraction, self.sample_by, self.sample_seed))
+            .transpose()
+    }
+}
+
+/// Returns the number of threads used when `--threads` is not given.
+fn default_threads() -> usize {
+    available_parallelism().map_or(1, |threads| threads.get())
+}
+
+/// Rewrites the legacy `<input-path> <num-threads> <output-file-name> [options]` invocation as the equivalent
+/// `summary` subcommand, leaving any other invocation untouched.
+///
+/// # Arguments
+///
+/// * `args` - The command-line arguments, including the program name.
+///
+/// # Returns
+///
+/// The arguments to be parsed by `Cli`.
+pub fn rewrite_legacy_args(args: Vec<String>) -> Vec<String> {
+    let is_legacy = args.len() >= 4
+        && !args[1].starts_with('-')
+        && !SUBCOMMANDS.contains(&args[1].as_str())
+        && args[2].parse::<usize>().is_ok();
+    if !is_legacy {
+        return args;
+    }
+
+    let mut rewritten = vec![
+        args[0].clone(),
+        "summary".to_owned(),
+        args[1].clone(),
+        "--threads".to_owned(),
+        args[2].clone(),
+        "--output".to_owned(),
+        args[3].clone(),
+    ];
+    rewritten.extend_from_slice(&args[4..]);
+    rewritten
+}
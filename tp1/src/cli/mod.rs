@@ -0,0 +1,2 @@
+pub mod cli_args;
+pub mod output_format;
@@ -0,0 +1,14 @@
+use clap::ValueEnum;
+
+/// The format used to print the results of a subcommand.
+///
+/// # Variants
+///
+/// * `Text` - Human-readable text.
+/// * `Json` - Pretty-printed JSON.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
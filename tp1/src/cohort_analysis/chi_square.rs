@@ -0,0 +1,143 @@
+use crate::cohort_analysis::cohort_report::ChiSquareTest;
+
+/// Maximum number of iterations of the series and the continued fraction of the incomplete gamma function.
+const MAX_ITERATIONS: usize = 500;
+
+/// Relative precision at which the series and the continued fraction stop.
+const EPSILON: f64 = 1e-14;
+
+/// Smallest value used in the continued fraction, to avoid dividing by zero.
+const FLOOR: f64 = 1e-300;
+
+/// Coefficients of the Lanczos approximation of the gamma function, with `g = 7`.
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Runs Pearson's chi-square test of independence on a table with two rows, such as the kills of two cohorts with
+/// each weapon.
+///
+/// Columns that are `0` in both rows are left out.
+///
+/// # Arguments
+///
+/// * `first` - The counts of the first row.
+/// * `second` - The counts of the second row, in the same columns as `first`.
+///
+/// # Returns
+///
+/// The `ChiSquareTest`, or `None` if a row is all `0` or fewer than two columns are left, so there is nothing to
+/// test.
+pub fn chi_square_test(first: &[u32], second: &[u32]) -> Option<ChiSquareTest> {
+    let columns: Vec<_> = first
+        .iter()
+        .zip(second)
+        .map(|(&a, &b)| (a as f64, b as f64))
+        .filter(|(a, b)| a + b > 0.0)
+        .collect();
+    let first_total: f64 = columns.iter().map(|(a, _)| a).sum();
+    let second_total: f64 = columns.iter().map(|(_, b)| b).sum();
+    if columns.len() < 2 || first_total == 0.0 || second_total == 0.0 {
+        return None;
+    }
+
+    let total = first_total + second_total;
+    let statistic = columns
+        .iter()
+        .flat_map(|(a, b)| {
+            let column_total = a + b;
+            [
+                (*a, first_total * column_total / total),
+                (*b, second_total * column_total / total),
+            ]
+        })
+        .map(|(observed, expected)| (observed - expected).powi(2) / expected)
+        .sum();
+    let degrees_of_freedom = columns.len() - 1;
+
+    Some(ChiSquareTest {
+        statistic,
+        degrees_of_freedom,
+        p_value: chi_square_survival(statistic, degrees_of_freedom),
+    })
+}
+
+/// Returns the probability that a chi-square variable with the given degrees of freedom is at least `statistic`,
+/// which is the p-value of the test.
+///
+/// # Arguments
+///
+/// * `statistic` - The chi-square statistic.
+/// * `degrees_of_freedom` - The degrees of freedom, at least `1`.
+pub fn chi_square_survival(statistic: f64, degrees_of_freedom: usize) -> f64 {
+    if statistic <= 0.0 {
+        return 1.0;
+    }
+    regularized_upper_gamma(degrees_of_freedom as f64 / 2.0, statistic / 2.0)
+}
+
+/// Computes the regularized upper incomplete gamma function `Q(a, x)`, with its series below `a + 1` and its
+/// continued fraction above, where each one converges fast.
+fn regularized_upper_gamma(a: f64, x: f64) -> f64 {
+    let prefactor = (-x + a * x.ln() - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..MAX_ITERATIONS {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        return (1.0 - sum * prefactor).clamp(0.0, 1.0);
+    }
+
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / FLOOR;
+    let mut d = 1.0 / b;
+    let mut fraction = d;
+    for i in 1..MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < FLOOR {
+            d = FLOOR;
+        }
+        c = b + an / c;
+        if c.abs() < FLOOR {
+            c = FLOOR;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        fraction *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    (fraction * prefactor).clamp(0.0, 1.0)
+}
+
+/// Computes the natural logarithm of the gamma function with the Lanczos approximation, for `x >= 0.5`.
+fn ln_gamma(x: f64) -> f64 {
+    let x = x - 1.0;
+    let series = LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS[0], |sum, (i, coefficient)| {
+            sum + coefficient / (x + i as f64 + 1.0)
+        });
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
@@ -0,0 +1,291 @@
+use crate::cohort_analysis::chi_square::chi_square_test;
+use crate::cohort_analysis::cohort_error::CohortError;
+use crate::cohort_analysis::cohort_report::{CohortReport, CohortSummary, WeaponShare};
+use crate::cohort_analysis::cohort_spec::CohortSpec;
+use crate::config::processing_config::ProcessingConfig;
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::distance_sum::DistanceSum;
+use crate::data_processing::player_stats::PlayerStats;
+use crate::data_processing::range_band_counts::RangeBandCounts;
+use crate::data_processing::weapon_stats::WeaponStats;
+use crate::data_query::data_querier::percentage;
+use crate::data_summary::data_summarizer::{sort_players_by_kills, summarize_range_bands};
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+/// Name of the pooled weapons outside the top of the weapon mix.
+const OTHER_WEAPONS: &str = "Other";
+
+/// The merged stats of the players of a cohort.
+///
+/// # Fields
+///
+/// * `players` - The number of players of the cohort with kills.
+/// * `weapons` - The merged `WeaponStats` of every weapon used by the players.
+struct CohortStats {
+    players: usize,
+    weapons: HashMap<String, WeaponStats>,
+}
+
+impl CohortStats {
+    /// Returns the kills of the cohort.
+    fn kills(&self) -> u32 {
+        self.weapons.values().map(|stats| stats.deaths).sum()
+    }
+
+    /// Returns the kills of the cohort with a weapon.
+    fn weapon_kills(&self, weapon: &str) -> u32 {
+        self.weapons.get(weapon).map_or(0, |stats| stats.deaths)
+    }
+
+    /// Returns the range band counts of the cohort over every weapon and map.
+    fn range_bands(&self) -> RangeBandCounts {
+        let mut total = RangeBandCounts::default();
+        self.weapons
+            .values()
+            .flat_map(|stats| stats.range_bands.values())
+            .for_each(|counts| total.merge(counts));
+        total
+    }
+
+    /// Returns the average distance of the kills with a valid distance, rounded to two decimal places.
+    fn average_distance(&self) -> Option<f64> {
+        let (count, distance) = self.weapons.values().fold(
+            (0, DistanceSum::default()),
+            |(count, mut distance), stats| {
+                distance.merge(&stats.total_distance);
+                (count + stats.valid_distances_count, distance)
+            },
+        );
+        (count > 0).then(|| (distance.centimeters() / count as f64 * 100.0).round() / 100.0)
+    }
+}
+
+/// Compares the weapon mix and the kill distances of two cohorts of players from the stats of a single pass over
+/// the input.
+///
+/// # Arguments
+///
+/// * `deaths_info` - The aggregated `DeathsInfo` of the input.
+/// * `cohort_a` - The `CohortSpec` of the first cohort.
+/// * `cohort_b` - The `CohortSpec` of the second cohort.
+/// * `config` - The `ProcessingConfig` the input was processed with, used to resolve the names of players files.
+///   Placement cohorts need the input processed with `track_killer_placements` enabled.
+/// * `top` - The number of weapons by kills of both cohorts compared on their own. The rest are pooled as `Other`.
+///
+/// # Returns
+///
+/// A `Result` containing the `CohortReport`, or a `CohortError` if a players file cannot be read or a cohort has no
+/// kills.
+pub fn compare_cohorts(
+    deaths_info: &DeathsInfo,
+    cohort_a: &CohortSpec,
+    cohort_b: &CohortSpec,
+    config: &ProcessingConfig,
+    top: usize,
+) -> Result<CohortReport, CohortError> {
+    let stats_a = cohort_stats(deaths_info, cohort_a, config)?;
+    let stats_b = cohort_stats(deaths_info, cohort_b, config)?;
+
+    let (weapon_mix, kills_a, kills_b) = weapon_mix(&stats_a, &stats_b, top);
+    let bands_a = range_band_counts(&stats_a);
+    let bands_b = range_band_counts(&stats_b);
+
+    let summary_a = summarize_cohort(cohort_a, &stats_a);
+    let summary_b = summarize_cohort(cohort_b, &stats_b);
+    let average_distance_difference = summary_a
+        .average_distance
+        .zip(summary_b.average_distance)
+        .map(|(a, b)| ((b - a) * 100.0).round() / 100.0);
+
+    Ok(CohortReport {
+        cohort_a: summary_a,
+        cohort_b: summary_b,
+        weapon_mix,
+        weapon_mix_test: chi_square_test(&kills_a, &kills_b),
+        range_band_test: chi_square_test(&bands_a, &bands_b),
+        average_distance_difference,
+    })
+}
+
+/// Merges the weapon stats of the kills of a cohort.
+///
+/// # Returns
+///
+/// The `CohortStats` of the cohort, or a `CohortError` if its players file cannot be read or it has no kills.
+fn cohort_stats(
+    deaths_info: &DeathsInfo,
+    spec: &CohortSpec,
+    config: &ProcessingConfig,
+) -> Result<CohortStats, CohortError> {
+    let stats = match spec {
+        CohortSpec::Rank { first, last } => {
+            players_stats(&ranked_members(deaths_info, *first, *last))
+        }
+        CohortSpec::Players(path) => players_stats(&listed_members(deaths_info, path, config)?),
+        CohortSpec::Placement { first, last } => placement_stats(deaths_info, *first, *last),
+    };
+
+    if stats.kills() == 0 {
+        return Err(CohortError::EmptyCohort(spec.to_string()));
+    }
+    Ok(stats)
+}
+
+/// Merges the weapon stats of every kill of the given players.
+fn players_stats(members: &[&PlayerStats]) -> CohortStats {
+    let mut weapons: HashMap<String, WeaponStats> = HashMap::new();
+    for player in members {
+        for (weapon, stats) in &player.used_weapons {
+            weapons.entry(weapon.clone()).or_default().merge(stats);
+        }
+    }
+
+    CohortStats {
+        players: members.len(),
+        weapons,
+    }
+}
+
+/// Merges the weapon stats of the kills made by killers that finished their match between `first` and `last`.
+///
+/// The kills are collected by placement during the pass over the input, so a player only counts with the kills
+/// made with those placements.
+fn placement_stats(deaths_info: &DeathsInfo, first: usize, last: Option<usize>) -> CohortStats {
+    let placements = first..=last.unwrap_or(usize::MAX);
+
+    let mut killers = HashSet::new();
+    let mut weapons: HashMap<String, WeaponStats> = HashMap::new();
+    for (placement, placement_stats) in &deaths_info.killer_placements {
+        if !placements.contains(&(*placement as usize)) {
+            continue;
+        }
+        killers.extend(&placement_stats.killers);
+        for (weapon, stats) in &placement_stats.weapons {
+            weapons.entry(weapon.clone()).or_default().merge(stats);
+        }
+    }
+
+    CohortStats {
+        players: killers.len(),
+        weapons,
+    }
+}
+
+/// Returns the `PlayerStats` of the players ranked between `first` and `last` by kills.
+fn ranked_members(
+    deaths_info: &DeathsInfo,
+    first: usize,
+    last: Option<usize>,
+) -> Vec<&PlayerStats> {
+    let ranked = sort_players_by_kills(&deaths_info.players);
+    let take = last.map_or(usize::MAX, |last| last - first + 1);
+    ranked
+        .into_iter()
+        .skip(first - 1)
+        .take(take)
+        .map(|(_, stats)| stats)
+        .collect()
+}
+
+/// Returns the `PlayerStats` of the players listed in a file that have kills in the input.
+fn listed_members<'a>(
+    deaths_info: &'a DeathsInfo,
+    path: &str,
+    config: &ProcessingConfig,
+) -> Result<Vec<&'a PlayerStats>, CohortError> {
+    let content = fs::read_to_string(path).map_err(|_| CohortError::FileRead(path.to_owned()))?;
+    let names: HashSet<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|name| config.player_names.canonical_name(name).into_owned())
+        .collect();
+    Ok(names
+        .iter()
+        .filter_map(|name| deaths_info.players.get(name))
+        .collect())
+}
+
+/// Computes the share of the kills of each cohort made with the top weapons of both cohorts, pooling the rest as
+/// `Other`.
+///
+/// # Returns
+///
+/// The `WeaponShare` of each weapon, and the kills of each cohort with them, in the same order.
+fn weapon_mix(
+    stats_a: &CohortStats,
+    stats_b: &CohortStats,
+    top: usize,
+) -> (Vec<WeaponShare>, Vec<u32>, Vec<u32>) {
+    let weapons: HashSet<&String> = stats_a
+        .weapons
+        .keys()
+        .chain(stats_b.weapons.keys())
+        .collect();
+    let mut weapons: Vec<(&String, u32)> = weapons
+        .into_iter()
+        .map(|weapon| {
+            let kills = stats_a.weapon_kills(weapon) + stats_b.weapon_kills(weapon);
+            (weapon, kills)
+        })
+        .collect();
+    weapons.sort_by(|w1, w2| w2.1.cmp(&w1.1).then_with(|| w1.0.cmp(w2.0)));
+
+    let mut rows: Vec<(String, u32, u32)> = weapons
+        .iter()
+        .take(top)
+        .map(|(weapon, _)| {
+            (
+                weapon.to_string(),
+                stats_a.weapon_kills(weapon),
+                stats_b.weapon_kills(weapon),
+            )
+        })
+        .collect();
+    if weapons.len() > top {
+        let (other_a, other_b) = weapons[top..].iter().fold((0, 0), |(a, b), (weapon, _)| {
+            (
+                a + stats_a.weapon_kills(weapon),
+                b + stats_b.weapon_kills(weapon),
+            )
+        });
+        rows.push((OTHER_WEAPONS.to_owned(), other_a, other_b));
+    }
+
+    let (total_a, total_b) = (stats_a.kills(), stats_b.kills());
+    let shares = rows
+        .iter()
+        .map(|(weapon, a, b)| {
+            let a_percentage = percentage(*a, total_a);
+            let b_percentage = percentage(*b, total_b);
+            WeaponShare {
+                weapon: weapon.clone(),
+                a_percentage,
+                b_percentage,
+                difference: ((b_percentage - a_percentage) * 100.0).round() / 100.0,
+            }
+        })
+        .collect();
+    let kills_a = rows.iter().map(|(_, a, _)| *a).collect();
+    let kills_b = rows.iter().map(|(_, _, b)| *b).collect();
+    (shares, kills_a, kills_b)
+}
+
+/// Returns the range band counts of a cohort as a row of the chi-square test.
+fn range_band_counts(stats: &CohortStats) -> [u32; 4] {
+    let counts = stats.range_bands();
+    [counts.close, counts.mid, counts.long, counts.extreme]
+}
+
+/// Builds the `CohortSummary` of a cohort.
+fn summarize_cohort(spec: &CohortSpec, stats: &CohortStats) -> CohortSummary {
+    CohortSummary {
+        name: spec.to_string(),
+        players: stats.players,
+        kills: stats.kills(),
+        average_distance: stats.average_distance(),
+        range_profile: summarize_range_bands(&stats.range_bands()),
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt;
+
+/// Errors that may occur while comparing two cohorts of players.
+///
+/// # Variants
+///
+/// * `FileRead` - A file with the players of a cohort could not be read. Holds the path of the file.
+/// * `EmptyCohort` - No player of a cohort has kills in the input. Holds the definition of the cohort.
+#[derive(Debug)]
+pub enum CohortError {
+    FileRead(String),
+    EmptyCohort(String),
+}
+
+impl fmt::Display for CohortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CohortError::FileRead(path) => write!(f, "Could not read players file {}", path),
+            CohortError::EmptyCohort(cohort) => {
+                write!(f, "No player of cohort {} has kills in the input", cohort)
+            }
+        }
+    }
+}
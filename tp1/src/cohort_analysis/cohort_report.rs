@@ -0,0 +1,148 @@
+use crate::data_summary::range_profile_summary::RangeProfileSummary;
+
+use serde::Serialize;
+use std::fmt;
+
+/// Smallest p-value written as a number in the text report.
+const MIN_SHOWN_P_VALUE: f64 = 0.0001;
+
+/// The aggregated kills of the players of a cohort.
+///
+/// # Fields
+///
+/// * `name` - The definition of the cohort, such as `rank:1-100`.
+/// * `players` - The number of players of the cohort with kills in the input.
+/// * `kills` - The kills of all the players of the cohort.
+/// * `average_distance` - The average distance of the kills with a valid distance, rounded to two decimal places,
+///   or `None` if no kill has one.
+/// * `range_profile` - How the kills with a valid distance are split into range bands, over every map.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct CohortSummary {
+    pub name: String,
+    pub players: usize,
+    pub kills: u32,
+    pub average_distance: Option<f64>,
+    pub range_profile: RangeProfileSummary,
+}
+
+/// The share of the kills of each cohort made with a weapon.
+///
+/// # Fields
+///
+/// * `weapon` - The name of the weapon, or `Other` for the weapons outside the top.
+/// * `a_percentage` - The percentage of the kills of the first cohort made with the weapon.
+/// * `b_percentage` - The percentage of the kills of the second cohort made with the weapon.
+/// * `difference` - `b_percentage` minus `a_percentage`.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct WeaponShare {
+    pub weapon: String,
+    pub a_percentage: f64,
+    pub b_percentage: f64,
+    pub difference: f64,
+}
+
+/// The result of a chi-square test of independence between the cohorts and a set of categories.
+///
+/// # Fields
+///
+/// * `statistic` - The chi-square statistic.
+/// * `degrees_of_freedom` - The number of categories with kills minus one.
+/// * `p_value` - The probability of a statistic at least this large if both cohorts had the same distribution.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquareTest {
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    pub p_value: f64,
+}
+
+impl fmt::Display for ChiSquareTest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "chi-square = {:.2}, df = {}, ",
+            self.statistic, self.degrees_of_freedom
+        )?;
+        if self.p_value < MIN_SHOWN_P_VALUE {
+            write!(f, "p < {}", MIN_SHOWN_P_VALUE)
+        } else {
+            write!(f, "p = {:.4}", self.p_value)
+        }
+    }
+}
+
+/// The comparison of the weapon mix and the kill distances of two cohorts of players.
+///
+/// # Fields
+///
+/// * `cohort_a` - The `CohortSummary` of the first cohort.
+/// * `cohort_b` - The `CohortSummary` of the second cohort.
+/// * `weapon_mix` - The `WeaponShare` of the top weapons by kills of both cohorts, sorted by kills, followed by the
+///   rest of the weapons pooled as `Other`.
+/// * `weapon_mix_test` - The chi-square test of the weapon mix, or `None` if there are fewer than two weapons.
+/// * `range_band_test` - The chi-square test of the range bands, or `None` if a cohort has no kill with a valid
+///   distance or fewer than two bands have kills.
+/// * `average_distance_difference` - The average distance of the second cohort minus the one of the first, or
+///   `None` if a cohort has no kill with a valid distance.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct CohortReport {
+    pub cohort_a: CohortSummary,
+    pub cohort_b: CohortSummary,
+    pub weapon_mix: Vec<WeaponShare>,
+    pub weapon_mix_test: Option<ChiSquareTest>,
+    pub range_band_test: Option<ChiSquareTest>,
+    pub average_distance_difference: Option<f64>,
+}
+
+impl fmt::Display for CohortReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_cohort(f, "A", &self.cohort_a)?;
+        write_cohort(f, "B", &self.cohort_b)?;
+        if let Some(difference) = self.average_distance_difference {
+            writeln!(f, "Average distance difference (B - A): {:+.2}", difference)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "Weapon mix (% of kills, A -> B)")?;
+        for share in &self.weapon_mix {
+            writeln!(
+                f,
+                "  {}: {} -> {} ({:+.2})",
+                share.weapon, share.a_percentage, share.b_percentage, share.difference
+            )?;
+        }
+        writeln!(f)?;
+
+        write_test(f, "Weapon mix", self.weapon_mix_test.as_ref())?;
+        write_test(f, "Range bands", self.range_band_test.as_ref())
+    }
+}
+
+/// Writes the kills, the average distance and the range bands of a cohort.
+fn write_cohort(f: &mut fmt::Formatter<'_>, label: &str, cohort: &CohortSummary) -> fmt::Result {
+    writeln!(
+        f,
+        "Cohort {} ({}): {} players, {} kills",
+        label, cohort.name, cohort.players, cohort.kills
+    )?;
+    match cohort.average_distance {
+        Some(distance) => writeln!(f, "  Average distance: {}", distance)?,
+        None => writeln!(f, "  Average distance: no valid distances")?,
+    }
+    let profile = &cohort.range_profile;
+    writeln!(
+        f,
+        "  Range bands (%): close {}, mid {}, long {}, extreme {}",
+        profile.close_percentage,
+        profile.mid_percentage,
+        profile.long_percentage,
+        profile.extreme_percentage
+    )
+}
+
+/// Writes the result of a test, or why it could not be run.
+fn write_test(f: &mut fmt::Formatter<'_>, name: &str, test: Option<&ChiSquareTest>) -> fmt::Result {
+    match test {
+        Some(test) => writeln!(f, "{} test: {}", name, test),
+        None => writeln!(f, "{} test: not enough data", name),
+    }
+}
@@ -0,0 +1,93 @@
+use crate::config::config_error::ConfigError;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// How the players of a cohort are chosen.
+///
+/// # Variants
+///
+/// * `Rank` - The players ranked between `first` and `last` by kills, starting at `1`, with ties broken by name.
+///   If `last` is `None`, every player from `first` on.
+/// * `Placement` - The player kills made by killers that finished their match between `first` and `last`,
+///   starting at `1`. If `last` is `None`, every placement from `first` on. Unlike the other cohorts, a player
+///   belongs to it only for the kills made with those placements.
+/// * `Players` - The players listed in a file, one name per line. Empty lines and lines starting with `#` are
+///   ignored, and the names are resolved like the names of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CohortSpec {
+    Rank { first: usize, last: Option<usize> },
+    Placement { first: usize, last: Option<usize> },
+    Players(String),
+}
+
+impl CohortSpec {
+    /// Returns whether the cohort needs the player kills collected by the placement of the killer.
+    pub fn needs_killer_placements(&self) -> bool {
+        matches!(self, CohortSpec::Placement { .. })
+    }
+}
+
+impl FromStr for CohortSpec {
+    type Err = ConfigError;
+
+    /// Parses `rank:<first>-<last>`, `placement:<first>-<last>`, either of them without `<last>`, or
+    /// `players:<file>`, for example `rank:1-100`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            ConfigError::InvalidOption(format!(
+                "Invalid cohort '{}', expected rank:<first>-<last>, placement:<first>-<last>, either of them \
+                 without <last>, or players:<file>.",
+                s
+            ))
+        };
+
+        match s.split_once(':') {
+            Some(("players", path)) if !path.is_empty() => Ok(CohortSpec::Players(path.to_owned())),
+            Some(("rank", range)) => {
+                let (first, last) = parse_range(range).ok_or_else(invalid)?;
+                Ok(CohortSpec::Rank { first, last })
+            }
+            Some(("placement", range)) => {
+                let (first, last) = parse_range(range).ok_or_else(invalid)?;
+                Ok(CohortSpec::Placement { first, last })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Parses a range as `<first>-<last>` or `<first>-`.
+///
+/// # Returns
+///
+/// An `Option` with the first and last positions, or `None` if the range is not valid: `first` must be at least
+/// `1` and `last`, if present, at least `first`.
+fn parse_range(range: &str) -> Option<(usize, Option<usize>)> {
+    let (first, last) = range.split_once('-')?;
+    let first = first.trim().parse::<usize>().ok()?;
+    let last = match last.trim() {
+        "" => None,
+        last => Some(last.parse::<usize>().ok()?),
+    };
+    if first == 0 || last.is_some_and(|last| last < first) {
+        return None;
+    }
+    Some((first, last))
+}
+
+impl fmt::Display for CohortSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CohortSpec::Rank { first, last } => match last {
+                Some(last) => write!(f, "rank:{}-{}", first, last),
+                None => write!(f, "rank:{}-", first),
+            },
+            CohortSpec::Placement { first, last } => match last {
+                Some(last) => write!(f, "placement:{}-{}", first, last),
+                None => write!(f, "placement:{}-", first),
+            },
+            CohortSpec::Players(path) => write!(f, "players:{}", path),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod chi_square;
+pub mod cohort_analyzer;
+pub mod cohort_error;
+pub mod cohort_report;
+pub mod cohort_spec;
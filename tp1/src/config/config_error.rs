@@ -0,0 +1,29 @@
+use std::fmt;
+
+/// Errors that may occur while building the processing configuration.
+///
+/// # Variants
+///
+/// * `FileRead` - A configuration file could not be read. Holds the path of the file.
+/// * `InvalidFormat` - A configuration file does not have the expected format. Holds the path of the file.
+/// * `InvalidOption` - A command-line option has an invalid value. Holds a description of the problem.
+#[derive(Debug)]
+pub enum ConfigError {
+    FileRead(String),
+    InvalidFormat(String),
+    InvalidOption(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::FileRead(path) => write!(f, "Could not read configuration file {}", path),
+            ConfigError::InvalidFormat(path) => {
+                write!(f, "Invalid format in configuration file {}", path)
+            }
+            ConfigError::InvalidOption(description) => write!(f, "{}", description),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,65 @@
+use crate::config::config_error::ConfigError;
+
+use std::collections::HashMap;
+use std::fs;
+
+/// Side length of the 8x8 km maps, in the units used by the position columns (centimeters).
+const LARGE_MAP_SIZE: f64 = 800_000.0;
+
+/// Maps sizes keyed by the value of the `map` column.
+///
+/// Every map is assumed to be a square whose coordinates go from `0` to its size.
+///
+/// # Fields
+///
+/// * `sizes` - A `HashMap` where keys are map names and values are the side length of the map.
+#[derive(Debug, Clone)]
+pub struct MapBounds {
+    sizes: HashMap<String, f64>,
+}
+
+impl Default for MapBounds {
+    fn default() -> Self {
+        let sizes = [
+            ("ERANGEL", LARGE_MAP_SIZE),
+            ("MIRAMAR", LARGE_MAP_SIZE),
+            ("VIKENDI", 600_000.0),
+            ("SANHOK", 400_000.0),
+        ]
+        .into_iter()
+        .map(|(map, size)| (map.to_owned(), size))
+        .collect();
+
+        MapBounds { sizes }
+    }
+}
+
+impl MapBounds {
+    /// Loads the map sizes from a JSON file containing an object of map names and sizes,
+    /// for example `{"ERANGEL": 800000, "SANHOK": 400000}`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the JSON file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the loaded `MapBounds`, or a `ConfigError` if the file cannot be read or parsed.
+    pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+        let content =
+            fs::read_to_string(path).map_err(|_| ConfigError::FileRead(path.to_owned()))?;
+        let sizes: HashMap<String, f64> = serde_json::from_str(&content)
+            .map_err(|_| ConfigError::InvalidFormat(path.to_owned()))?;
+
+        if sizes.values().any(|size| !size.is_finite() || *size <= 0.0) {
+            return Err(ConfigError::InvalidFormat(path.to_owned()));
+        }
+
+        Ok(MapBounds { sizes })
+    }
+
+    /// Returns the size of the given map, or `None` if the map is unknown.
+    pub fn size_of(&self, map: &str) -> Option<f64> {
+        self.sizes.get(map).copied()
+    }
+}
@@ -0,0 +1,10 @@
+pub mod config_error;
+pub mod map_bounds;
+pub mod outlier_policy;
+pub mod player_names;
+pub mod processing_config;
+pub mod processing_strategy;
+pub mod range_bands;
+pub mod sampling;
+pub mod text_encoding;
+pub mod weapon_taxonomy;
@@ -0,0 +1,39 @@
+use crate::config::config_error::ConfigError;
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// What to do with a row whose coordinates are not valid for its map.
+///
+/// # Variants
+///
+/// * `Drop` - The distance of the row is left out of the average distance.
+/// * `Clamp` - Out of bounds and negative coordinates are moved to the closest map border before computing the distance.
+/// * `Count` - The distance is averaged as is, the row is only counted as an outlier.
+///
+/// Regardless of the policy, rows with placeholder `(0, 0)` positions are never clamped and rows with
+/// non-finite coordinates are never averaged.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutlierPolicy {
+    Drop,
+    Clamp,
+    #[default]
+    Count,
+}
+
+impl FromStr for OutlierPolicy {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop" => Ok(OutlierPolicy::Drop),
+            "clamp" => Ok(OutlierPolicy::Clamp),
+            "count" => Ok(OutlierPolicy::Count),
+            _ => Err(ConfigError::InvalidOption(format!(
+                "Invalid outlier policy '{}', expected one of: drop, clamp, count.",
+                s
+            ))),
+        }
+    }
+}
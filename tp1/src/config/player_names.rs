@@ -0,0 +1,155 @@
+use crate::config::config_error::ConfigError;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+
+/// How player names are normalised before being used as keys.
+///
+/// # Fields
+///
+/// * `case` - Names are lowercased, so `GhostDL` and `ghostdl` are the same player.
+/// * `unicode` - Names are converted to their NFKC form, so compatible characters such as full-width letters
+///   match their plain version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NameNormalization {
+    pub(crate) case: bool,
+    pub(crate) unicode: bool,
+}
+
+impl FromStr for NameNormalization {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(NameNormalization::default()),
+            "case" => Ok(NameNormalization {
+                case: true,
+                unicode: false,
+            }),
+            "unicode" => Ok(NameNormalization {
+                case: false,
+                unicode: true,
+            }),
+            "all" => Ok(NameNormalization {
+                case: true,
+                unicode: true,
+            }),
+            _ => Err(ConfigError::InvalidOption(format!(
+                "Invalid name normalization '{}', expected one of: none, case, unicode, all.",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for NameNormalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match (self.case, self.unicode) {
+            (false, false) => "none",
+            (true, false) => "case",
+            (false, true) => "unicode",
+            (true, true) => "all",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl NameNormalization {
+    /// Returns `true` if no normalisation is applied.
+    fn is_none(&self) -> bool {
+        !self.case && !self.unicode
+    }
+
+    /// Normalises a name, borrowing it when no normalisation is applied.
+    fn apply<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let mut normalized = Cow::Borrowed(name);
+        if self.unicode {
+            normalized = Cow::Owned(normalized.nfkc().collect());
+        }
+        if self.case {
+            normalized = Cow::Owned(normalized.to_lowercase());
+        }
+        normalized
+    }
+}
+
+/// Resolves the names found in the dumps to the canonical name of each player.
+///
+/// A name is first normalised and then looked up in the aliases, which are normalised the same way. Names that
+/// are not aliases keep their normalised form.
+///
+/// # Fields
+///
+/// * `canonical_names` - A `HashMap` where keys are normalised aliases and values are canonical names.
+/// * `normalization` - The `NameNormalization` applied to every name.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerNames {
+    canonical_names: HashMap<String, String>,
+    normalization: NameNormalization,
+}
+
+impl PlayerNames {
+    /// Builds the resolver from an optional alias file and a normalisation.
+    ///
+    /// The alias file is a JSON object of canonical names and the list of their aliases,
+    /// for example `{"GhostDL": ["GhostDL_old", "ghost_dl"]}`.
+    ///
+    /// # Arguments
+    ///
+    /// * `aliases_path` - The path to the JSON alias file, if any.
+    /// * `normalization` - The `NameNormalization` applied to every name.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `PlayerNames`, or a `ConfigError` if the file cannot be read or parsed, or if an
+    /// alias is assigned to two different players.
+    pub fn new(
+        aliases_path: Option<&str>,
+        normalization: NameNormalization,
+    ) -> Result<Self, ConfigError> {
+        let mut player_names = PlayerNames {
+            canonical_names: HashMap::new(),
+            normalization,
+        };
+
+        let Some(path) = aliases_path else {
+            return Ok(player_names);
+        };
+
+        let content =
+            fs::read_to_string(path).map_err(|_| ConfigError::FileRead(path.to_owned()))?;
+        let aliases: HashMap<String, Vec<String>> = serde_json::from_str(&content)
+            .map_err(|_| ConfigError::InvalidFormat(path.to_owned()))?;
+
+        for (canonical_name, player_aliases) in aliases {
+            for alias in player_aliases.iter().chain([&canonical_name]) {
+                let key = normalization.apply(alias).into_owned();
+                let previous = player_names
+                    .canonical_names
+                    .insert(key, canonical_name.clone());
+                if previous.is_some_and(|previous| previous != canonical_name) {
+                    return Err(ConfigError::InvalidFormat(path.to_owned()));
+                }
+            }
+        }
+
+        Ok(player_names)
+    }
+
+    /// Returns the canonical name of a player, borrowing the given name when it does not change.
+    pub fn canonical_name<'a>(&'a self, name: &'a str) -> Cow<'a, str> {
+        if self.normalization.is_none() && self.canonical_names.is_empty() {
+            return Cow::Borrowed(name);
+        }
+
+        let normalized = self.normalization.apply(name);
+        match self.canonical_names.get(normalized.as_ref()) {
+            Some(canonical_name) => Cow::Borrowed(canonical_name),
+            None => normalized,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::config::map_bounds::MapBounds;
+use crate::config::outlier_policy::OutlierPolicy;
+use crate::config::player_names::PlayerNames;
+use crate::config::processing_strategy::ProcessingStrategy;
+use crate::config::range_bands::RangeBands;
+use crate::config::sampling::Sampling;
+use crate::config::text_encoding::TextEncoding;
+use crate::config::weapon_taxonomy::WeaponTaxonomy;
+
+/// Options that change how the death records are processed and summarized.
+///
+/// # Fields
+///
+/// * `map_bounds` - The size of each map, used to validate the positions of killers and victims.
+/// * `outlier_policy` - What to do with rows whose positions are not valid.
+/// * `weapon_taxonomy` - The canonical name and category of each weapon.
+/// * `player_names` - The canonical name of each player.
+/// * `track_matches` - Whether the stats of each match are collected. They use a lot of memory on big datasets.
+/// * `track_killer_placements` - Whether the player kills are also collected by the placement of the killer, for
+///   the placement cohorts.
+/// * `strategy` - How the files are spread over the threads and how their stats are combined.
+/// * `range_bands` - The limits of the range bands the kill distances are counted in.
+/// * `sampling` - The `Sampling` of the input, if only a fraction of it is processed.
+/// * `encoding` - How the lines of the CSV files are decoded.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessingConfig {
+    pub(crate) map_bounds: MapBounds,
+    pub(crate) outlier_policy: OutlierPolicy,
+    pub(crate) weapon_taxonomy: WeaponTaxonomy,
+    pub(crate) player_names: PlayerNames,
+    pub track_matches: bool,
+    pub track_killer_placements: bool,
+    pub strategy: ProcessingStrategy,
+    pub(crate) range_bands: RangeBands,
+    pub sampling: Option<Sampling>,
+    pub(crate) encoding: TextEncoding,
+}
@@ -0,0 +1,26 @@
+use clap::ValueEnum;
+
+/// How the files are spread over the threads and how their stats are combined.
+///
+/// Every strategy produces the same stats; they only differ in speed and memory use.
+///
+/// # Variants
+///
+/// * `Reduce` - Each file is processed into its own `DeathsInfo` and rayon merges them pairwise.
+/// * `LocalMaps` - Each rayon thread processes its files into a single local `DeathsInfo`, and the local results
+///   are merged at the end.
+/// * `Channel` - Worker threads take files from a shared queue and send the stats of each file over a channel to
+///   the calling thread, which merges them as they arrive.
+/// * `Pipeline` - Reader threads send batches of lines over a bounded channel to parser workers, which add the player
+///   stats to a sharded table shared by all of them, so no large maps are merged.
+/// * `Sharded` - Each rayon thread adds the player stats of its files to a sharded table shared by all the threads,
+///   so no large maps are merged.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProcessingStrategy {
+    #[default]
+    Reduce,
+    LocalMaps,
+    Channel,
+    Pipeline,
+    Sharded,
+}
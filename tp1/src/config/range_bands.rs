@@ -0,0 +1,95 @@
+use crate::config::config_error::ConfigError;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The range band of a kill, by the distance between the killer and the victim.
+///
+/// # Variants
+///
+/// * `Close` - Closer than the close range limit.
+/// * `Mid` - Between the close and the mid range limits.
+/// * `Long` - Between the mid and the long range limits.
+/// * `Extreme` - At the long range limit or farther.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeBand {
+    Close,
+    Mid,
+    Long,
+    Extreme,
+}
+
+/// Upper limits of the close, mid and long range bands, in the units used by the position columns (centimeters).
+///
+/// # Fields
+///
+/// * `close` - Kills closer than this distance are close range.
+/// * `mid` - Kills closer than this distance, and not close range, are mid range.
+/// * `long` - Kills closer than this distance, and not mid range, are long range. Any farther kill is extreme range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeBands {
+    pub(crate) close: f64,
+    pub(crate) mid: f64,
+    pub(crate) long: f64,
+}
+
+impl Default for RangeBands {
+    /// Returns the default limits: 50 m, 200 m and 500 m.
+    fn default() -> Self {
+        RangeBands {
+            close: 5_000.0,
+            mid: 20_000.0,
+            long: 50_000.0,
+        }
+    }
+}
+
+impl FromStr for RangeBands {
+    type Err = ConfigError;
+
+    /// Parses the limits from a comma-separated list of three increasing positive distances, for example
+    /// `5000,20000,50000`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            ConfigError::InvalidOption(format!(
+                "Invalid range bands '{}', expected three increasing positive distances such as 5000,20000,50000.",
+                s
+            ))
+        };
+
+        let limits = s
+            .split(',')
+            .map(|limit| limit.trim().parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        let [close, mid, long] = limits[..] else {
+            return Err(invalid());
+        };
+        if !(close > 0.0 && close < mid && mid < long && long.is_finite()) {
+            return Err(invalid());
+        }
+
+        Ok(RangeBands { close, mid, long })
+    }
+}
+
+impl fmt::Display for RangeBands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.close, self.mid, self.long)
+    }
+}
+
+impl RangeBands {
+    /// Returns the `RangeBand` of a kill at the given distance.
+    pub fn band_of(&self, distance: f64) -> RangeBand {
+        if distance < self.close {
+            RangeBand::Close
+        } else if distance < self.mid {
+            RangeBand::Mid
+        } else if distance < self.long {
+            RangeBand::Long
+        } else {
+            RangeBand::Extreme
+        }
+    }
+}
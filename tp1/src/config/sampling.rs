@@ -0,0 +1,93 @@
+use crate::config::config_error::ConfigError;
+
+use clap::ValueEnum;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// What is sampled when only a fraction of the input is processed.
+///
+/// # Variants
+///
+/// * `Rows` - Each row of every file is kept with the sampling probability.
+/// * `Files` - Each file is kept whole with the sampling probability. It is faster, but rows of the same file are
+///   not independent, so no confidence intervals are reported.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SampleUnit {
+    #[default]
+    Rows,
+    Files,
+}
+
+/// Processes a uniform random sample of the input instead of all of it.
+///
+/// Every decision is drawn from a `ChaCha8Rng` seeded with the SHA-256 of `seed`, the part of the file being read and
+/// the name of the file, so a run is reproducible whatever the number of threads, the strategy and the platform.
+///
+/// # Fields
+///
+/// * `fraction` - The probability of keeping each row or file, greater than `0` and at most `1`.
+/// * `unit` - The `SampleUnit` that is kept or skipped.
+/// * `seed` - The seed of the sampling.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Sampling {
+    pub(crate) fraction: f64,
+    pub(crate) unit: SampleUnit,
+    pub(crate) seed: u64,
+}
+
+impl Sampling {
+    /// Builds the sampling options, checking the fraction.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The probability of keeping each row or file.
+    /// * `unit` - The `SampleUnit` that is kept or skipped.
+    /// * `seed` - The seed of the sampling.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Sampling`, or a `ConfigError` if the fraction is not in `(0, 1]`.
+    pub fn new(fraction: f64, unit: SampleUnit, seed: u64) -> Result<Self, ConfigError> {
+        if !(fraction > 0.0 && fraction <= 1.0) {
+            return Err(ConfigError::InvalidOption(format!(
+                "Invalid sample fraction '{}', expected a number greater than 0 and at most 1.",
+                fraction
+            )));
+        }
+        Ok(Sampling {
+            fraction,
+            unit,
+            seed,
+        })
+    }
+
+    /// Returns the factor the counts of the sample are multiplied by to estimate the counts of the whole input.
+    pub(crate) fn scale(&self) -> f64 {
+        1.0 / self.fraction
+    }
+
+    /// Returns whether a file is processed. Files are always processed when rows are sampled.
+    pub(crate) fn keeps_file(&self, path: &Path) -> bool {
+        self.unit == SampleUnit::Rows || self.rng_for(path, 0).random_bool(self.fraction)
+    }
+
+    /// Returns the random number generator used to sample a part of a file, such as a row group.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file. Only its name is used, so moving the input does not change the sample.
+    /// * `part` - The index of the part of the file, `0` if the file is read as a whole.
+    pub(crate) fn rng_for(&self, path: &Path, part: u64) -> ChaCha8Rng {
+        let mut hasher = Sha256::new();
+        hasher.update(self.seed.to_le_bytes());
+        hasher.update(part.to_le_bytes());
+        if let Some(file_name) = path.file_name() {
+            hasher.update(file_name.as_encoded_bytes());
+        }
+        ChaCha8Rng::from_seed(hasher.finalize().into())
+    }
+}
@@ -0,0 +1,54 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Byte order mark that some tools write at the start of UTF-8 files.
+pub(crate) const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// How the bytes of the lines of the CSV files are decoded into text.
+///
+/// # Variants
+///
+/// * `Auto` - Lines that are valid UTF-8 are read as UTF-8 and any other line as Latin-1, so names from files in
+///   either encoding end up as the same text.
+/// * `Utf8Lossy` - Lines are read as UTF-8, replacing the invalid bytes with `U+FFFD`.
+/// * `Latin1` - Lines are read as Latin-1 (ISO-8859-1), where every byte is a character.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextEncoding {
+    #[default]
+    Auto,
+    Utf8Lossy,
+    Latin1,
+}
+
+impl TextEncoding {
+    /// Decodes a line, ignoring a byte order mark at its start.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the line, without the line end.
+    ///
+    /// # Returns
+    ///
+    /// The text of the line, borrowed from `bytes` when it is valid UTF-8 that does not need to be changed.
+    pub(crate) fn decode<'a>(&self, bytes: &'a [u8]) -> Cow<'a, str> {
+        let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+        match self {
+            TextEncoding::Auto => match std::str::from_utf8(bytes) {
+                Ok(text) => Cow::Borrowed(text),
+                Err(_) => Cow::Owned(decode_latin1(bytes)),
+            },
+            TextEncoding::Utf8Lossy => String::from_utf8_lossy(bytes),
+            TextEncoding::Latin1 if bytes.is_ascii() => {
+                Cow::Borrowed(std::str::from_utf8(bytes).unwrap_or_default())
+            }
+            TextEncoding::Latin1 => Cow::Owned(decode_latin1(bytes)),
+        }
+    }
+}
+
+/// Decodes Latin-1 bytes, whose values are the code points of their characters.
+fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| byte as char).collect()
+}
@@ -0,0 +1,94 @@
+use crate::config::config_error::ConfigError;
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+
+/// Category assigned to the weapons that are not in the mapping file.
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// An entry of the weapon mapping file.
+///
+/// # Fields
+///
+/// * `category` - The category of the weapon, for example `AR`, `SR`, `SMG`, `shotgun`, `melee` or `throwable`.
+/// * `aliases` - Other names used for the weapon in the dumps.
+#[derive(Deserialize, Debug)]
+struct WeaponMapping {
+    category: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+/// Maps the weapon names found in the dumps to a canonical name and a category.
+///
+/// Names are looked up exactly first and then ignoring case, so `m416` resolves to `M416` without listing it as
+/// an alias.
+///
+/// # Fields
+///
+/// * `canonical_names` - A `HashMap` where keys are lowercase names or aliases and values are canonical names.
+/// * `categories` - A `HashMap` where keys are canonical names and values are their categories.
+#[derive(Debug, Clone, Default)]
+pub struct WeaponTaxonomy {
+    canonical_names: HashMap<String, String>,
+    categories: HashMap<String, String>,
+}
+
+impl WeaponTaxonomy {
+    /// Loads the taxonomy from a JSON file containing an object of canonical names and their mapping,
+    /// for example `{"M416": {"category": "AR", "aliases": ["M-416"]}}`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the JSON file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the loaded `WeaponTaxonomy`, or a `ConfigError` if the file cannot be read or parsed,
+    /// or if an alias is assigned to two different weapons.
+    pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+        let content =
+            fs::read_to_string(path).map_err(|_| ConfigError::FileRead(path.to_owned()))?;
+        let mappings: HashMap<String, WeaponMapping> = serde_json::from_str(&content)
+            .map_err(|_| ConfigError::InvalidFormat(path.to_owned()))?;
+
+        let mut taxonomy = WeaponTaxonomy::default();
+        for (canonical_name, mapping) in mappings {
+            for name in mapping.aliases.iter().chain([&canonical_name]) {
+                let previous = taxonomy
+                    .canonical_names
+                    .insert(name.to_lowercase(), canonical_name.clone());
+                if previous.is_some_and(|previous| previous != canonical_name) {
+                    return Err(ConfigError::InvalidFormat(path.to_owned()));
+                }
+            }
+            taxonomy.categories.insert(canonical_name, mapping.category);
+        }
+
+        Ok(taxonomy)
+    }
+
+    /// Returns `true` if no mapping file was loaded.
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    /// Returns the canonical name of a weapon, or the given name if the weapon is not in the mapping.
+    pub fn canonical_name<'a>(&'a self, weapon: &'a str) -> &'a str {
+        if self.is_empty() || self.categories.contains_key(weapon) {
+            return weapon;
+        }
+
+        self.canonical_names
+            .get(&weapon.to_lowercase())
+            .map_or(weapon, String::as_str)
+    }
+
+    /// Returns the category of a canonical weapon name, or `UNCATEGORIZED` if the weapon is not in the mapping.
+    pub fn category_of(&self, canonical_name: &str) -> &str {
+        self.categories
+            .get(canonical_name)
+            .map_or(UNCATEGORIZED, String::as_str)
+    }
+}
@@ -0,0 +1,397 @@
+use crate::config::processing_config::ProcessingConfig;
+use crate::data_processing::data_processor::{merge_files_info, update_stats_from_record};
+use crate::data_processing::death_record::{placement_from_float, DeathRecord};
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::input_format::InputFormat;
+use crate::data_processing::position::Position;
+use crate::data_processing::row_sampler::RowSampler;
+use crate::data_processing::run_progress::RunProgress;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float64Type;
+use arrow_array::{new_null_array, Array, ArrayRef, Float64Array, RecordBatch, StringArray};
+use arrow_cast::cast;
+use arrow_ipc::reader::FileReader;
+use arrow_schema::{DataType, Schema};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ProjectionMask;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use std::fs::File;
+use std::mem::take;
+use std::path::PathBuf;
+
+const KILLED_BY: &str = "killed_by";
+const KILLER_NAME: &str = "killer_name";
+const KILLER_PLACEMENT: &str = "killer_placement";
+const KILLER_POSITION_X: &str = "killer_position_x";
+const KILLER_POSITION_Y: &str = "killer_position_y";
+const MAP: &str = "map";
+const MATCH_ID: &str = "match_id";
+const VICTIM_NAME: &str = "victim_name";
+const VICTIM_PLACEMENT: &str = "victim_placement";
+const VICTIM_POSITION_X: &str = "victim_position_x";
+const VICTIM_POSITION_Y: &str = "victim_position_y";
+
+/// Columns read from columnar files, named as in the header of the CSV files. The `time` column is not needed.
+const NEEDED_COLUMNS: [&str; 11] = [
+    KILLED_BY,
+    KILLER_NAME,
+    KILLER_PLACEMENT,
+    KILLER_POSITION_X,
+    KILLER_POSITION_Y,
+    MAP,
+    MATCH_ID,
+    VICTIM_NAME,
+    VICTIM_PLACEMENT,
+    VICTIM_POSITION_X,
+    VICTIM_POSITION_Y,
+];
+
+/// The columns of a record batch, cast to the types a `DeathRecord` is built from.
+///
+/// Columns missing from the file are all null, like missing trailing columns of a CSV line. Text columns are read
+/// as strings and numeric columns as `f64`, whatever their type in the file.
+struct DeathColumns {
+    killed_by: StringArray,
+    killer_name: StringArray,
+    killer_placement: Float64Array,
+    killer_position_x: Float64Array,
+    killer_position_y: Float64Array,
+    map: StringArray,
+    match_id: StringArray,
+    victim_name: StringArray,
+    victim_placement: Float64Array,
+    victim_position_x: Float64Array,
+    victim_position_y: Float64Array,
+}
+
+impl DeathColumns {
+    /// Casts the columns of a record batch.
+    ///
+    /// # Returns
+    ///
+    /// The `DeathColumns`, or an error message if the weapon or killer column is missing or a column cannot be cast.
+    fn from_batch(batch: &RecordBatch) -> Result<Self, String> {
+        for required in [KILLED_BY, KILLER_NAME] {
+            if batch.column_by_name(required).is_none() {
+                return Err(format!("Missing column {}", required));
+            }
+        }
+
+        Ok(DeathColumns {
+            killed_by: string_column(batch, KILLED_BY)?,
+            killer_name: string_column(batch, KILLER_NAME)?,
+            killer_placement: float_column(batch, KILLER_PLACEMENT)?,
+            killer_position_x: float_column(batch, KILLER_POSITION_X)?,
+            killer_position_y: float_column(batch, KILLER_POSITION_Y)?,
+            map: string_column(batch, MAP)?,
+            match_id: string_column(batch, MATCH_ID)?,
+            victim_name: string_column(batch, VICTIM_NAME)?,
+            victim_placement: float_column(batch, VICTIM_PLACEMENT)?,
+            victim_position_x: float_column(batch, VICTIM_POSITION_X)?,
+            victim_position_y: float_column(batch, VICTIM_POSITION_Y)?,
+        })
+    }
+
+    /// Builds the `DeathRecord` of a row, borrowing its text fields from the columns.
+    fn record(&self, row: usize) -> DeathRecord<'_> {
+        DeathRecord {
+            killed_by: string_value(&self.killed_by, row),
+            killer_name: string_value(&self.killer_name, row),
+            victim_name: string_value(&self.victim_name, row),
+            killer_placement: placement_value(&self.killer_placement, row),
+            victim_placement: placement_value(&self.victim_placement, row),
+            map: string_value(&self.map, row),
+            match_id: string_value(&self.match_id, row),
+            killer_position: position_value(&self.killer_position_x, &self.killer_position_y, row),
+            victim_position: position_value(&self.victim_position_x, &self.victim_position_y, row),
+        }
+    }
+}
+
+/// Reads a Parquet file, adding its stats to the given `DeathsInfo`.
+///
+/// Only the needed columns are read, and the row groups are processed in parallel with rayon.
+///
+/// # Arguments
+///
+/// * `path` - The path to the Parquet file.
+/// * `config` - The `ProcessingConfig` used to process the rows of the file.
+/// * `progress` - The `RunProgress` updated with the rows read.
+/// * `deaths_info` - The `DeathsInfo` the stats of the file are added to.
+pub(crate) fn read_parquet_into(
+    path: &PathBuf,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+    deaths_info: &mut DeathsInfo,
+) {
+    let builder = match File::open(path)
+        .map_err(|err| err.to_string())
+        .and_then(|file| {
+            ParquetRecordBatchReaderBuilder::try_new(file).map_err(|err| err.to_string())
+        }) {
+        Ok(builder) => builder,
+        Err(err) => {
+            eprintln!("Error opening file {}: {}", path.display(), err);
+            return;
+        }
+    };
+    let mask = ProjectionMask::roots(builder.parquet_schema(), needed_indices(builder.schema()));
+    let row_groups = builder.metadata().num_row_groups();
+
+    let file_deaths_info = (0..row_groups)
+        .into_par_iter()
+        .map(|row_group| {
+            let mut local_deaths_info = DeathsInfo::default();
+            if let Err(err) = read_parquet_row_group(
+                path,
+                row_group,
+                mask.clone(),
+                config,
+                progress,
+                &mut local_deaths_info,
+            ) {
+                eprintln!(
+                    "Error reading row group {} in file {}: {}",
+                    row_group,
+                    path.display(),
+                    err
+                );
+            }
+            local_deaths_info
+        })
+        .reduce(DeathsInfo::default, merge_files_info);
+
+    if !progress.is_cancelled() {
+        progress.record_file();
+    }
+    *deaths_info = merge_files_info(take(deaths_info), file_deaths_info);
+}
+
+/// Reads a row group of a Parquet file, adding its stats to the given `DeathsInfo`.
+///
+/// # Returns
+///
+/// An error message if the row group cannot be read.
+fn read_parquet_row_group(
+    path: &PathBuf,
+    row_group: usize,
+    mask: ProjectionMask,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+    deaths_info: &mut DeathsInfo,
+) -> Result<(), String> {
+    let builder =
+        ParquetRecordBatchReaderBuilder::try_new(File::open(path).map_err(|err| err.to_string())?)
+            .map_err(|err| err.to_string())?;
+    let bytes = builder.metadata().row_group(row_group).compressed_size() as u64;
+    let reader = builder
+        .with_projection(mask)
+        .with_row_groups(vec![row_group])
+        .build()
+        .map_err(|err| err.to_string())?;
+
+    let mut sampler = RowSampler::new(config.sampling.as_ref(), path, row_group as u64);
+    for batch in reader {
+        if progress.is_cancelled() {
+            return Ok(());
+        }
+        let batch = batch.map_err(|err| err.to_string())?;
+        update_stats_from_batch(&batch, config, &mut sampler, deaths_info)?;
+        progress.record_rows(batch.num_rows() as u64, 0);
+    }
+    progress.record_rows(0, bytes);
+    Ok(())
+}
+
+/// Reads an Arrow IPC file, adding its stats to the given `DeathsInfo`.
+///
+/// Only the needed columns are read, and the record batches are processed in parallel with rayon.
+///
+/// # Arguments
+///
+/// * `path` - The path to the Arrow IPC file.
+/// * `config` - The `ProcessingConfig` used to process the rows of the file.
+/// * `progress` - The `RunProgress` updated with the rows read.
+/// * `deaths_info` - The `DeathsInfo` the stats of the file are added to.
+pub(crate) fn read_arrow_ipc_into(
+    path: &PathBuf,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+    deaths_info: &mut DeathsInfo,
+) {
+    let (projection, batches) = match open_arrow_ipc(path, None) {
+        Ok(reader) => (needed_indices(&reader.schema()), reader.num_batches()),
+        Err(err) => {
+            eprintln!("Error opening file {}: {}", path.display(), err);
+            return;
+        }
+    };
+
+    let file_deaths_info = (0..batches)
+        .into_par_iter()
+        .map(|index| {
+            let mut local_deaths_info = DeathsInfo::default();
+            if progress.is_cancelled() {
+                return local_deaths_info;
+            }
+            let batch = open_arrow_ipc(path, Some(projection.clone())).and_then(|mut reader| {
+                reader.set_index(index).map_err(|err| err.to_string())?;
+                match reader.next() {
+                    Some(batch) => batch.map_err(|err| err.to_string()),
+                    None => Err("Missing record batch".to_owned()),
+                }
+            });
+            let result = batch.and_then(|batch| {
+                let mut sampler = RowSampler::new(config.sampling.as_ref(), path, index as u64);
+                update_stats_from_batch(&batch, config, &mut sampler, &mut local_deaths_info)?;
+                progress.record_rows(batch.num_rows() as u64, 0);
+                Ok(())
+            });
+            if let Err(err) = result {
+                eprintln!(
+                    "Error reading record batch {} in file {}: {}",
+                    index,
+                    path.display(),
+                    err
+                );
+            }
+            local_deaths_info
+        })
+        .reduce(DeathsInfo::default, merge_files_info);
+
+    if let Ok(metadata) = path.metadata() {
+        progress.record_rows(0, metadata.len());
+    }
+    if !progress.is_cancelled() {
+        progress.record_file();
+    }
+    *deaths_info = merge_files_info(take(deaths_info), file_deaths_info);
+}
+
+/// Opens an Arrow IPC file, reading only the given columns.
+fn open_arrow_ipc(
+    path: &PathBuf,
+    projection: Option<Vec<usize>>,
+) -> Result<FileReader<std::io::BufReader<File>>, String> {
+    let file = File::open(path).map_err(|err| err.to_string())?;
+    FileReader::try_new_buffered(file, projection).map_err(|err| err.to_string())
+}
+
+/// Adds the stats of every row of a record batch kept by the `RowSampler` to the given `DeathsInfo`.
+///
+/// # Returns
+///
+/// An error message if the columns of the batch cannot be read.
+fn update_stats_from_batch(
+    batch: &RecordBatch,
+    config: &ProcessingConfig,
+    sampler: &mut RowSampler,
+    deaths_info: &mut DeathsInfo,
+) -> Result<(), String> {
+    let columns = DeathColumns::from_batch(batch)?;
+    for row in 0..batch.num_rows() {
+        if sampler.keeps_row() {
+            update_stats_from_record(columns.record(row), deaths_info, config);
+        }
+    }
+    Ok(())
+}
+
+/// Reads the schema and counts the rows of a Parquet or Arrow IPC file, without reading its values.
+///
+/// # Arguments
+///
+/// * `path` - The path to the columnar file.
+/// * `format` - The `InputFormat` of the file.
+///
+/// # Returns
+///
+/// A `Result` with the number of rows and the needed columns missing from the file, or an error message if the
+/// file cannot be read.
+pub(crate) fn inspect_columnar_file(
+    path: &PathBuf,
+    format: InputFormat,
+) -> Result<(u64, Vec<&'static str>), String> {
+    let (rows, schema) = if format == InputFormat::Parquet {
+        let file = File::open(path).map_err(|err| err.to_string())?;
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new(file).map_err(|err| err.to_string())?;
+        let rows = builder.metadata().file_metadata().num_rows() as u64;
+        (rows, builder.schema().clone())
+    } else {
+        let reader = open_arrow_ipc(path, Some(Vec::new()))?;
+        let schema = open_arrow_ipc(path, None)?.schema();
+        let mut rows = 0;
+        for batch in reader {
+            rows += batch.map_err(|err| err.to_string())?.num_rows() as u64;
+        }
+        (rows, schema)
+    };
+
+    let missing = NEEDED_COLUMNS
+        .into_iter()
+        .filter(|name| schema.field_with_name(name).is_err())
+        .collect();
+    Ok((rows, missing))
+}
+
+/// Returns the indices of the needed columns in a schema.
+fn needed_indices(schema: &Schema) -> Vec<usize> {
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| NEEDED_COLUMNS.contains(&field.name().as_str()))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns a column of a batch cast to the given type, or an all-null column if the batch does not have it.
+fn cast_column(batch: &RecordBatch, name: &str, data_type: &DataType) -> Result<ArrayRef, String> {
+    match batch.column_by_name(name) {
+        Some(column) => cast(column, data_type).map_err(|err| format!("Column {}: {}", name, err)),
+        None => Ok(new_null_array(data_type, batch.num_rows())),
+    }
+}
+
+/// Returns a column of a batch as strings.
+fn string_column(batch: &RecordBatch, name: &str) -> Result<StringArray, String> {
+    Ok(cast_column(batch, name, &DataType::Utf8)?
+        .as_string::<i32>()
+        .clone())
+}
+
+/// Returns a column of a batch as `f64` values.
+fn float_column(batch: &RecordBatch, name: &str) -> Result<Float64Array, String> {
+    Ok(cast_column(batch, name, &DataType::Float64)?
+        .as_primitive::<Float64Type>()
+        .clone())
+}
+
+/// Returns the value of a row of a string column, or an empty string if it is null.
+fn string_value(column: &StringArray, row: usize) -> &str {
+    if column.is_null(row) {
+        ""
+    } else {
+        column.value(row)
+    }
+}
+
+/// Returns the value of a row of a float column, or `None` if it is null.
+fn float_value(column: &Float64Array, row: usize) -> Option<f64> {
+    (!column.is_null(row)).then(|| column.value(row))
+}
+
+/// Returns the placement of a row, applying the same rules as for the placement columns of the CSV files.
+fn placement_value(column: &Float64Array, row: usize) -> Option<u32> {
+    float_value(column, row).and_then(placement_from_float)
+}
+
+/// Returns the position of a row, or `None` if one of its coordinates is null.
+fn position_value(x: &Float64Array, y: &Float64Array, row: usize) -> Option<Position> {
+    Some(Position {
+        x: float_value(x, row)?,
+        y: float_value(y, row)?,
+    })
+}
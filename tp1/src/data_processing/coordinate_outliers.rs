@@ -0,0 +1,45 @@
+use crate::data_processing::position::PositionIssue;
+
+/// Counters of the rows whose positions did not pass the validation against the bounds of their map.
+///
+/// Each row is counted once, using the first issue found in the killer or victim position.
+///
+/// # Fields
+///
+/// * `non_finite` - Rows with a `NaN` or infinite coordinate.
+/// * `placeholder` - Rows with a placeholder `(0, 0)` position.
+/// * `negative` - Rows with a negative coordinate.
+/// * `out_of_bounds` - Rows with a coordinate greater than the size of the map.
+/// * `excluded` - Rows whose distance was left out of the average distance.
+/// * `clamped` - Rows whose positions were clamped to the map before computing the distance.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CoordinateOutliers {
+    pub(crate) non_finite: u32,
+    pub(crate) placeholder: u32,
+    pub(crate) negative: u32,
+    pub(crate) out_of_bounds: u32,
+    pub(crate) excluded: u32,
+    pub(crate) clamped: u32,
+}
+
+impl CoordinateOutliers {
+    /// Counts a row with the given issue.
+    pub(crate) fn record(&mut self, issue: PositionIssue) {
+        match issue {
+            PositionIssue::NonFinite => self.non_finite += 1,
+            PositionIssue::Placeholder => self.placeholder += 1,
+            PositionIssue::Negative => self.negative += 1,
+            PositionIssue::OutOfBounds => self.out_of_bounds += 1,
+        }
+    }
+
+    /// Adds the counters of another `CoordinateOutliers` to these ones.
+    pub(crate) fn merge(&mut self, other: CoordinateOutliers) {
+        self.non_finite += other.non_finite;
+        self.placeholder += other.placeholder;
+        self.negative += other.negative;
+        self.out_of_bounds += other.out_of_bounds;
+        self.excluded += other.excluded;
+        self.clamped += other.clamped;
+    }
+}
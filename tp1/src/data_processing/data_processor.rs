@@ -1,14 +1,39 @@
+use crate::config::outlier_policy::OutlierPolicy;
+use crate::config::processing_config::ProcessingConfig;
+use crate::config::processing_strategy::ProcessingStrategy;
+use crate::data_processing::columnar_reader::{read_arrow_ipc_into, read_parquet_into};
+use crate::data_processing::coordinate_outliers::CoordinateOutliers;
+use crate::data_processing::death_category::DeathCategory;
+use crate::data_processing::death_record::DeathRecord;
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::input_format::InputFormat;
+use crate::data_processing::line_reader::LineReader;
+use crate::data_processing::match_stats::{LongestKill, MatchStats};
+use crate::data_processing::pipeline_processor::pipeline_files;
+use crate::data_processing::placement_stats::PlacementStats;
 use crate::data_processing::player_stats::PlayerStats;
+use crate::data_processing::position::{Position, PositionIssue};
+use crate::data_processing::row_sampler::RowSampler;
+use crate::data_processing::run_progress::RunProgress;
+use crate::data_processing::sharded_accumulator::{ShardedAccumulator, SHARDS_PER_THREAD};
 use crate::data_processing::weapon_stats::WeaponStats;
 use crate::data_summary::data_summarizer::summarize;
 use crate::data_summary::deaths_info_summary::DeathsInfoSummary;
 
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
-use rayon::ThreadPoolBuilder;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::collections::HashMap;
-use std::fs::{read_dir, File};
-use std::io::{BufRead, BufReader};
+use std::fs::{metadata, read_dir, File};
+use std::io::BufReader;
+use std::mem::take;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+/// Number of rows read between two updates of the run progress.
+const ROWS_PER_PROGRESS_UPDATE: u64 = 10_000;
 
 /// Processes data in parallel from CSV files in the given directory.
 ///
@@ -16,63 +41,278 @@ use std::path::PathBuf;
 ///
 /// * `dir_path` - The directory containing CSV files to process.
 /// * `num_threads` - The number of threads to use for parallel processing.
+/// * `config` - The `ProcessingConfig` used to process and summarize the data.
+/// * `progress` - The `RunProgress` updated while the files are processed and summarized.
 ///
 /// # Returns
 ///
 /// A `DeathsInfoSummary` containing the summarized deaths information.
-pub fn process_data_in_parallel(dir_path: &str, num_threads: usize) -> DeathsInfoSummary {
-    summarize(process_directory(dir_path, num_threads))
+pub fn process_data_in_parallel(
+    dir_path: &str,
+    num_threads: usize,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfoSummary {
+    let deaths_info = process_directory(dir_path, num_threads, config, progress);
+    progress.finish();
+
+    let start = Instant::now();
+    let summary = summarize(&deaths_info, config);
+    progress.add_summarizing(start.elapsed());
+    summary
 }
 
 /// Processes CSV files in the specified directory using a thread pool and aggregates player stats.
 ///
 /// This function creates a thread pool with the specified number of threads to process CSV files in parallel. If an error occurs
-/// while creating the thread pool or reading the directory, an error message is printed, and an empty `HashMap` is returned.
+/// while creating the thread pool or reading the directory, an error message is printed, and an empty `DeathsInfo` is returned.
+/// If the run is cancelled, the files not processed yet are skipped and the stats gathered so far are returned.
 ///
 /// # Arguments
 ///
 /// * `dir_path` - The directory containing CSV files to process.
 /// * `num_threads` - The number of threads to use for parallel processing.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `progress` - The `RunProgress` updated while the files are processed.
 ///
 /// # Returns
 ///
-/// A `HashMap` where keys are player names and values are their respective `PlayerStats`.
-fn process_directory(dir_path: &str, num_threads: usize) -> HashMap<String, PlayerStats> {
+/// A `DeathsInfo` with the aggregated stats of all the files.
+pub fn process_directory(
+    dir_path: &str,
+    num_threads: usize,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
     let thread_pool = match ThreadPoolBuilder::new().num_threads(num_threads).build() {
         Ok(pool) => pool,
         Err(err) => {
             eprintln!("Error creating thread pool: {}", err);
-            return HashMap::new();
+            return DeathsInfo::default();
         }
     };
 
-    let deaths_info = thread_pool.install(|| {
-        let paths: Vec<PathBuf> = collect_csv_files(dir_path);
+    let start = Instant::now();
+    let paths: Vec<PathBuf> = collect_input_files(dir_path);
+    let total_bytes = paths
+        .iter()
+        .filter_map(|path| metadata(path).ok())
+        .map(|file_metadata| file_metadata.len())
+        .sum();
+    progress.set_totals(paths.len(), total_bytes);
+    progress.add_listing(start.elapsed());
 
-        paths
-            .par_iter()
-            .map(process_file)
-            .reduce(HashMap::new, merge_files_info)
-    });
+    process_files(&paths, &thread_pool, num_threads, config, progress)
+}
+
+/// Processes the given files with the strategy of the configuration and aggregates their stats.
+///
+/// # Arguments
+///
+/// * `paths` - The paths of the files to process.
+/// * `thread_pool` - The `ThreadPool` the files are processed in.
+/// * `num_threads` - The number of threads of the pool.
+/// * `config` - The `ProcessingConfig` with the strategy used to process the files.
+/// * `progress` - The `RunProgress` updated while the files are processed.
+///
+/// # Returns
+///
+/// A `DeathsInfo` with the aggregated stats of the files.
+pub(crate) fn process_files(
+    paths: &[PathBuf],
+    thread_pool: &ThreadPool,
+    num_threads: usize,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    // The channel and pipeline strategies run their own threads, which hand the columnar files to the pool. They
+    // are not started from a thread of the pool, which would wait for them without taking any of that work.
+    match config.strategy {
+        ProcessingStrategy::Reduce => thread_pool.install(|| reduce_files(paths, config, progress)),
+        ProcessingStrategy::LocalMaps => {
+            thread_pool.install(|| fold_files(paths, config, progress))
+        }
+        ProcessingStrategy::Channel => {
+            send_files(paths, thread_pool, num_threads, config, progress)
+        }
+        ProcessingStrategy::Pipeline => {
+            pipeline_files(paths, thread_pool, num_threads, config, progress)
+        }
+        ProcessingStrategy::Sharded => {
+            thread_pool.install(|| shard_files(paths, num_threads, config, progress))
+        }
+    }
+}
+
+/// Processes each file into its own `DeathsInfo` and merges them pairwise with rayon.
+///
+/// # Arguments
+///
+/// * `paths` - The paths of the CSV files.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `progress` - The `RunProgress` updated while the files are processed.
+///
+/// # Returns
+///
+/// A `DeathsInfo` with the aggregated stats of all the files.
+fn reduce_files(
+    paths: &[PathBuf],
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    paths
+        .par_iter()
+        .map(|path| process_file(path, config, progress))
+        .reduce(
+            DeathsInfo::default,
+            |final_deaths_info, local_deaths_info| {
+                timed_merge(final_deaths_info, local_deaths_info, progress)
+            },
+        )
+}
 
+/// Processes the files of each rayon thread into a single local `DeathsInfo` and merges the local results.
+///
+/// # Arguments
+///
+/// * `paths` - The paths of the CSV files.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `progress` - The `RunProgress` updated while the files are processed.
+///
+/// # Returns
+///
+/// A `DeathsInfo` with the aggregated stats of all the files.
+fn fold_files(paths: &[PathBuf], config: &ProcessingConfig, progress: &RunProgress) -> DeathsInfo {
+    paths
+        .par_iter()
+        .fold(DeathsInfo::default, |mut local_deaths_info, path| {
+            read_file_into(path, config, progress, &mut local_deaths_info);
+            local_deaths_info
+        })
+        .reduce(
+            DeathsInfo::default,
+            |final_deaths_info, local_deaths_info| {
+                timed_merge(final_deaths_info, local_deaths_info, progress)
+            },
+        )
+}
+
+/// Processes the files in worker threads that send the stats of each file over a channel to the calling thread,
+/// which merges them as they arrive.
+///
+/// # Arguments
+///
+/// * `paths` - The paths of the CSV files.
+/// * `thread_pool` - The `ThreadPool` of the run, where the row groups of Parquet and Arrow IPC files are processed.
+/// * `num_threads` - The number of worker threads.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `progress` - The `RunProgress` updated while the files are processed.
+///
+/// # Returns
+///
+/// A `DeathsInfo` with the aggregated stats of all the files.
+fn send_files(
+    paths: &[PathBuf],
+    thread_pool: &ThreadPool,
+    num_threads: usize,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    let next_file = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..num_threads.max(1) {
+            let sender = sender.clone();
+            let next_file = &next_file;
+            scope.spawn(move || {
+                while let Some(path) = paths.get(next_file.fetch_add(1, Ordering::Relaxed)) {
+                    let file_deaths_info = process_file_in(thread_pool, path, config, progress);
+                    if sender.send(file_deaths_info).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        receiver.into_iter().fold(
+            DeathsInfo::default(),
+            |final_deaths_info, local_deaths_info| {
+                timed_merge(final_deaths_info, local_deaths_info, progress)
+            },
+        )
+    })
+}
+
+/// Processes the files with rayon, adding the player stats of each file to a `ShardedAccumulator` shared by all
+/// the threads. Only the rest of the stats, which are small, are merged pairwise.
+///
+/// # Arguments
+///
+/// * `paths` - The paths of the CSV files.
+/// * `num_threads` - The number of threads writing to the accumulator.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `progress` - The `RunProgress` updated while the files are processed.
+///
+/// # Returns
+///
+/// A `DeathsInfo` with the aggregated stats of all the files.
+fn shard_files(
+    paths: &[PathBuf],
+    num_threads: usize,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    let accumulator = ShardedAccumulator::new(num_threads * SHARDS_PER_THREAD);
+
+    let mut deaths_info = paths
+        .par_iter()
+        .fold(DeathsInfo::default, |mut local_deaths_info, path| {
+            read_file_into(path, config, progress, &mut local_deaths_info);
+            let start = Instant::now();
+            accumulator.add_players(take(&mut local_deaths_info.players));
+            progress.add_merging(start.elapsed());
+            local_deaths_info
+        })
+        .reduce(
+            DeathsInfo::default,
+            |final_deaths_info, local_deaths_info| {
+                timed_merge(final_deaths_info, local_deaths_info, progress)
+            },
+        );
+
+    deaths_info.players = accumulator.into_players();
     deaths_info
 }
 
-/// Collects all CSV files from the specified directory.
+/// Merges two `DeathsInfo`, adding the time spent to the merging phase of the run.
+fn timed_merge(
+    final_deaths_info: DeathsInfo,
+    local_deaths_info: DeathsInfo,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    let start = Instant::now();
+    let merged = merge_files_info(final_deaths_info, local_deaths_info);
+    progress.add_merging(start.elapsed());
+    merged
+}
+
+/// Collects all input files from the specified directory: CSV, Parquet and Arrow IPC files.
 ///
 /// # Arguments
 ///
-/// * `dir_path` - The directory containing CSV files.
+/// * `dir_path` - The directory containing the input files.
 ///
 /// # Returns
 ///
-/// A `Vec<PathBuf>` containing the paths to all CSV files found in the directory.
-fn collect_csv_files(dir_path: &str) -> Vec<PathBuf> {
+/// A `Vec<PathBuf>` containing the paths to all input files found in the directory.
+pub(crate) fn collect_input_files(dir_path: &str) -> Vec<PathBuf> {
     match read_dir(dir_path) {
         Ok(dir) => dir
             .flatten()
             .map(|d| d.path())
-            .filter(|path| path.extension().map_or(false, |ext| ext == "csv"))
+            .filter(|path| InputFormat::of_path(path).is_some())
             .collect(),
         Err(err) => {
             eprintln!("Error reading directory {}: {}", dir_path, err);
@@ -81,40 +321,148 @@ fn collect_csv_files(dir_path: &str) -> Vec<PathBuf> {
     }
 }
 
-/// Processes a single CSV file and aggregates player stats.
+/// Processes a single input file and aggregates player stats.
 ///
 /// This function reads the specified CSV file, skipping the first line which contains the format definition,
 /// and updates player statistics based on the data. If an error occurs while opening the file, an error message
-/// is printed, and an empty `HashMap` is returned.
+/// is printed, and an empty `DeathsInfo` is returned. If the run is cancelled, the file stops being read and the
+/// stats of the rows read so far are returned.
 ///
 /// # Arguments
 ///
 /// * `path` - The path to the CSV file.
+/// * `config` - The `ProcessingConfig` used to process the lines of the file.
+/// * `progress` - The `RunProgress` updated with the rows read.
 ///
 /// # Returns
 ///
-/// A `HashMap` where keys are player names and values are their respective `PlayerStats`.
-fn process_file(path: &PathBuf) -> HashMap<String, PlayerStats> {
-    let mut local_deaths_info = HashMap::new();
+/// A `DeathsInfo` with the aggregated stats of the file.
+pub(crate) fn process_file(
+    path: &PathBuf,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    let mut local_deaths_info = DeathsInfo::default();
+    read_file_into(path, config, progress, &mut local_deaths_info);
+    local_deaths_info
+}
 
+/// Processes a single input file from a thread outside the pool of the run. Parquet and Arrow IPC files are
+/// processed in the pool, so that their row groups use the threads of the run instead of the global rayon pool.
+///
+/// # Arguments
+///
+/// * `thread_pool` - The `ThreadPool` of the run.
+/// * `path` - The path to the CSV, Parquet or Arrow IPC file.
+/// * `config` - The `ProcessingConfig` used to process the rows of the file.
+/// * `progress` - The `RunProgress` updated with the rows read.
+///
+/// # Returns
+///
+/// A `DeathsInfo` with the aggregated stats of the file.
+pub(crate) fn process_file_in(
+    thread_pool: &ThreadPool,
+    path: &PathBuf,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    match InputFormat::of_path(path) {
+        Some(InputFormat::Parquet | InputFormat::ArrowIpc) => {
+            thread_pool.install(|| process_file(path, config, progress))
+        }
+        _ => process_file(path, config, progress),
+    }
+}
+
+/// Reads a single input file, adding its stats to the given `DeathsInfo`. If whole files are sampled and the file
+/// is not in the sample, it is counted as processed without being read.
+///
+/// # Arguments
+///
+/// * `path` - The path to the CSV, Parquet or Arrow IPC file.
+/// * `config` - The `ProcessingConfig` used to process the rows of the file.
+/// * `progress` - The `RunProgress` updated with the rows read.
+/// * `deaths_info` - The `DeathsInfo` the stats of the file are added to.
+fn read_file_into(
+    path: &PathBuf,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+    deaths_info: &mut DeathsInfo,
+) {
+    if progress.is_cancelled() {
+        return;
+    }
+    if let Some(sampling) = &config.sampling {
+        if !sampling.keeps_file(path) {
+            progress.record_file();
+            return;
+        }
+    }
+    progress.record_read_file(path);
+    let start = Instant::now();
+
+    match InputFormat::of_path(path) {
+        Some(InputFormat::Parquet) => read_parquet_into(path, config, progress, deaths_info),
+        Some(InputFormat::ArrowIpc) => read_arrow_ipc_into(path, config, progress, deaths_info),
+        _ => read_csv_into(path, config, progress, deaths_info),
+    }
+
+    progress.add_parsing(start.elapsed());
+}
+
+/// Reads a single CSV file, adding its stats to the given `DeathsInfo`. The lines are decoded with the encoding of
+/// the config, and reading stops at the first line that cannot be read. If rows are sampled, only the rows in the
+/// sample are processed, but every row is counted as read.
+///
+/// # Arguments
+///
+/// * `path` - The path to the CSV file.
+/// * `config` - The `ProcessingConfig` used to process the lines of the file.
+/// * `progress` - The `RunProgress` updated with the rows read.
+/// * `deaths_info` - The `DeathsInfo` the stats of the file are added to.
+fn read_csv_into(
+    path: &PathBuf,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+    deaths_info: &mut DeathsInfo,
+) {
     match File::open(path) {
         Ok(file) => {
-            let reader = BufReader::new(file);
-            for line in reader.lines().skip(1) {
+            let mut lines = LineReader::new(BufReader::new(file), config.encoding);
+            let mut sampler = RowSampler::new(config.sampling.as_ref(), path, 0);
+            let (mut rows, mut bytes) = (0, 0);
+            lines.next_line();
+            while let Some(line) = lines.next_line() {
                 match line {
-                    Ok(line_content) => {
-                        update_stats_from_line(&line_content, &mut local_deaths_info)
+                    Ok((line_content, line_bytes)) => {
+                        bytes += line_bytes;
+                        if sampler.keeps_row() {
+                            update_stats_from_line(&line_content, deaths_info, config)
+                        }
+                    }
+                    Err(err) => {
+                        eprintln!("Error reading line in file {}: {}", path.display(), err);
+                        break;
+                    }
+                }
+                rows += 1;
+                if rows == ROWS_PER_PROGRESS_UPDATE {
+                    progress.record_rows(rows, bytes);
+                    (rows, bytes) = (0, 0);
+                    if progress.is_cancelled() {
+                        break;
                     }
-                    Err(err) => eprintln!("Error reading line in file {}: {}", path.display(), err),
                 }
             }
+            progress.record_rows(rows, bytes);
+            if !progress.is_cancelled() {
+                progress.record_file();
+            }
         }
         Err(err) => {
             eprintln!("Error opening file {}: {}", path.display(), err);
         }
     }
-
-    local_deaths_info
 }
 
 /// Updates player statistics based on a single line from a CSV file.
@@ -122,117 +470,323 @@ fn process_file(path: &PathBuf) -> HashMap<String, PlayerStats> {
 /// # Arguments
 ///
 /// * `line_content` - The content of a line from the CSV file.
-/// * `local_deaths_info` - A mutable reference to the `HashMap` where player statistics are being updated.
-fn update_stats_from_line(
+/// * `local_deaths_info` - A mutable reference to the `DeathsInfo` being updated.
+/// * `config` - The `ProcessingConfig` used to normalise the player and weapon names and validate the positions of the line.
+pub(crate) fn update_stats_from_line(
     line_content: &str,
-    local_deaths_info: &mut HashMap<String, PlayerStats>,
+    local_deaths_info: &mut DeathsInfo,
+    config: &ProcessingConfig,
 ) {
-    let mut fields = line_content.split(',');
-
-    if let (Some(weapon_name), Some(killer_name)) = (fields.next(), fields.next()) {
-        let player_stats = local_deaths_info
-            .entry(killer_name.to_owned())
-            .or_insert_with(|| PlayerStats {
-                used_weapons: HashMap::new(),
-                deaths: 0,
-            });
-        player_stats.deaths += 1;
-
-        let weapon_stats = player_stats
-            .used_weapons
-            .entry(weapon_name.to_owned())
-            .or_insert_with(|| WeaponStats {
-                deaths: 0,
-                valid_distances_count: 0,
-                total_distance: 0.0,
+    if let Some(record) = DeathRecord::parse(line_content) {
+        update_stats_from_record(record, local_deaths_info, config);
+    }
+}
+
+/// Updates player statistics based on a single death record, whatever the format of the file it was read from.
+///
+/// Player names are resolved to their canonical name, so kills made under any alias are added to the same player.
+/// Player kills are added to the stats of the killer. Any other death is tallied by its category and its weapon
+/// stats are kept apart from the players, so that it still counts towards the weapon totals.
+///
+/// # Arguments
+///
+/// * `record` - The `DeathRecord` of the death.
+/// * `local_deaths_info` - A mutable reference to the `DeathsInfo` being updated.
+/// * `config` - The `ProcessingConfig` used to normalise the player and weapon names and validate the positions of the record.
+pub(crate) fn update_stats_from_record(
+    record: DeathRecord,
+    local_deaths_info: &mut DeathsInfo,
+    config: &ProcessingConfig,
+) {
+    let killer_name = config.player_names.canonical_name(record.killer_name);
+    let victim_name = config.player_names.canonical_name(record.victim_name);
+    let record = DeathRecord {
+        killer_name: &killer_name,
+        victim_name: &victim_name,
+        ..record
+    };
+
+    let weapon_name = config.weapon_taxonomy.canonical_name(record.killed_by);
+    let category = DeathCategory::classify(&record, weapon_name);
+    update_placement_stats(
+        &record,
+        category,
+        weapon_name,
+        &mut local_deaths_info.placement_stats,
+    );
+
+    let weapons = match category {
+        DeathCategory::PlayerKill => {
+            let player_stats = local_deaths_info
+                .players
+                .entry(record.killer_name.to_owned())
+                .or_insert_with(|| PlayerStats {
+                    used_weapons: HashMap::new(),
+                    deaths: 0,
+                });
+            player_stats.deaths += 1;
+            &mut player_stats.used_weapons
+        }
+        category => {
+            local_deaths_info.special_deaths.record(category);
+            &mut local_deaths_info.unattributed_weapons
+        }
+    };
+
+    let weapon_stats = weapons.entry(weapon_name.to_owned()).or_default();
+    weapon_stats.deaths += 1;
+
+    let distance = update_weapon_distance_stats(
+        &record,
+        weapon_stats,
+        &mut local_deaths_info.coordinate_outliers,
+        config,
+    );
+
+    if let (true, DeathCategory::PlayerKill, Some(placement)) = (
+        config.track_killer_placements,
+        category,
+        record.killer_placement,
+    ) {
+        local_deaths_info
+            .killer_placements
+            .entry(placement)
+            .or_default()
+            .record_kill(&record, weapon_name, distance, &config.range_bands);
+    }
+
+    if config.track_matches && !record.match_id.is_empty() {
+        let match_stats = local_deaths_info
+            .matches
+            .entry(record.match_id.to_owned())
+            .or_default();
+        update_match_stats(&record, category, weapon_name, distance, match_stats);
+    }
+}
+
+/// Updates the stats of the match of a death record.
+///
+/// # Arguments
+///
+/// * `record` - The `DeathRecord` of the current line.
+/// * `category` - The `DeathCategory` of the record.
+/// * `weapon_name` - The canonical name of the weapon.
+/// * `distance` - The validated distance of the kill, if any.
+/// * `match_stats` - A mutable reference to the `MatchStats` of the match of the record.
+fn update_match_stats(
+    record: &DeathRecord,
+    category: DeathCategory,
+    weapon_name: &str,
+    distance: Option<f64>,
+    match_stats: &mut MatchStats,
+) {
+    match_stats.record_death(record.map, weapon_name);
+
+    if let (Some(placement), false) = (record.killer_placement, record.killer_name.is_empty()) {
+        match_stats.record_placement(record.killer_name, placement);
+    }
+    if let (Some(placement), false) = (record.victim_placement, record.victim_name.is_empty()) {
+        match_stats.record_placement(record.victim_name, placement);
+    }
+
+    if category == DeathCategory::PlayerKill {
+        match_stats.record_kill(record.killer_name);
+        if let Some(distance) = distance {
+            match_stats.record_longest_kill(LongestKill {
+                killer: record.killer_name.to_owned(),
+                victim: record.victim_name.to_owned(),
+                weapon: weapon_name.to_owned(),
+                distance,
             });
-        weapon_stats.deaths += 1;
+        }
+    }
+}
 
-        update_weapon_distance_stats(&mut fields, weapon_stats);
+/// Updates the placement statistics based on a death record.
+///
+/// The placement of the killer is only counted for player kills, while the placement of the victim is counted for
+/// every death.
+///
+/// # Arguments
+///
+/// * `record` - The `DeathRecord` of the current line.
+/// * `category` - The `DeathCategory` of the record.
+/// * `weapon_name` - The canonical name of the weapon.
+/// * `placement_stats` - A mutable reference to the `PlacementStats` being updated.
+fn update_placement_stats(
+    record: &DeathRecord,
+    category: DeathCategory,
+    weapon_name: &str,
+    placement_stats: &mut PlacementStats,
+) {
+    if let Some(victim_placement) = record.victim_placement {
+        placement_stats.record_victim(victim_placement, weapon_name);
+    }
+
+    if let Some(killer_placement) = record.killer_placement {
+        if category == DeathCategory::PlayerKill {
+            placement_stats.record_kill(killer_placement, weapon_name);
+        }
     }
 }
 
-/// Updates the weapon distance statistics based on remaining fields in a line from a CSV file.
+/// Updates the weapon distance statistics based on the positions of a death record, counting the distance in
+/// its range band on the map of the record.
 ///
 /// # Arguments
 ///
-/// * `fields` - The remaining fields from a CSV line, after extracting the weapon and killer name.
+/// * `record` - The `DeathRecord` of the current line.
 /// * `weapon_stats` - A mutable reference to the `WeaponStats` for the current weapon.
+/// * `coordinate_outliers` - A mutable reference to the counters of rows with invalid positions.
+/// * `config` - The `ProcessingConfig` with the map bounds, the outlier policy and the range bands.
+///
+/// # Returns
+///
+/// An `Option<f64>` with the distance added to the stats, or `None` if the record has no valid distance.
 fn update_weapon_distance_stats(
-    fields: &mut std::str::Split<char>,
+    record: &DeathRecord,
     weapon_stats: &mut WeaponStats,
-) {
-    if let (Some(killer_x_str), Some(killer_y_str), Some(victim_x_str), Some(victim_y_str)) =
-        (fields.nth(1), fields.next(), fields.nth(5), fields.next())
-    {
-        if let Some(distance) =
-            calculate_distance(killer_x_str, killer_y_str, victim_x_str, victim_y_str)
-        {
-            weapon_stats.total_distance += distance;
-            weapon_stats.valid_distances_count += 1;
-        }
-    }
+    coordinate_outliers: &mut CoordinateOutliers,
+    config: &ProcessingConfig,
+) -> Option<f64> {
+    let distance = calculate_distance(
+        record.killer_position?,
+        record.victim_position?,
+        config.map_bounds.size_of(record.map),
+        config.outlier_policy,
+        coordinate_outliers,
+    )?;
+
+    weapon_stats.record_distance(record.map, distance, config.range_bands.band_of(distance));
+    Some(distance)
 }
 
-/// Calculates the distance between the killer and the victim.
+/// Calculates the distance between the killer and the victim, validating both positions against the map bounds.
+///
+/// Rows with an invalid position are counted in `coordinate_outliers` and handled according to `outlier_policy`.
 ///
 /// # Arguments
 ///
-/// * `killer_x_str`, `killer_y_str`, `victim_x_str`, `victim_y_str` - Strings representing the coordinates of the killer and victim.
+/// * `killer_position`, `victim_position` - The positions of the killer and victim.
+/// * `map_size` - The size of the map where the death happened, or `None` if the map is unknown.
+/// * `outlier_policy` - What to do with the row if one of the positions is not valid.
+/// * `coordinate_outliers` - A mutable reference to the counters of rows with invalid positions.
 ///
 /// # Returns
 ///
-/// An `Option<f64>` representing the distance if all coordinates are valid, otherwise `None`.
+/// An `Option<f64>` representing the distance if it should be averaged, otherwise `None`.
 fn calculate_distance(
-    killer_x_str: &str,
-    killer_y_str: &str,
-    victim_x_str: &str,
-    victim_y_str: &str,
+    killer_position: Position,
+    victim_position: Position,
+    map_size: Option<f64>,
+    outlier_policy: OutlierPolicy,
+    coordinate_outliers: &mut CoordinateOutliers,
 ) -> Option<f64> {
-    let killer_x: f64 = killer_x_str.parse().ok()?;
-    let killer_y: f64 = killer_y_str.parse().ok()?;
-    let victim_x: f64 = victim_x_str.parse().ok()?;
-    let victim_y: f64 = victim_y_str.parse().ok()?;
+    let issue = killer_position
+        .check(map_size)
+        .or_else(|| victim_position.check(map_size));
 
-    Some(((killer_x - victim_x).powi(2) + (killer_y - victim_y).powi(2)).sqrt())
+    let Some(issue) = issue else {
+        return Some(killer_position.distance_to(&victim_position));
+    };
+    coordinate_outliers.record(issue);
+
+    match (outlier_policy, issue) {
+        (_, PositionIssue::NonFinite)
+        | (OutlierPolicy::Drop, _)
+        | (OutlierPolicy::Clamp, PositionIssue::Placeholder) => {
+            coordinate_outliers.excluded += 1;
+            None
+        }
+        (OutlierPolicy::Clamp, _) => {
+            coordinate_outliers.clamped += 1;
+            Some(
+                killer_position
+                    .clamp(map_size)
+                    .distance_to(&victim_position.clamp(map_size)),
+            )
+        }
+        (OutlierPolicy::Count, _) => Some(killer_position.distance_to(&victim_position)),
+    }
 }
 
-/// Merges local file player stats into the final aggregated stats.
+/// Merges local file deaths info into the final aggregated deaths info.
 ///
-/// This function updates the final player statistics by combining them with the player statistics from a local file.
+/// This function updates the final player statistics, weapon statistics and counters by combining them with the ones from a local file.
 ///
 /// # Arguments
 ///
-/// * `final_deaths_info` - The final aggregated player stats.
-/// * `local_deaths_info` - The player stats from the current file.
+/// * `final_deaths_info` - The final aggregated deaths info.
+/// * `local_deaths_info` - The deaths info from the current file.
 ///
 /// # Returns
 ///
 /// The updated `final_deaths_info` with merged stats.
-fn merge_files_info(
-    mut final_deaths_info: HashMap<String, PlayerStats>,
-    local_deaths_info: HashMap<String, PlayerStats>,
-) -> HashMap<String, PlayerStats> {
+pub(crate) fn merge_files_info(
+    mut final_deaths_info: DeathsInfo,
+    local_deaths_info: DeathsInfo,
+) -> DeathsInfo {
+    final_deaths_info
+        .coordinate_outliers
+        .merge(local_deaths_info.coordinate_outliers);
+    final_deaths_info
+        .special_deaths
+        .merge(local_deaths_info.special_deaths);
+    final_deaths_info
+        .placement_stats
+        .merge(local_deaths_info.placement_stats);
+    for (placement, local_placement_stats) in local_deaths_info.killer_placements {
+        final_deaths_info
+            .killer_placements
+            .entry(placement)
+            .or_default()
+            .merge(local_placement_stats);
+    }
+    for (match_id, local_match_stats) in local_deaths_info.matches {
+        final_deaths_info
+            .matches
+            .entry(match_id)
+            .or_default()
+            .merge(local_match_stats);
+    }
+    merge_weapon_stats(
+        &mut final_deaths_info.unattributed_weapons,
+        local_deaths_info.unattributed_weapons,
+    );
+
     local_deaths_info
+        .players
         .into_iter()
         .for_each(|(player, local_player_stats)| {
-            let final_player_stats =
-                final_deaths_info
-                    .entry(player)
-                    .or_insert_with(|| PlayerStats {
-                        used_weapons: HashMap::new(),
-                        deaths: 0,
-                    });
-
-            final_player_stats.deaths += local_player_stats.deaths;
-            merge_weapon_stats(
-                &mut final_player_stats.used_weapons,
-                local_player_stats.used_weapons,
-            );
+            merge_player_stats(&mut final_deaths_info.players, player, local_player_stats)
         });
     final_deaths_info
 }
 
+/// Merges the stats of a player into the final aggregated player stats.
+///
+/// # Arguments
+///
+/// * `final_players` - A mutable reference to the final aggregated player stats.
+/// * `player` - The name of the player.
+/// * `local_player_stats` - The stats of the player to add.
+pub(crate) fn merge_player_stats(
+    final_players: &mut HashMap<String, PlayerStats>,
+    player: String,
+    local_player_stats: PlayerStats,
+) {
+    let final_player_stats = final_players.entry(player).or_insert_with(|| PlayerStats {
+        used_weapons: HashMap::new(),
+        deaths: 0,
+    });
+
+    final_player_stats.deaths += local_player_stats.deaths;
+    merge_weapon_stats(
+        &mut final_player_stats.used_weapons,
+        local_player_stats.used_weapons,
+    );
+}
+
 /// Merges weapon stats from a local file into the final aggregated weapon stats.
 ///
 /// # Arguments
@@ -245,15 +799,12 @@ fn merge_weapon_stats(
 ) {
     local_weapons
         .into_iter()
-        .for_each(|(weapon, local_weapon_stats)| {
-            let final_weapon_stats = final_weapons.entry(weapon).or_insert_with(|| WeaponStats {
-                deaths: 0,
-                valid_distances_count: 0,
-                total_distance: 0.0,
-            });
-
-            final_weapon_stats.deaths += local_weapon_stats.deaths;
-            final_weapon_stats.total_distance += local_weapon_stats.total_distance;
-            final_weapon_stats.valid_distances_count += local_weapon_stats.valid_distances_count;
-        });
+        .for_each(
+            |(weapon, local_weapon_stats)| match final_weapons.get_mut(&weapon) {
+                Some(final_weapon_stats) => final_weapon_stats.merge(&local_weapon_stats),
+                None => {
+                    final_weapons.insert(weapon, local_weapon_stats);
+                }
+            },
+        );
 }
@@ -0,0 +1,110 @@
+use crate::data_processing::death_record::DeathRecord;
+
+/// Name used in the dumps for players whose name is not known.
+const UNKNOWN_PLAYER: &str = "#unknown";
+
+/// Canonical weapon names of deaths caused by a vehicle. Without a killer the vehicle exploded, with one it is a
+/// roadkill or a kill with a vehicle.
+const VEHICLES: [&str; 10] = [
+    "Aquarail",
+    "Boat",
+    "Buggy",
+    "Dacia",
+    "Motorbike",
+    "Motorbike (SideCar)",
+    "PG-117",
+    "Pickup Truck",
+    "Uaz",
+    "Van",
+];
+
+/// The cause of a death that was not caused by a player.
+///
+/// # Variants
+///
+/// * `BlueZone` - The victim was outside of the blue zone.
+/// * `Falling` - The victim died from a fall.
+/// * `Drowning` - The victim drowned.
+/// * `VehicleExplosion` - The victim died in a vehicle explosion, with no player as the killer.
+/// * `Other` - Any other death without a killer, for example the red zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvironmentCause {
+    BlueZone,
+    Falling,
+    Drowning,
+    VehicleExplosion,
+    Other,
+}
+
+/// The category of a death, which decides where it is tallied.
+///
+/// # Variants
+///
+/// * `PlayerKill` - A player killed a player of another team. Only these deaths count as kills in the player stats.
+/// * `Environment` - The death was caused by the environment, see `EnvironmentCause`.
+/// * `Suicide` - The killer and the victim are the same player.
+/// * `TeamKill` - The killer and the victim are teammates. The dumps have no team column, so two players are
+///   considered teammates when they got the same final placement, as every member of a team shares it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeathCategory {
+    PlayerKill,
+    Environment(EnvironmentCause),
+    Suicide,
+    TeamKill,
+}
+
+impl DeathCategory {
+    /// Classifies a death record.
+    ///
+    /// The cause of the death is checked first, so deaths by the blue zone, falling or drowning are never counted
+    /// as suicides or team kills even when they have a killer name. Deaths by a vehicle are only environment deaths
+    /// when they have no killer, since a player driving it is the killer of a roadkill.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The `DeathRecord` to classify.
+    /// * `weapon_name` - The canonical name of the weapon of the record, so aliases of the causes are recognised.
+    ///
+    /// # Returns
+    ///
+    /// The `DeathCategory` of the record.
+    pub(crate) fn classify(record: &DeathRecord, weapon_name: &str) -> Self {
+        match environment_cause(weapon_name) {
+            Some(EnvironmentCause::VehicleExplosion) if !record.killer_name.is_empty() => {}
+            Some(cause) => return DeathCategory::Environment(cause),
+            None => {}
+        }
+
+        if record.killer_name.is_empty() {
+            return DeathCategory::Environment(EnvironmentCause::Other);
+        }
+
+        if record.killer_name == record.victim_name && record.killer_name != UNKNOWN_PLAYER {
+            return DeathCategory::Suicide;
+        }
+
+        match (record.killer_placement, record.victim_placement) {
+            (Some(killer_placement), Some(victim_placement))
+                if killer_placement == victim_placement =>
+            {
+                DeathCategory::TeamKill
+            }
+            _ => DeathCategory::PlayerKill,
+        }
+    }
+}
+
+/// Finds the environment cause of a death from the canonical name of its weapon.
+///
+/// # Returns
+///
+/// An `Option<EnvironmentCause>` with the cause, or `None` if the death was caused by a weapon.
+fn environment_cause(weapon_name: &str) -> Option<EnvironmentCause> {
+    match weapon_name {
+        "Bluezone" => Some(EnvironmentCause::BlueZone),
+        "Falling" => Some(EnvironmentCause::Falling),
+        "Drown" => Some(EnvironmentCause::Drowning),
+        _ if VEHICLES.contains(&weapon_name) => Some(EnvironmentCause::VehicleExplosion),
+        _ => None,
+    }
+}
@@ -0,0 +1,122 @@
+use crate::data_processing::position::Position;
+
+/// Number of columns in the deaths CSV files.
+const COLUMN_COUNT: usize = 12;
+
+const KILLED_BY: usize = 0;
+const KILLER_NAME: usize = 1;
+const KILLER_PLACEMENT: usize = 2;
+const KILLER_POSITION_X: usize = 3;
+const KILLER_POSITION_Y: usize = 4;
+const MAP: usize = 5;
+const MATCH_ID: usize = 6;
+const VICTIM_NAME: usize = 8;
+const VICTIM_PLACEMENT: usize = 9;
+const VICTIM_POSITION_X: usize = 10;
+const VICTIM_POSITION_Y: usize = 11;
+
+/// Names of the columns in the header of the deaths CSV files, in order.
+pub(crate) const COLUMN_NAMES: [&str; COLUMN_COUNT] = [
+    "killed_by",
+    "killer_name",
+    "killer_placement",
+    "killer_position_x",
+    "killer_position_y",
+    "map",
+    "match_id",
+    "time",
+    "victim_name",
+    "victim_placement",
+    "victim_position_x",
+    "victim_position_y",
+];
+
+/// A single row of a deaths CSV file, borrowing its text fields from the line it was parsed from.
+///
+/// The columns of the files are `killed_by, killer_name, killer_placement, killer_position_x, killer_position_y,
+/// map, match_id, time, victim_name, victim_placement, victim_position_x, victim_position_y`.
+///
+/// # Fields
+///
+/// * `killed_by` - The weapon or cause of the death.
+/// * `killer_name` - The name of the killer, empty when the death was not caused by a player.
+/// * `victim_name` - The name of the victim.
+/// * `killer_placement` - The final placement of the killer in the match, if it is a valid placement.
+/// * `victim_placement` - The final placement of the victim in the match, if it is a valid placement.
+/// * `map` - The map where the death happened.
+/// * `match_id` - The identifier of the match where the death happened.
+/// * `killer_position` - The position of the killer, if both of its coordinates are valid `f64` values.
+/// * `victim_position` - The position of the victim, if both of its coordinates are valid `f64` values.
+#[derive(Debug)]
+pub struct DeathRecord<'a> {
+    pub(crate) killed_by: &'a str,
+    pub(crate) killer_name: &'a str,
+    pub(crate) victim_name: &'a str,
+    pub(crate) killer_placement: Option<u32>,
+    pub(crate) victim_placement: Option<u32>,
+    pub(crate) map: &'a str,
+    pub(crate) match_id: &'a str,
+    pub(crate) killer_position: Option<Position>,
+    pub(crate) victim_position: Option<Position>,
+}
+
+impl<'a> DeathRecord<'a> {
+    /// Parses a line of a deaths CSV file.
+    ///
+    /// Missing trailing columns are treated as empty.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<DeathRecord>` with the parsed row, or `None` if the line does not have at least the weapon and killer columns.
+    pub(crate) fn parse(line_content: &'a str) -> Option<Self> {
+        let mut columns = [""; COLUMN_COUNT];
+        let mut found_columns = 0;
+        for (column, field) in columns.iter_mut().zip(line_content.split(',')) {
+            *column = field;
+            found_columns += 1;
+        }
+
+        if found_columns <= KILLER_NAME {
+            return None;
+        }
+
+        Some(DeathRecord {
+            killed_by: columns[KILLED_BY],
+            killer_name: columns[KILLER_NAME],
+            victim_name: columns[VICTIM_NAME],
+            killer_placement: parse_placement(columns[KILLER_PLACEMENT]),
+            victim_placement: parse_placement(columns[VICTIM_PLACEMENT]),
+            map: columns[MAP],
+            match_id: columns[MATCH_ID],
+            killer_position: Position::parse(
+                columns[KILLER_POSITION_X],
+                columns[KILLER_POSITION_Y],
+            ),
+            victim_position: Position::parse(
+                columns[VICTIM_POSITION_X],
+                columns[VICTIM_POSITION_Y],
+            ),
+        })
+    }
+}
+
+/// Parses a placement column. The dumps store placements as floats, for example `12.0`.
+///
+/// # Returns
+///
+/// An `Option<u32>` with the placement if it is a finite number greater than or equal to `1`, otherwise `None`.
+fn parse_placement(placement_str: &str) -> Option<u32> {
+    placement_str
+        .parse::<f64>()
+        .ok()
+        .and_then(placement_from_float)
+}
+
+/// Converts a placement stored as a float, as it is in the CSV dumps and in the columnar files, to a placement.
+///
+/// # Returns
+///
+/// An `Option<u32>` with the placement if it is a finite number greater than or equal to `1`, otherwise `None`.
+pub(crate) fn placement_from_float(placement: f64) -> Option<u32> {
+    (placement.is_finite() && placement >= 1.0).then_some(placement as u32)
+}
@@ -0,0 +1,36 @@
+use crate::data_processing::coordinate_outliers::CoordinateOutliers;
+use crate::data_processing::killer_placement_stats::KillerPlacementStats;
+use crate::data_processing::match_stats::MatchStats;
+use crate::data_processing::placement_stats::PlacementStats;
+use crate::data_processing::player_stats::PlayerStats;
+use crate::data_processing::special_deaths::SpecialDeaths;
+use crate::data_processing::weapon_stats::WeaponStats;
+
+use std::collections::HashMap;
+
+/// Aggregated information of the deaths read from one or more files.
+///
+/// # Fields
+///
+/// * `players` - A `HashMap` where keys are killer names and values are their respective `PlayerStats`.
+///   Only player kills are counted here.
+/// * `unattributed_weapons` - A `HashMap` where keys are weapon names and values are the `WeaponStats` of the
+///   deaths that are not player kills, such as environment deaths, suicides and team kills.
+/// * `special_deaths` - Counters of the deaths that are not player kills, by category.
+/// * `placement_stats` - Statistics of the kills by the final placement of the killer and the victim.
+/// * `coordinate_outliers` - Counters of the rows whose positions were not valid.
+/// * `killer_placements` - A `HashMap` where keys are killer placements and values are the `KillerPlacementStats`
+///   of the player kills made with that placement. It is only filled when tracking killer placements is enabled in
+///   the `ProcessingConfig`.
+/// * `matches` - A `HashMap` where keys are match identifiers and values are their `MatchStats`.
+///   It is only filled when tracking matches is enabled in the `ProcessingConfig`.
+#[derive(Debug, Default)]
+pub struct DeathsInfo {
+    pub(crate) players: HashMap<String, PlayerStats>,
+    pub(crate) unattributed_weapons: HashMap<String, WeaponStats>,
+    pub(crate) special_deaths: SpecialDeaths,
+    pub(crate) placement_stats: PlacementStats,
+    pub(crate) coordinate_outliers: CoordinateOutliers,
+    pub(crate) killer_placements: HashMap<u32, KillerPlacementStats>,
+    pub(crate) matches: HashMap<String, MatchStats>,
+}
@@ -0,0 +1,117 @@
+use crate::config::processing_config::ProcessingConfig;
+use crate::data_processing::data_processor::{
+    collect_input_files, merge_files_info, process_files,
+};
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::run_progress::RunProgress;
+
+use rayon::ThreadPoolBuilder;
+use std::collections::{HashMap, HashSet};
+use std::fs::metadata;
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Watches a directory and keeps the aggregated stats up to date as new CSV files arrive.
+///
+/// The directory is polled every `poll_interval` and each file is processed once its size has not changed between
+/// two polls, so files that are still being written are not read halfway. This includes the files already in the
+/// directory, which are processed on the second scan. The new files are processed with the strategy of the
+/// configuration, their stats are merged into the retained `DeathsInfo` and `on_update` is called with the result
+/// and the new files that were read, which leaves out the files outside the sample.
+///
+/// # Arguments
+///
+/// * `dir_path` - The directory to watch.
+/// * `num_threads` - The number of threads used to process the new files.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `poll_interval` - The time between two scans of the directory.
+/// * `on_update` - A function called with the aggregated `DeathsInfo` and the paths of the new files read after the
+///   second scan and after every later scan that found new files. Watching stops when it returns
+///   `ControlFlow::Break`.
+///
+/// # Returns
+///
+/// `Ok(())` once `on_update` stops the watch, or an error message if the thread pool cannot be created.
+pub fn watch_directory(
+    dir_path: &str,
+    num_threads: usize,
+    config: &ProcessingConfig,
+    poll_interval: Duration,
+    mut on_update: impl FnMut(&DeathsInfo, &[PathBuf]) -> ControlFlow<()>,
+) -> Result<(), String> {
+    let thread_pool = ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .map_err(|err| format!("Error creating thread pool: {}", err))?;
+
+    let mut deaths_info = DeathsInfo::default();
+    let mut processed_files = HashSet::new();
+    let mut pending_sizes = HashMap::new();
+    let mut first_scan = true;
+    let mut wrote_initial_output = false;
+
+    loop {
+        let ready_files = find_ready_files(dir_path, &processed_files, &mut pending_sizes);
+
+        // The first scan only records the sizes of the files, so the second one always writes the outputs, even if
+        // the directory was empty. Later scans only write them when they found new files.
+        if !first_scan && (!wrote_initial_output || !ready_files.is_empty()) {
+            wrote_initial_output = true;
+            let progress = RunProgress::default();
+            let new_deaths_info =
+                process_files(&ready_files, &thread_pool, num_threads, config, &progress);
+            deaths_info = merge_files_info(deaths_info, new_deaths_info);
+
+            eprintln!("Processed {} new files.", ready_files.len());
+            let flow = on_update(&deaths_info, &progress.read_files());
+            processed_files.extend(ready_files);
+            if flow.is_break() {
+                return Ok(());
+            }
+        }
+
+        first_scan = false;
+        sleep(poll_interval);
+    }
+}
+
+/// Finds the CSV files of the directory that are ready to be processed.
+///
+/// A file is ready if it was not processed yet and its size is the same as in the previous scan.
+///
+/// # Arguments
+///
+/// * `dir_path` - The directory to scan.
+/// * `processed_files` - The files that were already processed.
+/// * `pending_sizes` - A mutable reference to the sizes of the new files found in the previous scan.
+///
+/// # Returns
+///
+/// A `Vec<PathBuf>` with the files that are ready to be processed.
+fn find_ready_files(
+    dir_path: &str,
+    processed_files: &HashSet<PathBuf>,
+    pending_sizes: &mut HashMap<PathBuf, u64>,
+) -> Vec<PathBuf> {
+    let mut ready_files = Vec::new();
+
+    for path in collect_input_files(dir_path) {
+        if processed_files.contains(&path) {
+            continue;
+        }
+
+        let Ok(size) = metadata(&path).map(|metadata| metadata.len()) else {
+            continue;
+        };
+        if pending_sizes.get(&path) == Some(&size) {
+            pending_sizes.remove(&path);
+            ready_files.push(path);
+        } else {
+            pending_sizes.insert(path, size);
+        }
+    }
+
+    ready_files
+}
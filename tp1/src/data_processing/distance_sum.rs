@@ -0,0 +1,27 @@
+/// Fixed-point units in a centimeter of a `DistanceSum`.
+const UNITS_PER_CENTIMETER: f64 = 1_000_000.0;
+
+/// Sum of kill distances, kept as an integer number of millionths of a centimeter.
+///
+/// Adding floating point numbers gives a slightly different result depending on the order they are added in, and
+/// every strategy and thread count merges the stats of the files in a different order. Rounding each distance to a
+/// fixed-point integer once makes the sum the same whatever the order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DistanceSum(u128);
+
+impl DistanceSum {
+    /// Adds a distance, in centimeters, to the sum.
+    pub(crate) fn add(&mut self, distance: f64) {
+        self.0 += (distance * UNITS_PER_CENTIMETER).round() as u128;
+    }
+
+    /// Adds another `DistanceSum` to this one.
+    pub(crate) fn merge(&mut self, other: &DistanceSum) {
+        self.0 += other.0;
+    }
+
+    /// Returns the sum in centimeters.
+    pub(crate) fn centimeters(&self) -> f64 {
+        self.0 as f64 / UNITS_PER_CENTIMETER
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::Path;
+
+/// The formats of the input files, recognised by their extension.
+///
+/// # Variants
+///
+/// * `Csv` - A deaths CSV file, `.csv`.
+/// * `Parquet` - A Parquet file with the columns of the CSV files, `.parquet` or `.pq`.
+/// * `ArrowIpc` - An Arrow IPC file with the columns of the CSV files, `.arrow`, `.ipc` or `.feather`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    Csv,
+    Parquet,
+    ArrowIpc,
+}
+
+impl InputFormat {
+    /// Finds the format of a file from its extension.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<InputFormat>`, or `None` if the file is not an input file.
+    pub fn of_path(path: &Path) -> Option<InputFormat> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(InputFormat::Csv),
+            "parquet" | "pq" => Some(InputFormat::Parquet),
+            "arrow" | "ipc" | "feather" => Some(InputFormat::ArrowIpc),
+            _ => None,
+        }
+    }
+}
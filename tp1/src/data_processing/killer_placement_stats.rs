@@ -0,0 +1,47 @@
+use crate::config::range_bands::RangeBands;
+use crate::data_processing::death_record::DeathRecord;
+use crate::data_processing::weapon_stats::WeaponStats;
+
+use std::collections::{HashMap, HashSet};
+
+/// Player kills made by the killers that finished a match with the same placement.
+///
+/// # Fields
+///
+/// * `killers` - The names of the players that made a kill with this placement.
+/// * `weapons` - A `HashMap` where keys are weapon names and values are the `WeaponStats` of the kills made with
+///   each weapon.
+#[derive(Debug, Default)]
+pub struct KillerPlacementStats {
+    pub(crate) killers: HashSet<String>,
+    pub(crate) weapons: HashMap<String, WeaponStats>,
+}
+
+impl KillerPlacementStats {
+    /// Counts a player kill made with the given weapon, and its distance if it is valid.
+    pub(crate) fn record_kill(
+        &mut self,
+        record: &DeathRecord,
+        weapon: &str,
+        distance: Option<f64>,
+        range_bands: &RangeBands,
+    ) {
+        if !self.killers.contains(record.killer_name) {
+            self.killers.insert(record.killer_name.to_owned());
+        }
+
+        let weapon_stats = self.weapons.entry(weapon.to_owned()).or_default();
+        weapon_stats.deaths += 1;
+        if let Some(distance) = distance {
+            weapon_stats.record_distance(record.map, distance, range_bands.band_of(distance));
+        }
+    }
+
+    /// Adds the kills of another `KillerPlacementStats` to these ones.
+    pub(crate) fn merge(&mut self, other: KillerPlacementStats) {
+        self.killers.extend(other.killers);
+        for (weapon, stats) in other.weapons {
+            self.weapons.entry(weapon).or_default().merge(&stats);
+        }
+    }
+}
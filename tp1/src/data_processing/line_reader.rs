@@ -0,0 +1,54 @@
+use crate::config::text_encoding::TextEncoding;
+
+use std::borrow::Cow;
+use std::io::{self, BufRead};
+
+/// Reads the lines of a CSV file as bytes and decodes them with a `TextEncoding`, so a line that is not valid
+/// UTF-8 is still read instead of failing.
+///
+/// # Fields
+///
+/// * `reader` - The reader of the file.
+/// * `encoding` - The `TextEncoding` of the lines.
+/// * `buffer` - The bytes of the last line read, reused between lines.
+#[derive(Debug)]
+pub struct LineReader<R: BufRead> {
+    reader: R,
+    encoding: TextEncoding,
+    buffer: Vec<u8>,
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// Builds a reader of the lines of a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader of the file.
+    /// * `encoding` - The `TextEncoding` of the lines.
+    pub(crate) fn new(reader: R, encoding: TextEncoding) -> Self {
+        LineReader {
+            reader,
+            encoding,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Reads the next line, without its `\n` or `\r\n` line end.
+    ///
+    /// # Returns
+    ///
+    /// `None` at the end of the file, or a `Result` with the decoded line and the number of bytes it took in the file,
+    /// or the `io::Error` if it could not be read.
+    pub(crate) fn next_line(&mut self) -> Option<io::Result<(Cow<'_, str>, u64)>> {
+        self.buffer.clear();
+        match self.reader.read_until(b'\n', &mut self.buffer) {
+            Ok(0) => None,
+            Ok(bytes) => {
+                let line = self.buffer.strip_suffix(b"\n").unwrap_or(&self.buffer);
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                Some(Ok((self.encoding.decode(line), bytes as u64)))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
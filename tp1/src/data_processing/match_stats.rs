@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+/// The kill with the longest distance of a match.
+///
+/// # Fields
+///
+/// * `killer` - The name of the killer.
+/// * `victim` - The name of the victim.
+/// * `weapon` - The name of the weapon.
+/// * `distance` - The distance between the killer and the victim.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LongestKill {
+    pub(crate) killer: String,
+    pub(crate) victim: String,
+    pub(crate) weapon: String,
+    pub(crate) distance: f64,
+}
+
+impl LongestKill {
+    /// Returns `true` if this kill should replace `other` as the longest one.
+    ///
+    /// Ties are broken by the names, so the result does not depend on the order in which the files are merged.
+    fn is_longer_than(&self, other: &LongestKill) -> bool {
+        self.distance
+            .total_cmp(&other.distance)
+            .then_with(|| other.killer.cmp(&self.killer))
+            .then_with(|| other.victim.cmp(&self.victim))
+            .then_with(|| other.weapon.cmp(&self.weapon))
+            .is_gt()
+    }
+}
+
+/// Statistics of a single match.
+///
+/// A match can be split across several files, so every field can be merged with the partial stats of another file.
+///
+/// # Fields
+///
+/// * `map` - The map where the match was played.
+/// * `deaths` - The number of deaths in the match.
+/// * `kills` - A `HashMap` where keys are player names and values are their player kills in the match.
+/// * `placements` - A `HashMap` where keys are player names and values are their final placement in the match.
+/// * `weapons` - A `HashMap` where keys are weapon names and values are the deaths caused by each weapon.
+/// * `longest_kill` - The player kill with the longest valid distance, if any.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MatchStats {
+    pub(crate) map: String,
+    pub(crate) deaths: u32,
+    pub(crate) kills: HashMap<String, u32>,
+    pub(crate) placements: HashMap<String, u32>,
+    pub(crate) weapons: HashMap<String, u32>,
+    pub(crate) longest_kill: Option<LongestKill>,
+}
+
+impl MatchStats {
+    /// Counts a death made with the given weapon.
+    pub(crate) fn record_death(&mut self, map: &str, weapon: &str) {
+        if self.map.is_empty() {
+            self.map = map.to_owned();
+        }
+        self.deaths += 1;
+        *self.weapons.entry(weapon.to_owned()).or_insert(0) += 1;
+    }
+
+    /// Counts a player kill made by the given player.
+    pub(crate) fn record_kill(&mut self, killer: &str) {
+        *self.kills.entry(killer.to_owned()).or_insert(0) += 1;
+    }
+
+    /// Stores the final placement of a player, keeping the best one if different placements were found.
+    pub(crate) fn record_placement(&mut self, player: &str, placement: u32) {
+        let best_placement = self
+            .placements
+            .entry(player.to_owned())
+            .or_insert(placement);
+        *best_placement = (*best_placement).min(placement);
+    }
+
+    /// Replaces the longest kill of the match if the given one is longer.
+    pub(crate) fn record_longest_kill(&mut self, kill: LongestKill) {
+        if self
+            .longest_kill
+            .as_ref()
+            .is_none_or(|longest_kill| kill.is_longer_than(longest_kill))
+        {
+            self.longest_kill = Some(kill);
+        }
+    }
+
+    /// Adds the partial stats of the same match from another file to these ones.
+    pub(crate) fn merge(&mut self, other: MatchStats) {
+        if self.map.is_empty() {
+            self.map = other.map;
+        }
+        self.deaths += other.deaths;
+        for (player, kills) in other.kills {
+            *self.kills.entry(player).or_insert(0) += kills;
+        }
+        for (player, placement) in other.placements {
+            self.record_placement(&player, placement);
+        }
+        for (weapon, deaths) in other.weapons {
+            *self.weapons.entry(weapon).or_insert(0) += deaths;
+        }
+        if let Some(longest_kill) = other.longest_kill {
+            self.record_longest_kill(longest_kill);
+        }
+    }
+}
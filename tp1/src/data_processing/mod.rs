@@ -1,3 +1,22 @@
+pub mod columnar_reader;
+pub mod coordinate_outliers;
 pub mod data_processor;
+pub mod death_category;
+pub mod death_record;
+pub mod deaths_info;
+pub mod directory_watcher;
+pub mod distance_sum;
+pub mod input_format;
+pub mod killer_placement_stats;
+pub mod line_reader;
+pub mod match_stats;
+pub mod pipeline_processor;
+pub mod placement_stats;
 pub mod player_stats;
+pub mod position;
+pub mod range_band_counts;
+pub mod row_sampler;
+pub mod run_progress;
+pub mod sharded_accumulator;
+pub mod special_deaths;
 pub mod weapon_stats;
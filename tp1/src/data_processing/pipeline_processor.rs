@@ -0,0 +1,242 @@
+use crate::config::processing_config::ProcessingConfig;
+use crate::data_processing::data_processor::{
+    merge_files_info, process_file_in, update_stats_from_line,
+};
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::input_format::InputFormat;
+use crate::data_processing::line_reader::LineReader;
+use crate::data_processing::row_sampler::RowSampler;
+use crate::data_processing::run_progress::RunProgress;
+use crate::data_processing::sharded_accumulator::{ShardedAccumulator, SHARDS_PER_THREAD};
+
+use rayon::ThreadPool;
+use std::fs::File;
+use std::io::BufReader;
+use std::mem::take;
+use std::panic::resume_unwind;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+/// Number of lines sent together from a reader thread to the parser workers.
+const LINES_PER_BATCH: usize = 1_000;
+
+/// Number of batches that can wait in the channel before the reader threads block.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Processes the files with a producer/consumer pipeline.
+///
+/// Reader threads take files from a shared queue and send their lines in batches over a bounded channel. Parser
+/// workers take the batches, add the player stats to a `ShardedAccumulator` shared by all of them and keep the rest
+/// of the stats, which are small, in a local `DeathsInfo` that is merged at the end. Parquet and Arrow IPC files are
+/// already split in columns, so the reader threads hand them to the thread pool of the run, which processes their
+/// row groups in parallel. A quarter of the threads are readers, with at least one reader and one parser.
+///
+/// # Arguments
+///
+/// * `paths` - The paths of the CSV files.
+/// * `thread_pool` - The `ThreadPool` of the run, where the Parquet and Arrow IPC files are processed.
+/// * `num_threads` - The total number of reader and parser threads.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `progress` - The `RunProgress` updated while the files are processed.
+///
+/// # Returns
+///
+/// A `DeathsInfo` with the aggregated stats of all the files.
+pub(crate) fn pipeline_files(
+    paths: &[PathBuf],
+    thread_pool: &ThreadPool,
+    num_threads: usize,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    let readers = (num_threads / 4).clamp(1, paths.len().max(1));
+    let parsers = num_threads.saturating_sub(readers).max(1);
+
+    let next_file = AtomicUsize::new(0);
+    let accumulator = ShardedAccumulator::new(parsers * SHARDS_PER_THREAD);
+    let (sender, receiver) = sync_channel(CHANNEL_CAPACITY);
+    let receiver = Mutex::new(receiver);
+
+    let mut deaths_info = thread::scope(|scope| {
+        let reader_threads: Vec<_> = (0..readers)
+            .map(|_| {
+                let sender = sender.clone();
+                let next_file = &next_file;
+                let accumulator = &accumulator;
+                scope.spawn(move || {
+                    let mut columnar_deaths_info = DeathsInfo::default();
+                    while let Some(path) = paths.get(next_file.fetch_add(1, Ordering::Relaxed)) {
+                        let keep_reading = match InputFormat::of_path(path) {
+                            Some(InputFormat::Parquet | InputFormat::ArrowIpc) => {
+                                let mut file_deaths_info =
+                                    process_file_in(thread_pool, path, config, progress);
+                                accumulator.add_players(take(&mut file_deaths_info.players));
+                                columnar_deaths_info =
+                                    merge_files_info(columnar_deaths_info, file_deaths_info);
+                                !progress.is_cancelled()
+                            }
+                            _ => read_file_batches(path, &sender, config, progress),
+                        };
+                        if !keep_reading {
+                            break;
+                        }
+                    }
+                    columnar_deaths_info
+                })
+            })
+            .collect();
+        drop(sender);
+
+        let workers: Vec<_> = (0..parsers)
+            .map(|_| scope.spawn(|| parse_batches(&receiver, &accumulator, config, progress)))
+            .collect();
+
+        reader_threads
+            .into_iter()
+            .chain(workers)
+            .map(|worker| worker.join().unwrap_or_else(|panic| resume_unwind(panic)))
+            .fold(
+                DeathsInfo::default(),
+                |final_deaths_info, local_deaths_info| {
+                    let start = Instant::now();
+                    let merged = merge_files_info(final_deaths_info, local_deaths_info);
+                    progress.add_merging(start.elapsed());
+                    merged
+                },
+            )
+    });
+
+    deaths_info.players = accumulator.into_players();
+    deaths_info
+}
+
+/// Reads a file, skipping the header, and sends its lines in batches to the parser workers. If the input is
+/// sampled, only the file and lines in the sample are sent, drawn the same way as when the file is read directly.
+/// The lines are decoded with the encoding of the config, and reading stops at the first line that cannot be read.
+///
+/// # Arguments
+///
+/// * `path` - The path to the CSV file.
+/// * `sender` - The sending half of the channel of line batches.
+/// * `config` - The `ProcessingConfig` with the encoding and the sampling of the input.
+/// * `progress` - The `RunProgress` updated with the rows read.
+///
+/// # Returns
+///
+/// `false` if the parser workers are gone or the run was cancelled, so no more files should be read.
+fn read_file_batches(
+    path: &PathBuf,
+    sender: &SyncSender<Vec<String>>,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> bool {
+    if progress.is_cancelled() {
+        return false;
+    }
+    if let Some(sampling) = &config.sampling {
+        if !sampling.keeps_file(path) {
+            progress.record_file();
+            return true;
+        }
+    }
+    progress.record_read_file(path);
+
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) => {
+            eprintln!("Error opening file {}: {}", path.display(), err);
+            return true;
+        }
+    };
+
+    let mut sampler = RowSampler::new(config.sampling.as_ref(), path, 0);
+    let mut batch = Vec::with_capacity(LINES_PER_BATCH);
+    let (mut rows, mut bytes) = (0, 0);
+    let mut lines = LineReader::new(BufReader::new(file), config.encoding);
+    lines.next_line();
+    while let Some(line) = lines.next_line() {
+        // Checked on every line, as with sampling a batch can take many lines to fill. The lines read so far are
+        // still sent below.
+        if progress.is_cancelled() {
+            break;
+        }
+        match line {
+            Ok((line_content, line_bytes)) => {
+                bytes += line_bytes;
+                if sampler.keeps_row() {
+                    batch.push(line_content.into_owned());
+                }
+            }
+            Err(err) => {
+                eprintln!("Error reading line in file {}: {}", path.display(), err);
+                break;
+            }
+        }
+        rows += 1;
+        if batch.len() == LINES_PER_BATCH {
+            progress.record_rows(rows, bytes);
+            (rows, bytes) = (0, 0);
+            if sender.send(take(&mut batch)).is_err() {
+                return false;
+            }
+        }
+    }
+
+    progress.record_rows(rows, bytes);
+    if !batch.is_empty() && sender.send(batch).is_err() {
+        return false;
+    }
+    if !progress.is_cancelled() {
+        progress.record_file();
+    }
+    true
+}
+
+/// Parses the batches of lines until the channel is closed.
+///
+/// The player stats of each batch are added to the shared accumulator, and the rest of the stats are kept locally.
+///
+/// # Arguments
+///
+/// * `receiver` - The receiving half of the channel of line batches, shared by all the parser workers.
+/// * `accumulator` - The `ShardedAccumulator` the player stats are added to.
+/// * `config` - The `ProcessingConfig` used to process the lines.
+/// * `progress` - The `RunProgress` updated with the time spent.
+///
+/// # Returns
+///
+/// A `DeathsInfo` with every stat except the player stats.
+fn parse_batches(
+    receiver: &Mutex<Receiver<Vec<String>>>,
+    accumulator: &ShardedAccumulator,
+    config: &ProcessingConfig,
+    progress: &RunProgress,
+) -> DeathsInfo {
+    let mut local_deaths_info = DeathsInfo::default();
+
+    loop {
+        let batch = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => break,
+        };
+        let Ok(batch) = batch else {
+            break;
+        };
+
+        let start = Instant::now();
+        for line in &batch {
+            update_stats_from_line(line, &mut local_deaths_info, config);
+        }
+        progress.add_parsing(start.elapsed());
+
+        let start = Instant::now();
+        accumulator.add_players(take(&mut local_deaths_info.players));
+        progress.add_merging(start.elapsed());
+    }
+
+    local_deaths_info
+}
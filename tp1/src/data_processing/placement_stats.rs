@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+/// Placement brackets, as the inclusive range of placements of each one and its name.
+/// The last bracket has no upper bound.
+pub const PLACEMENT_BRACKETS: [(u32, u32, &str); 6] = [
+    (1, 1, "1"),
+    (2, 5, "2-5"),
+    (6, 10, "6-10"),
+    (11, 25, "11-25"),
+    (26, 50, "26-50"),
+    (51, u32::MAX, "51+"),
+];
+
+/// Kills made by the players that finished a match within a placement bracket.
+///
+/// # Fields
+///
+/// * `kills` - The number of kills made by those players.
+/// * `weapons` - A `HashMap` where keys are weapon names and values are the kills made with each weapon.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BracketStats {
+    pub(crate) kills: u32,
+    pub(crate) weapons: HashMap<String, u32>,
+}
+
+/// Sum of the placements of the victims of a weapon.
+///
+/// # Fields
+///
+/// * `total_placement` - The sum of the final placements of the victims.
+/// * `count` - The number of victims with a known placement.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PlacementTotals {
+    pub(crate) total_placement: u64,
+    pub(crate) count: u32,
+}
+
+/// Statistics of the kills by the final placement of the killer and the victim.
+///
+/// # Fields
+///
+/// * `brackets` - The `BracketStats` of each bracket in `PLACEMENT_BRACKETS`, by the placement of the killer.
+///   Only player kills with a known killer placement are counted.
+/// * `victim_placements` - A `HashMap` where keys are weapon names and values are the `PlacementTotals` of the
+///   victims of every death made with the weapon.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PlacementStats {
+    pub(crate) brackets: [BracketStats; PLACEMENT_BRACKETS.len()],
+    pub(crate) victim_placements: HashMap<String, PlacementTotals>,
+}
+
+impl PlacementStats {
+    /// Counts a player kill made by a killer with the given placement.
+    pub(crate) fn record_kill(&mut self, killer_placement: u32, weapon: &str) {
+        if let Some(bracket) = bracket_index(killer_placement) {
+            let bracket_stats = &mut self.brackets[bracket];
+            bracket_stats.kills += 1;
+            *bracket_stats.weapons.entry(weapon.to_owned()).or_insert(0) += 1;
+        }
+    }
+
+    /// Adds the placement of a victim of the given weapon.
+    pub(crate) fn record_victim(&mut self, victim_placement: u32, weapon: &str) {
+        let totals = self.victim_placements.entry(weapon.to_owned()).or_default();
+        totals.total_placement += victim_placement as u64;
+        totals.count += 1;
+    }
+
+    /// Adds the statistics of another `PlacementStats` to these ones.
+    pub(crate) fn merge(&mut self, other: PlacementStats) {
+        for (bracket_stats, other_bracket) in self.brackets.iter_mut().zip(other.brackets) {
+            bracket_stats.kills += other_bracket.kills;
+            for (weapon, kills) in other_bracket.weapons {
+                *bracket_stats.weapons.entry(weapon).or_insert(0) += kills;
+            }
+        }
+
+        for (weapon, other_totals) in other.victim_placements {
+            let totals = self.victim_placements.entry(weapon).or_default();
+            totals.total_placement += other_totals.total_placement;
+            totals.count += other_totals.count;
+        }
+    }
+}
+
+/// Finds the index in `PLACEMENT_BRACKETS` of the bracket that contains the given placement.
+fn bracket_index(placement: u32) -> Option<usize> {
+    PLACEMENT_BRACKETS
+        .iter()
+        .position(|(lower, upper, _)| (*lower..=*upper).contains(&placement))
+}
@@ -0,0 +1,82 @@
+/// A position on the map, in the units used by the position columns.
+///
+/// # Fields
+///
+/// * `x` - The horizontal coordinate.
+/// * `y` - The vertical coordinate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+}
+
+/// A problem found in a position while validating it against the bounds of its map.
+///
+/// # Variants
+///
+/// * `NonFinite` - One of the coordinates is `NaN` or infinite.
+/// * `Placeholder` - The position is exactly `(0, 0)`, which the dumps use when the real position is unknown.
+/// * `Negative` - One of the coordinates is negative.
+/// * `OutOfBounds` - One of the coordinates is greater than the size of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionIssue {
+    NonFinite,
+    Placeholder,
+    Negative,
+    OutOfBounds,
+}
+
+impl Position {
+    /// Parses a position from the strings of its coordinates.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<Position>` with the position if both coordinates are valid `f64` values, otherwise `None`.
+    pub(crate) fn parse(x_str: &str, y_str: &str) -> Option<Self> {
+        Some(Position {
+            x: x_str.parse().ok()?,
+            y: y_str.parse().ok()?,
+        })
+    }
+
+    /// Checks the position against the bounds of its map.
+    ///
+    /// # Arguments
+    ///
+    /// * `map_size` - The size of the map, or `None` if the map is unknown, in which case only the lower bound is checked.
+    ///
+    /// # Returns
+    ///
+    /// The first `PositionIssue` found, or `None` if the position is valid.
+    pub(crate) fn check(&self, map_size: Option<f64>) -> Option<PositionIssue> {
+        if !self.x.is_finite() || !self.y.is_finite() {
+            Some(PositionIssue::NonFinite)
+        } else if self.x == 0.0 && self.y == 0.0 {
+            Some(PositionIssue::Placeholder)
+        } else if self.x < 0.0 || self.y < 0.0 {
+            Some(PositionIssue::Negative)
+        } else if map_size.is_some_and(|size| self.x > size || self.y > size) {
+            Some(PositionIssue::OutOfBounds)
+        } else {
+            None
+        }
+    }
+
+    /// Moves the position to the closest point inside the map.
+    ///
+    /// # Arguments
+    ///
+    /// * `map_size` - The size of the map, or `None` if the map is unknown, in which case only the lower bound is applied.
+    pub(crate) fn clamp(&self, map_size: Option<f64>) -> Position {
+        let upper_bound = map_size.unwrap_or(f64::INFINITY);
+        Position {
+            x: self.x.clamp(0.0, upper_bound),
+            y: self.y.clamp(0.0, upper_bound),
+        }
+    }
+
+    /// Calculates the euclidean distance to another position.
+    pub(crate) fn distance_to(&self, other: &Position) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+}
@@ -0,0 +1,42 @@
+use crate::config::range_bands::RangeBand;
+
+/// Counters of the kills with a valid distance in each range band.
+///
+/// # Fields
+///
+/// * `close` - Kills at close range.
+/// * `mid` - Kills at mid range.
+/// * `long` - Kills at long range.
+/// * `extreme` - Kills at extreme range.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RangeBandCounts {
+    pub(crate) close: u32,
+    pub(crate) mid: u32,
+    pub(crate) long: u32,
+    pub(crate) extreme: u32,
+}
+
+impl RangeBandCounts {
+    /// Counts a kill in the given band.
+    pub(crate) fn record(&mut self, band: RangeBand) {
+        match band {
+            RangeBand::Close => self.close += 1,
+            RangeBand::Mid => self.mid += 1,
+            RangeBand::Long => self.long += 1,
+            RangeBand::Extreme => self.extreme += 1,
+        }
+    }
+
+    /// Adds the counters of another `RangeBandCounts` to these ones.
+    pub(crate) fn merge(&mut self, other: &RangeBandCounts) {
+        self.close += other.close;
+        self.mid += other.mid;
+        self.long += other.long;
+        self.extreme += other.extreme;
+    }
+
+    /// Returns the number of kills counted in every band.
+    pub(crate) fn total(&self) -> u32 {
+        self.close + self.mid + self.long + self.extreme
+    }
+}
@@ -0,0 +1,51 @@
+use crate::config::sampling::{SampleUnit, Sampling};
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use std::path::Path;
+
+/// Decides which rows of a part of a file are processed.
+///
+/// # Fields
+///
+/// * `rng` - The random number generator of the part, or `None` if every row is kept.
+/// * `fraction` - The probability of keeping each row.
+#[derive(Debug)]
+pub struct RowSampler {
+    rng: Option<ChaCha8Rng>,
+    fraction: f64,
+}
+
+impl RowSampler {
+    /// Builds the sampler of a part of a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `sampling` - The `Sampling` of the run, if any.
+    /// * `path` - The path of the file.
+    /// * `part` - The index of the part of the file, such as a row group, `0` if the file is read as a whole.
+    ///
+    /// # Returns
+    ///
+    /// A `RowSampler` that keeps every row unless rows are sampled.
+    pub(crate) fn new(sampling: Option<&Sampling>, path: &Path, part: u64) -> Self {
+        match sampling {
+            Some(sampling) if sampling.unit == SampleUnit::Rows => RowSampler {
+                rng: Some(sampling.rng_for(path, part)),
+                fraction: sampling.fraction,
+            },
+            _ => RowSampler {
+                rng: None,
+                fraction: 1.0,
+            },
+        }
+    }
+
+    /// Returns whether the next row is processed.
+    pub(crate) fn keeps_row(&mut self) -> bool {
+        match &mut self.rng {
+            Some(rng) => rng.random_bool(self.fraction),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::data_summary::run_metadata::{PhaseTimings, RunMetadata};
+
+use std::io::{stderr, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Progress, cancellation flag and phase timings of a run, shared by all the threads processing the files.
+///
+/// Every counter is atomic, so the threads update it without locking while the progress is reported. Only the list of
+/// read files is behind a lock, and it is updated once per file.
+///
+/// # Fields
+///
+/// * `started` - When the run started.
+/// * `total_files` - Number of files to process.
+/// * `total_bytes` - Total size of the files to process.
+/// * `files_done` - Number of files fully processed.
+/// * `read_files` - The files whose rows were read, fully or until the run was cancelled. Files left out of a sample
+///   are not in it.
+/// * `bytes_done` - Number of bytes read.
+/// * `rows_done` - Number of rows read.
+/// * `cancelled` - Whether the run was cancelled.
+/// * `finished` - Whether the processing finished, which stops the progress report.
+/// * `listing_nanos` - Time spent listing the files.
+/// * `parsing_nanos` - Time spent by all the threads parsing the files.
+/// * `merging_nanos` - Time spent by all the threads merging the partial results.
+/// * `summarizing_nanos` - Time spent summarizing the results.
+#[derive(Debug)]
+pub struct RunProgress {
+    started: Instant,
+    total_files: AtomicUsize,
+    total_bytes: AtomicU64,
+    files_done: AtomicUsize,
+    read_files: Mutex<Vec<PathBuf>>,
+    bytes_done: AtomicU64,
+    rows_done: AtomicU64,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+    listing_nanos: AtomicU64,
+    parsing_nanos: AtomicU64,
+    merging_nanos: AtomicU64,
+    summarizing_nanos: AtomicU64,
+}
+
+impl Default for RunProgress {
+    fn default() -> Self {
+        RunProgress {
+            started: Instant::now(),
+            total_files: AtomicUsize::new(0),
+            total_bytes: AtomicU64::new(0),
+            files_done: AtomicUsize::new(0),
+            read_files: Mutex::new(Vec::new()),
+            bytes_done: AtomicU64::new(0),
+            rows_done: AtomicU64::new(0),
+            cancelled: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            listing_nanos: AtomicU64::new(0),
+            parsing_nanos: AtomicU64::new(0),
+            merging_nanos: AtomicU64::new(0),
+            summarizing_nanos: AtomicU64::new(0),
+        }
+    }
+}
+
+impl RunProgress {
+    /// Sets the number and total size of the files to process.
+    pub fn set_totals(&self, files: usize, bytes: u64) {
+        self.total_files.store(files, Ordering::Relaxed);
+        self.total_bytes.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Adds rows read from a file, with their size in bytes.
+    pub fn record_rows(&self, rows: u64, bytes: u64) {
+        self.rows_done.fetch_add(rows, Ordering::Relaxed);
+        self.bytes_done.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Marks a file as fully processed.
+    pub fn record_file(&self) {
+        self.files_done.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Adds a file to the files whose rows are read, before it starts being read.
+    pub fn record_read_file(&self, path: &Path) {
+        if let Ok(mut read_files) = self.read_files.lock() {
+            read_files.push(path.to_path_buf());
+        }
+    }
+
+    /// Returns the files whose rows were read, sorted.
+    pub fn read_files(&self) -> Vec<PathBuf> {
+        let mut read_files = self
+            .read_files
+            .lock()
+            .map(|read_files| read_files.clone())
+            .unwrap_or_default();
+        read_files.sort();
+        read_files
+    }
+
+    /// Asks the threads to stop processing, so that a partial summary can be written.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the run was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Marks the processing as finished, stopping the progress report.
+    pub fn finish(&self) {
+        self.finished.store(true, Ordering::Relaxed);
+    }
+
+    /// Adds time spent listing the files.
+    pub fn add_listing(&self, elapsed: Duration) {
+        add_duration(&self.listing_nanos, elapsed);
+    }
+
+    /// Adds time spent by a thread parsing a file.
+    pub fn add_parsing(&self, elapsed: Duration) {
+        add_duration(&self.parsing_nanos, elapsed);
+    }
+
+    /// Adds time spent by a thread merging two partial results.
+    pub fn add_merging(&self, elapsed: Duration) {
+        add_duration(&self.merging_nanos, elapsed);
+    }
+
+    /// Adds time spent summarizing the results.
+    pub fn add_summarizing(&self, elapsed: Duration) {
+        add_duration(&self.summarizing_nanos, elapsed);
+    }
+
+    /// Builds the `RunMetadata` of the run so far.
+    ///
+    /// # Returns
+    ///
+    /// The `RunMetadata`, marked as partial if the run was cancelled.
+    pub fn metadata(&self) -> RunMetadata {
+        RunMetadata {
+            partial: self.is_cancelled(),
+            files_processed: self.files_done.load(Ordering::Relaxed),
+            rows_processed: self.rows_done.load(Ordering::Relaxed),
+            timings: PhaseTimings {
+                listing_ms: nanos_to_ms(&self.listing_nanos),
+                parsing_ms: nanos_to_ms(&self.parsing_nanos),
+                merging_ms: nanos_to_ms(&self.merging_nanos),
+                summarizing_ms: nanos_to_ms(&self.summarizing_nanos),
+                total_ms: self.started.elapsed().as_secs_f64() * 1000.0,
+            },
+        }
+    }
+
+    /// Prints the progress to stderr at the given interval until the processing finishes.
+    ///
+    /// Nothing is printed when stderr is not a terminal, so that logs are not flooded.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The time between two updates.
+    pub fn report_until_finished(&self, interval: Duration) {
+        if !stderr().is_terminal() {
+            return;
+        }
+        while !self.finished.load(Ordering::Relaxed) {
+            thread::sleep(interval);
+            eprint!("\r{}", self);
+            let _ = stderr().flush();
+        }
+        eprintln!("\r{}", self);
+    }
+}
+
+impl std::fmt::Display for RunProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let rows = self.rows_done.load(Ordering::Relaxed);
+        let rows_per_second = if elapsed > 0.0 {
+            rows as f64 / elapsed
+        } else {
+            0.0
+        };
+        write!(
+            f,
+            "{}/{} files, {:.1}/{:.1} MB, {:.0} rows/s",
+            self.files_done.load(Ordering::Relaxed),
+            self.total_files.load(Ordering::Relaxed),
+            self.bytes_done.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+            self.total_bytes.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+            rows_per_second
+        )
+    }
+}
+
+/// Adds a duration to a nanoseconds counter.
+fn add_duration(counter: &AtomicU64, elapsed: Duration) {
+    counter.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+}
+
+/// Converts a nanoseconds counter to milliseconds.
+fn nanos_to_ms(counter: &AtomicU64) -> f64 {
+    counter.load(Ordering::Relaxed) as f64 / 1_000_000.0
+}
@@ -0,0 +1,92 @@
+use crate::data_processing::data_processor::merge_player_stats;
+use crate::data_processing::player_stats::PlayerStats;
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::sync::Mutex;
+
+/// Number of shards created for each thread that writes to the accumulator, so that two threads rarely need the
+/// same shard at the same time.
+pub const SHARDS_PER_THREAD: usize = 4;
+
+/// A table of `PlayerStats` split in shards, each behind its own lock, so that several threads can add stats at the
+/// same time while rarely waiting for each other.
+///
+/// Each player always lands in the same shard, so the shards never share a player and can be joined without merging.
+///
+/// # Fields
+///
+/// * `shards` - The player stats of each shard.
+/// * `hasher` - The hasher used to pick the shard of a player.
+#[derive(Debug)]
+pub struct ShardedAccumulator {
+    shards: Vec<Mutex<HashMap<String, PlayerStats>>>,
+    hasher: RandomState,
+}
+
+impl ShardedAccumulator {
+    /// Creates an empty accumulator.
+    ///
+    /// # Arguments
+    ///
+    /// * `shard_count` - The number of shards. At least one shard is created.
+    ///
+    /// # Returns
+    ///
+    /// The new `ShardedAccumulator`.
+    pub fn new(shard_count: usize) -> Self {
+        ShardedAccumulator {
+            shards: (0..shard_count.max(1))
+                .map(|_| Mutex::new(HashMap::new()))
+                .collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Adds the stats of several players, locking each shard once.
+    ///
+    /// # Arguments
+    ///
+    /// * `players` - The stats of the players to add.
+    pub fn add_players(&self, players: HashMap<String, PlayerStats>) {
+        let mut by_shard: Vec<Vec<(String, PlayerStats)>> =
+            self.shards.iter().map(|_| Vec::new()).collect();
+        for (player, player_stats) in players {
+            by_shard[self.shard_of(&player)].push((player, player_stats));
+        }
+
+        for (shard, shard_players) in self.shards.iter().zip(by_shard) {
+            if shard_players.is_empty() {
+                continue;
+            }
+            let mut shard = shard
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            for (player, player_stats) in shard_players {
+                merge_player_stats(&mut shard, player, player_stats);
+            }
+        }
+    }
+
+    /// Joins the shards into a single table.
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` with the stats of every player.
+    pub fn into_players(self) -> HashMap<String, PlayerStats> {
+        self.shards
+            .into_iter()
+            .flat_map(|shard| {
+                shard
+                    .into_inner()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+            })
+            .collect()
+    }
+
+    /// Returns the index of the shard of a player.
+    fn shard_of(&self, player: &str) -> usize {
+        (self.hasher.hash_one(player) % self.shards.len() as u64) as usize
+    }
+}
@@ -0,0 +1,52 @@
+use crate::data_processing::death_category::{DeathCategory, EnvironmentCause};
+
+/// Counters of the deaths that are not counted as kills in the player stats.
+///
+/// # Fields
+///
+/// * `blue_zone` - Deaths outside of the blue zone.
+/// * `falling` - Deaths from a fall.
+/// * `drowning` - Deaths by drowning.
+/// * `vehicle_explosion` - Deaths in a vehicle explosion.
+/// * `other_environment` - Other deaths without a killer.
+/// * `suicides` - Deaths where the killer is the victim.
+/// * `team_kills` - Deaths where the killer is a teammate of the victim.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SpecialDeaths {
+    pub(crate) blue_zone: u32,
+    pub(crate) falling: u32,
+    pub(crate) drowning: u32,
+    pub(crate) vehicle_explosion: u32,
+    pub(crate) other_environment: u32,
+    pub(crate) suicides: u32,
+    pub(crate) team_kills: u32,
+}
+
+impl SpecialDeaths {
+    /// Counts a death of the given category. Player kills are not counted here.
+    pub(crate) fn record(&mut self, category: DeathCategory) {
+        match category {
+            DeathCategory::PlayerKill => {}
+            DeathCategory::Environment(EnvironmentCause::BlueZone) => self.blue_zone += 1,
+            DeathCategory::Environment(EnvironmentCause::Falling) => self.falling += 1,
+            DeathCategory::Environment(EnvironmentCause::Drowning) => self.drowning += 1,
+            DeathCategory::Environment(EnvironmentCause::VehicleExplosion) => {
+                self.vehicle_explosion += 1
+            }
+            DeathCategory::Environment(EnvironmentCause::Other) => self.other_environment += 1,
+            DeathCategory::Suicide => self.suicides += 1,
+            DeathCategory::TeamKill => self.team_kills += 1,
+        }
+    }
+
+    /// Adds the counters of another `SpecialDeaths` to these ones.
+    pub(crate) fn merge(&mut self, other: SpecialDeaths) {
+        self.blue_zone += other.blue_zone;
+        self.falling += other.falling;
+        self.drowning += other.drowning;
+        self.vehicle_explosion += other.vehicle_explosion;
+        self.other_environment += other.other_environment;
+        self.suicides += other.suicides;
+        self.team_kills += other.team_kills;
+    }
+}
@@ -1,14 +1,53 @@
+use crate::config::range_bands::RangeBand;
+use crate::data_processing::distance_sum::DistanceSum;
+use crate::data_processing::range_band_counts::RangeBandCounts;
+use std::collections::HashMap;
+
 /// Represents statistics for a weapon.
 ///
 /// # Fields
 ///
 /// * `deaths` - The number of deaths for the weapon.
 /// * `valid_distances_count` - The number of valid distance measurements recorded for this weapon.
-///     That is, when all position fields for killer and victim have a valid f64 value.
-/// * `total_distance` - The sum of all distances of all valid measurements involving this weapon.
-#[derive(Debug)]
+///   That is, when all position fields for killer and victim have a valid f64 value.
+/// * `total_distance` - The `DistanceSum` of all distances of all valid measurements involving this weapon.
+/// * `range_bands` - A `HashMap` where keys are map names and values are the `RangeBandCounts` of the valid
+///   distance measurements on that map.
+#[derive(Debug, Default)]
 pub struct WeaponStats {
     pub(crate) deaths: u32,
     pub(crate) valid_distances_count: u32,
-    pub(crate) total_distance: f64,
+    pub(crate) total_distance: DistanceSum,
+    pub(crate) range_bands: HashMap<String, RangeBandCounts>,
+}
+
+impl WeaponStats {
+    /// Adds a valid distance measured on the given map, counting it in its range band.
+    pub(crate) fn record_distance(&mut self, map: &str, distance: f64, band: RangeBand) {
+        self.total_distance.add(distance);
+        self.valid_distances_count += 1;
+        match self.range_bands.get_mut(map) {
+            Some(counts) => counts.record(band),
+            None => {
+                let mut counts = RangeBandCounts::default();
+                counts.record(band);
+                self.range_bands.insert(map.to_owned(), counts);
+            }
+        }
+    }
+
+    /// Adds the stats of another `WeaponStats` to these ones.
+    pub(crate) fn merge(&mut self, other: &WeaponStats) {
+        self.deaths += other.deaths;
+        self.valid_distances_count += other.valid_distances_count;
+        self.total_distance.merge(&other.total_distance);
+        for (map, counts) in &other.range_bands {
+            match self.range_bands.get_mut(map) {
+                Some(map_counts) => map_counts.merge(counts),
+                None => {
+                    self.range_bands.insert(map.clone(), *counts);
+                }
+            }
+        }
+    }
 }
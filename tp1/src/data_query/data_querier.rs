@@ -0,0 +1,169 @@
+use crate::data_query::query_data::QueryData;
+use crate::data_query::query_result::{
+    PlayerBreakdown, PlayerKills, TopEntry, WeaponBreakdown, WeaponDetails,
+};
+use crate::data_query::top_filter::TopFilter;
+use crate::data_query::top_metric::TopMetric;
+use crate::data_summary::data_summarizer::calculate_average_distance;
+
+/// Number of players listed in the details of a weapon.
+const WEAPON_TOP_PLAYERS: usize = 10;
+
+/// Looks up the full weapon breakdown of a player.
+///
+/// # Arguments
+///
+/// * `data` - The `QueryData` of all the processed files.
+/// * `player` - The name of the player, which is resolved to its canonical name.
+///
+/// # Returns
+///
+/// An `Option<PlayerBreakdown>` with the weapons used by the player, or `None` if the player has no kills.
+pub fn query_player(data: &QueryData, player: &str) -> Option<PlayerBreakdown> {
+    let player = data.config.player_names.canonical_name(player);
+    let player_stats = data.deaths_info.players.get(player.as_ref())?;
+
+    let mut weapons: Vec<_> = player_stats
+        .used_weapons
+        .iter()
+        .map(|(weapon, stats)| WeaponBreakdown {
+            weapon: weapon.clone(),
+            kills: stats.deaths,
+            kills_percentage: percentage(stats.deaths, player_stats.deaths),
+            average_distance: calculate_average_distance(
+                stats.valid_distances_count,
+                stats.total_distance.centimeters(),
+            ),
+        })
+        .collect();
+    weapons.sort_by(|w1, w2| {
+        w2.kills
+            .cmp(&w1.kills)
+            .then_with(|| w1.weapon.cmp(&w2.weapon))
+    });
+
+    Some(PlayerBreakdown {
+        player: player.into_owned(),
+        kills: player_stats.deaths,
+        weapons,
+    })
+}
+
+/// Looks up the details of a weapon.
+///
+/// # Arguments
+///
+/// * `data` - The `QueryData` of all the processed files.
+/// * `weapon` - The name of the weapon, which is resolved to its canonical name.
+///
+/// # Returns
+///
+/// An `Option<WeaponDetails>` with the stats of the weapon, or `None` if the weapon caused no deaths.
+pub fn query_weapon(data: &QueryData, weapon: &str) -> Option<WeaponDetails> {
+    let weapon = data.config.weapon_taxonomy.canonical_name(weapon);
+    let total_deaths: u32 = data.weapon_stats.values().map(|stats| stats.deaths).sum();
+    let stats = data.weapon_stats.get(weapon)?;
+
+    let mut top_players: Vec<_> = data
+        .deaths_info
+        .players
+        .iter()
+        .filter_map(|(player, player_stats)| {
+            let kills = player_stats.used_weapons.get(weapon)?.deaths;
+            Some(PlayerKills {
+                player: player.clone(),
+                kills,
+            })
+        })
+        .collect();
+    top_players.sort_by(|p1, p2| {
+        p2.kills
+            .cmp(&p1.kills)
+            .then_with(|| p1.player.cmp(&p2.player))
+    });
+    top_players.truncate(WEAPON_TOP_PLAYERS);
+
+    Some(WeaponDetails {
+        weapon: weapon.to_owned(),
+        category: data.config.weapon_taxonomy.category_of(weapon).to_owned(),
+        deaths: stats.deaths,
+        deaths_percentage: percentage(stats.deaths, total_deaths),
+        average_distance: calculate_average_distance(
+            stats.valid_distances_count,
+            stats.total_distance.centimeters(),
+        ),
+        top_players,
+    })
+}
+
+/// Ranks the players or weapons by the given metric.
+///
+/// # Arguments
+///
+/// * `data` - The `QueryData` of all the processed files.
+/// * `metric` - The `TopMetric` used to rank the entries.
+/// * `limit` - The maximum number of entries returned.
+/// * `filter` - The `TopFilter` the entries must pass to be ranked.
+///
+/// # Returns
+///
+/// A `Vec` with the top `limit` entries in descending order of the metric, breaking ties by name.
+pub fn query_top(
+    data: &QueryData,
+    metric: TopMetric,
+    limit: usize,
+    filter: &TopFilter,
+) -> Vec<TopEntry> {
+    let mut entries: Vec<(String, f64)> = match metric {
+        TopMetric::Kills => data
+            .deaths_info
+            .players
+            .iter()
+            .map(|(player, stats)| (player.clone(), stats.deaths as f64))
+            .collect(),
+        TopMetric::WeaponVariety => data
+            .deaths_info
+            .players
+            .iter()
+            .map(|(player, stats)| (player.clone(), stats.used_weapons.len() as f64))
+            .collect(),
+        TopMetric::WeaponDeaths => data
+            .weapon_stats
+            .iter()
+            .map(|(weapon, stats)| (weapon.clone(), stats.deaths as f64))
+            .collect(),
+        TopMetric::AverageDistance => data
+            .weapon_stats
+            .iter()
+            .filter(|(_, stats)| stats.valid_distances_count > 0)
+            .map(|(weapon, stats)| {
+                let average = calculate_average_distance(
+                    stats.valid_distances_count,
+                    stats.total_distance.centimeters(),
+                );
+                (weapon.clone(), average)
+            })
+            .collect(),
+    };
+
+    entries.retain(|(name, value)| filter.accepts(name, *value));
+    entries.sort_by(|e1, e2| e2.1.total_cmp(&e1.1).then_with(|| e1.0.cmp(&e2.0)));
+    entries
+        .into_iter()
+        .take(limit)
+        .enumerate()
+        .map(|(index, (name, value))| TopEntry {
+            rank: index + 1,
+            name,
+            value,
+        })
+        .collect()
+}
+
+/// Calculates `part` as a percentage of `total`, rounded to two decimal places, or `0.0` if `total` is `0`.
+pub(crate) fn percentage(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (part as f64 / total as f64 * 10000.0).round() / 100.0
+}
@@ -0,0 +1,5 @@
+pub mod data_querier;
+pub mod query_data;
+pub mod query_result;
+pub mod top_filter;
+pub mod top_metric;
@@ -0,0 +1,43 @@
+use crate::config::processing_config::ProcessingConfig;
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::weapon_stats::WeaponStats;
+use crate::data_summary::data_summarizer::compute_weapon_stats;
+
+use std::collections::HashMap;
+
+/// The aggregated stats the queries are answered from.
+///
+/// The totals of every weapon are computed once when it is built, so a server answering many queries over the
+/// same data does not compute them again for each one.
+///
+/// # Fields
+///
+/// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
+/// * `weapon_stats` - A `HashMap` where keys are weapon names and values are their total `WeaponStats`.
+/// * `config` - The `ProcessingConfig` used to process the files.
+#[derive(Debug)]
+pub struct QueryData<'a> {
+    pub(crate) deaths_info: &'a DeathsInfo,
+    pub(crate) weapon_stats: HashMap<String, WeaponStats>,
+    pub(crate) config: &'a ProcessingConfig,
+}
+
+impl<'a> QueryData<'a> {
+    /// Builds the data of the queries, computing the totals of every weapon.
+    ///
+    /// # Arguments
+    ///
+    /// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
+    /// * `config` - The `ProcessingConfig` used to process the files.
+    ///
+    /// # Returns
+    ///
+    /// The `QueryData` of the given stats.
+    pub fn new(deaths_info: &'a DeathsInfo, config: &'a ProcessingConfig) -> Self {
+        QueryData {
+            deaths_info,
+            weapon_stats: compute_weapon_stats(deaths_info),
+            config,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use serde::Serialize;
+use std::fmt;
+
+/// The stats of a weapon used by a player.
+///
+/// # Fields
+///
+/// * `weapon` - The name of the weapon.
+/// * `kills` - The kills made by the player with the weapon.
+/// * `kills_percentage` - The percentage of the kills of the player made with the weapon.
+/// * `average_distance` - The average distance of the kills made by the player with the weapon.
+#[derive(Serialize, Debug)]
+pub struct WeaponBreakdown {
+    pub weapon: String,
+    pub kills: u32,
+    pub kills_percentage: f64,
+    pub average_distance: f64,
+}
+
+/// The full weapon breakdown of a player.
+///
+/// # Fields
+///
+/// * `player` - The canonical name of the player.
+/// * `kills` - The total kills made by the player.
+/// * `weapons` - The `WeaponBreakdown` of every weapon used by the player, sorted by kills.
+#[derive(Serialize, Debug)]
+pub struct PlayerBreakdown {
+    pub player: String,
+    pub kills: u32,
+    pub weapons: Vec<WeaponBreakdown>,
+}
+
+/// The kills made with a weapon by a player.
+///
+/// # Fields
+///
+/// * `player` - The name of the player.
+/// * `kills` - The kills made by the player with the weapon.
+#[derive(Serialize, Debug)]
+pub struct PlayerKills {
+    pub player: String,
+    pub kills: u32,
+}
+
+/// The details of a weapon.
+///
+/// # Fields
+///
+/// * `weapon` - The canonical name of the weapon.
+/// * `category` - The category of the weapon.
+/// * `deaths` - The total deaths caused by the weapon.
+/// * `deaths_percentage` - The percentage of all deaths caused by the weapon.
+/// * `average_distance` - The average distance of the deaths caused by the weapon.
+/// * `top_players` - The 10 players with the most kills with the weapon.
+#[derive(Serialize, Debug)]
+pub struct WeaponDetails {
+    pub weapon: String,
+    pub category: String,
+    pub deaths: u32,
+    pub deaths_percentage: f64,
+    pub average_distance: f64,
+    pub top_players: Vec<PlayerKills>,
+}
+
+/// An entry of the `top` query.
+///
+/// # Fields
+///
+/// * `rank` - The rank of the entry, starting at `1`.
+/// * `name` - The name of the player or weapon.
+/// * `value` - The value of the ranked metric.
+#[derive(Serialize, Debug)]
+pub struct TopEntry {
+    pub rank: usize,
+    pub name: String,
+    pub value: f64,
+}
+
+/// The entries of the `top` query, serialized as a list.
+///
+/// # Fields
+///
+/// * `0` - The `TopEntry` of every ranked player or weapon, sorted by rank.
+#[derive(Serialize, Debug)]
+#[serde(transparent)]
+pub struct TopRanking(pub Vec<TopEntry>);
+
+impl fmt::Display for PlayerBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({} kills)", self.player, self.kills)?;
+        for weapon in &self.weapons {
+            writeln!(
+                f,
+                "  {}: {} kills ({}%), average distance {}",
+                weapon.weapon, weapon.kills, weapon.kills_percentage, weapon.average_distance
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for WeaponDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({})", self.weapon, self.category)?;
+        writeln!(f, "  deaths: {} ({}%)", self.deaths, self.deaths_percentage)?;
+        writeln!(f, "  average distance: {}", self.average_distance)?;
+        writeln!(f, "  top players:")?;
+        for player in &self.top_players {
+            writeln!(f, "    {}: {} kills", player.player, player.kills)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TopEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {}: {}", self.rank, self.name, self.value)
+    }
+}
+
+impl fmt::Display for TopRanking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.0 {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use clap::Args;
+
+/// Filters applied to the entries of the `top` query before they are ranked.
+///
+/// # Fields
+///
+/// * `min_value` - The minimum value of the metric an entry must have.
+/// * `name_contains` - A text the name of the entry must contain, ignoring case.
+#[derive(Args, Debug, Clone, Default)]
+pub struct TopFilter {
+    /// Only rank the entries whose metric is at least this value.
+    #[arg(long)]
+    pub min_value: Option<f64>,
+    /// Only rank the entries whose name contains this text, ignoring case.
+    #[arg(long)]
+    pub name_contains: Option<String>,
+}
+
+impl TopFilter {
+    /// Checks whether an entry passes the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the player or weapon.
+    /// * `value` - The value of the metric for the entry.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the entry passes every filter that is set.
+    pub fn accepts(&self, name: &str, value: f64) -> bool {
+        let min_value_ok = self.min_value.is_none_or(|min_value| value >= min_value);
+        let name_ok = self
+            .name_contains
+            .as_ref()
+            .is_none_or(|text| name.to_lowercase().contains(&text.to_lowercase()));
+        min_value_ok && name_ok
+    }
+}
@@ -0,0 +1,17 @@
+use clap::ValueEnum;
+
+/// The metric used to rank the entries of the `top` query.
+///
+/// # Variants
+///
+/// * `Kills` - Players by number of kills.
+/// * `WeaponDeaths` - Weapons by number of deaths.
+/// * `AverageDistance` - Weapons by average kill distance.
+/// * `WeaponVariety` - Players by number of different weapons used.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopMetric {
+    Kills,
+    WeaponDeaths,
+    AverageDistance,
+    WeaponVariety,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// The `z` value of a 95% confidence level.
+const Z_95: f64 = 1.96;
+
+/// A 95% confidence interval of a percentage estimated from a sample.
+///
+/// # Fields
+///
+/// * `low` - The lower bound, in percent.
+/// * `high` - The upper bound, in percent.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceInterval {
+    pub low: f64,
+    pub high: f64,
+}
+
+impl ConfidenceInterval {
+    /// Computes the Wilson score interval of a proportion, which stays within `[0, 100]` and behaves well for
+    /// small samples and proportions close to `0` or `1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `part` - The number of sampled elements with the property, such as the kills with a weapon.
+    /// * `total` - The number of sampled elements.
+    ///
+    /// # Returns
+    ///
+    /// The `ConfidenceInterval`, with its bounds rounded to two decimal places. It spans `[0, 100]` if `total` is `0`.
+    pub(crate) fn wilson(part: u32, total: u32) -> Self {
+        if total == 0 {
+            return ConfidenceInterval {
+                low: 0.0,
+                high: 100.0,
+            };
+        }
+
+        let n = total as f64;
+        let p = part as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let denominator = 1.0 + z2 / n;
+        let center = (p + z2 / (2.0 * n)) / denominator;
+        let half_width = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+        let percent = |value: f64| (value.clamp(0.0, 1.0) * 10000.0).round() / 100.0;
+
+        ConfidenceInterval {
+            low: percent(center - half_width),
+            high: percent(center + half_width),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::config::outlier_policy::OutlierPolicy;
+
+use serde::{Deserialize, Serialize};
+
+/// Summary of the rows whose positions were not valid for their map.
+///
+/// # Fields
+///
+/// * `policy` - The `OutlierPolicy` applied to those rows.
+/// * `non_finite` - Rows with a `NaN` or infinite coordinate.
+/// * `placeholder` - Rows with a placeholder `(0, 0)` position.
+/// * `negative` - Rows with a negative coordinate.
+/// * `out_of_bounds` - Rows with a coordinate greater than the size of the map.
+/// * `excluded` - Rows whose distance was left out of the average distance.
+/// * `clamped` - Rows whose positions were clamped to the map before computing the distance.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CoordinateOutliersSummary {
+    pub policy: OutlierPolicy,
+    pub non_finite: u32,
+    pub placeholder: u32,
+    pub negative: u32,
+    pub out_of_bounds: u32,
+    pub excluded: u32,
+    pub clamped: u32,
+}
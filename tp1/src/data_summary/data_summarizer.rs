@@ -1,11 +1,35 @@
+use crate::config::outlier_policy::OutlierPolicy;
+use crate::config::processing_config::ProcessingConfig;
+use crate::config::sampling::SampleUnit;
+use crate::config::weapon_taxonomy::WeaponTaxonomy;
+use crate::data_processing::coordinate_outliers::CoordinateOutliers;
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::match_stats::MatchStats;
+use crate::data_processing::placement_stats::{BracketStats, PlacementStats, PLACEMENT_BRACKETS};
 use crate::data_processing::player_stats::PlayerStats;
+use crate::data_processing::range_band_counts::RangeBandCounts;
+use crate::data_processing::special_deaths::SpecialDeaths;
 use crate::data_processing::weapon_stats::WeaponStats;
+use crate::data_summary::confidence_interval::ConfidenceInterval;
+use crate::data_summary::coordinate_outliers_summary::CoordinateOutliersSummary;
 use crate::data_summary::deaths_info_summary::DeathsInfoSummary;
+use crate::data_summary::match_summary::{
+    LeaderboardEntry, LongestKillSummary, MatchSummary, MvpSummary,
+};
+use crate::data_summary::placement_summary::{PlacementBracketSummary, PlacementSummary};
 use crate::data_summary::player_stats_summary::PlayerStatsSummary;
+use crate::data_summary::range_profile_summary::RangeProfileSummary;
+use crate::data_summary::special_deaths_summary::{EnvironmentDeathsSummary, SpecialDeathsSummary};
 use crate::data_summary::weapon_stats_summary::WeaponStatsSummary;
 
 use std::collections::HashMap;
 
+/// Points given to a player for each kill in the MVP score.
+const MVP_KILL_POINTS: f64 = 1.0;
+
+/// Points given to the winner of a match in the MVP score. A player placed `n` gets `MVP_PLACEMENT_POINTS / n`.
+const MVP_PLACEMENT_POINTS: f64 = 10.0;
+
 /// Aggregates player and weapon statistics from the given deaths information.
 ///
 /// This function processes the deaths information to compute aggregated statistics, including top killers and top weapons.
@@ -13,54 +37,175 @@ use std::collections::HashMap;
 ///
 /// # Arguments
 ///
-/// * `deaths_info` - A `HashMap` where keys are player names and values are their respective `PlayerStats`.
+/// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
+/// * `config` - The `ProcessingConfig` used to process the data.
 ///
 /// # Returns
 ///
 /// A `DeathsInfoSummary` containing:
 /// - `top_killers`: A `HashMap` of the top 10 players by kills and their statistics.
 /// - `top_weapons`: A `HashMap` of the top 10 weapons by kills and their statistics.
-pub fn summarize(deaths_info: HashMap<String, PlayerStats>) -> DeathsInfoSummary {
-    let weapon_stats = compute_weapon_stats(&deaths_info);
+/// - `weapon_categories`: If a weapon mapping was loaded, a `HashMap` with the statistics of every weapon category.
+/// - `placement`: The kills by placement bracket and the average victim placement of each weapon.
+/// - `special_deaths`: The deaths that are not player kills, by category.
+/// - `coordinate_outliers`: The counters of rows with invalid positions and the policy applied to them.
+/// - `matches`: The summary of every tracked match, sorted by match identifier.
+///
+/// If the input was sampled, the counts are scaled to estimate the counts of the whole input, and if rows were
+/// sampled the percentages come with their confidence intervals.
+pub fn summarize(deaths_info: &DeathsInfo, config: &ProcessingConfig) -> DeathsInfoSummary {
+    // Rows of the same file are not independent, so the Wilson intervals only hold when rows are sampled.
+    let with_intervals = config
+        .sampling
+        .is_some_and(|sampling| sampling.unit == SampleUnit::Rows);
+    let weapon_stats = compute_weapon_stats(deaths_info);
     let total_deaths = calculate_total_deaths(&weapon_stats);
+    let weapon_categories = (!config.weapon_taxonomy.is_empty()).then(|| {
+        let category_stats = compute_category_stats(&weapon_stats, &config.weapon_taxonomy);
+        process_weapon_categories(category_stats, total_deaths, with_intervals)
+    });
 
-    let top_killers = process_top_killers(deaths_info);
-    let top_weapons = process_top_weapons(weapon_stats, total_deaths);
+    let top_killers = process_top_killers(&deaths_info.players, with_intervals);
+    let top_weapons = process_top_weapons(&weapon_stats, total_deaths, with_intervals);
+    let placement = summarize_placement_stats(&deaths_info.placement_stats);
+    let matches = summarize_matches(&deaths_info.matches);
+    let special_deaths = summarize_special_deaths(&deaths_info.special_deaths);
+    let coordinate_outliers =
+        summarize_coordinate_outliers(&deaths_info.coordinate_outliers, config.outlier_policy);
 
-    DeathsInfoSummary {
+    let mut summary = DeathsInfoSummary {
         top_killers,
         top_weapons,
+        weapon_categories,
+        placement,
+        special_deaths,
+        coordinate_outliers,
+        matches,
+    };
+    if let Some(sampling) = &config.sampling {
+        scale_counts(&mut summary, sampling.scale());
     }
+    summary
 }
 
-/// Computes aggregated statistics for weapons from the given player deaths information.
+/// Multiplies the counts of a summary built from a sample by the inverse of the sampling fraction, so they
+/// estimate the counts of the whole input. Percentages and averages are already estimates and are kept, and the
+/// matches are kept as sampled, since a sampled match is not a smaller version of the whole one.
 ///
 /// # Arguments
 ///
-/// * `deaths_info` - A `HashMap` where keys are player names and values are their respective `PlayerStats`.
+/// * `summary` - The `DeathsInfoSummary` built from the sample.
+/// * `scale` - The factor the counts are multiplied by.
+fn scale_counts(summary: &mut DeathsInfoSummary, scale: f64) {
+    let scaled = |count: &mut u32| *count = (*count as f64 * scale).round() as u32;
+
+    for stats in summary.top_killers.values_mut() {
+        scaled(&mut stats.deaths);
+    }
+    let weapon_summaries = summary.top_weapons.values_mut().chain(
+        summary
+            .weapon_categories
+            .iter_mut()
+            .flat_map(|c| c.values_mut()),
+    );
+    for stats in weapon_summaries {
+        for profile in stats.range_profile.values_mut() {
+            scaled(&mut profile.measured_deaths);
+        }
+    }
+    for bracket in summary.placement.brackets.values_mut() {
+        scaled(&mut bracket.kills);
+    }
+
+    let environment = &mut summary.special_deaths.environment;
+    for count in [
+        &mut environment.total,
+        &mut environment.blue_zone,
+        &mut environment.falling,
+        &mut environment.drowning,
+        &mut environment.vehicle_explosion,
+        &mut environment.other,
+        &mut summary.special_deaths.suicides,
+        &mut summary.special_deaths.team_kills,
+    ] {
+        scaled(count);
+    }
+
+    let outliers = &mut summary.coordinate_outliers;
+    for count in [
+        &mut outliers.non_finite,
+        &mut outliers.placeholder,
+        &mut outliers.negative,
+        &mut outliers.out_of_bounds,
+        &mut outliers.excluded,
+        &mut outliers.clamped,
+    ] {
+        scaled(count);
+    }
+}
+
+/// Computes aggregated statistics for weapons from the given deaths information.
+///
+/// Both the weapons used by the players and the weapons of the deaths that are not player kills are included,
+/// so the totals cover every death.
+///
+/// # Arguments
+///
+/// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
 ///
 /// # Returns
 ///
 /// A `HashMap` where keys are weapon names and values are the total `WeaponStats` for that weapon.
-fn compute_weapon_stats(
-    deaths_info: &HashMap<String, PlayerStats>,
-) -> HashMap<String, WeaponStats> {
+pub(crate) fn compute_weapon_stats(deaths_info: &DeathsInfo) -> HashMap<String, WeaponStats> {
     let mut weapon_stats: HashMap<String, WeaponStats> = HashMap::new();
-    for player in deaths_info.values() {
-        for (weapon, stats) in &player.used_weapons {
-            let entry = weapon_stats.entry(weapon.clone()).or_insert(WeaponStats {
-                deaths: 0,
-                valid_distances_count: 0,
-                total_distance: 0.0,
-            });
-            entry.deaths += stats.deaths;
-            entry.valid_distances_count += stats.valid_distances_count;
-            entry.total_distance += stats.total_distance;
-        }
+    let used_weapons = deaths_info
+        .players
+        .values()
+        .flat_map(|player| &player.used_weapons)
+        .chain(&deaths_info.unattributed_weapons);
+
+    for (weapon, stats) in used_weapons {
+        add_weapon_stats(&mut weapon_stats, weapon, stats);
     }
     weapon_stats
 }
 
+/// Rolls up the weapon statistics into statistics per weapon category.
+///
+/// # Arguments
+///
+/// * `weapon_stats` - A `HashMap` where keys are canonical weapon names and values are `WeaponStats`.
+/// * `weapon_taxonomy` - The `WeaponTaxonomy` with the category of each weapon.
+///
+/// # Returns
+///
+/// A `HashMap` where keys are category names and values are the total `WeaponStats` for that category.
+fn compute_category_stats(
+    weapon_stats: &HashMap<String, WeaponStats>,
+    weapon_taxonomy: &WeaponTaxonomy,
+) -> HashMap<String, WeaponStats> {
+    let mut category_stats: HashMap<String, WeaponStats> = HashMap::new();
+    for (weapon, stats) in weapon_stats {
+        add_weapon_stats(
+            &mut category_stats,
+            weapon_taxonomy.category_of(weapon),
+            stats,
+        );
+    }
+    category_stats
+}
+
+/// Adds the given `WeaponStats` to the entry with the given key.
+///
+/// # Arguments
+///
+/// * `totals` - A mutable reference to the `HashMap` of aggregated `WeaponStats`.
+/// * `key` - The key of the entry to update, such as a weapon or a category name.
+/// * `stats` - The `WeaponStats` to add.
+fn add_weapon_stats(totals: &mut HashMap<String, WeaponStats>, key: &str, stats: &WeaponStats) {
+    totals.entry(key.to_owned()).or_default().merge(stats);
+}
+
 /// Calculates the total number of deaths from the weapon statistics.
 ///
 /// # Arguments
@@ -82,24 +227,37 @@ fn calculate_total_deaths(weapon_stats: &HashMap<String, WeaponStats>) -> u32 {
 /// # Arguments
 ///
 /// * `deaths_info` - A `HashMap` where keys are player names and values are their respective `PlayerStats`.
+/// * `with_intervals` - Whether the confidence intervals of the percentages are computed, for a sample of rows.
 ///
 /// # Returns
 ///
 /// A `HashMap` where keys are player names and values are `PlayerStatsSummary` for the top 10 players by deaths.
 fn process_top_killers(
-    deaths_info: HashMap<String, PlayerStats>,
+    deaths_info: &HashMap<String, PlayerStats>,
+    with_intervals: bool,
 ) -> HashMap<String, PlayerStatsSummary> {
     let player_vec = sort_players_by_kills(deaths_info);
 
     let mut top_killers = HashMap::new();
     for (player_name, stats) in player_vec.into_iter().take(10) {
         let weapon_percentage =
-            calculate_player_weapon_percentage(stats.used_weapons, stats.deaths);
+            calculate_player_weapon_percentage(&stats.used_weapons, stats.deaths);
+        let weapons_percentage_ci = with_intervals.then(|| {
+            weapon_percentage
+                .keys()
+                .map(|weapon| {
+                    let kills = stats.used_weapons[weapon].deaths;
+                    let interval = ConfidenceInterval::wilson(kills, stats.deaths);
+                    (weapon.clone(), interval)
+                })
+                .collect()
+        });
         top_killers.insert(
-            player_name,
+            player_name.clone(),
             PlayerStatsSummary {
                 deaths: stats.deaths,
                 weapons_percentage: weapon_percentage,
+                weapons_percentage_ci,
             },
         );
     }
@@ -107,7 +265,7 @@ fn process_top_killers(
     top_killers
 }
 
-/// Sorts players with valid names by the number of kills in descending order.
+/// Sorts players by the number of kills in descending order, breaking ties by name.
 ///
 /// # Arguments
 ///
@@ -116,13 +274,12 @@ fn process_top_killers(
 /// # Returns
 ///
 /// A `Vec` of tuples where each tuple contains a player name and their `PlayerStats`, sorted by the number of deaths.
-fn sort_players_by_kills(deaths_info: HashMap<String, PlayerStats>) -> Vec<(String, PlayerStats)> {
-    let mut player_vec: Vec<_> = deaths_info
-        .into_iter()
-        .filter(|(killer_name, _)| !killer_name.is_empty())
-        .collect();
+pub(crate) fn sort_players_by_kills(
+    deaths_info: &HashMap<String, PlayerStats>,
+) -> Vec<(&String, &PlayerStats)> {
+    let mut player_vec: Vec<_> = deaths_info.iter().collect();
 
-    player_vec.sort_by(|p1, p2| p2.1.deaths.cmp(&p1.1.deaths).then_with(|| p1.0.cmp(&p2.0)));
+    player_vec.sort_by(|p1, p2| p2.1.deaths.cmp(&p1.1.deaths).then_with(|| p1.0.cmp(p2.0)));
 
     player_vec
 }
@@ -138,7 +295,7 @@ fn sort_players_by_kills(deaths_info: HashMap<String, PlayerStats>) -> Vec<(Stri
 ///
 /// A `HashMap` where keys are weapon names and values are the percentage of total kills made with each weapon.
 fn calculate_player_weapon_percentage(
-    used_weapons: HashMap<String, WeaponStats>,
+    used_weapons: &HashMap<String, WeaponStats>,
     total_kills: u32,
 ) -> HashMap<String, f64> {
     if total_kills == 0 {
@@ -151,7 +308,7 @@ fn calculate_player_weapon_percentage(
     let top_weapons = weapon_vec.into_iter().take(3).collect::<Vec<_>>();
     for (weapon, stats) in top_weapons {
         let percentage = (stats.deaths as f64 / total_kills as f64 * 10000.0).round() / 100.0;
-        weapon_percentage.insert(weapon, percentage);
+        weapon_percentage.insert(weapon.clone(), percentage);
     }
 
     weapon_percentage
@@ -167,13 +324,15 @@ fn calculate_player_weapon_percentage(
 ///
 /// * `weapon_stats` - A `HashMap` where keys are weapon names and values are `WeaponStats`.
 /// * `total_deaths` - The total number of deaths across all weapons.
+/// * `with_intervals` - Whether the confidence intervals of the percentages are computed, for a sample of rows.
 ///
 /// # Returns
 ///
 /// A `HashMap` where keys are weapon names and values are `WeaponStatsSummary` for the top 10 weapons.
 fn process_top_weapons(
-    weapon_stats: HashMap<String, WeaponStats>,
+    weapon_stats: &HashMap<String, WeaponStats>,
     total_deaths: u32,
+    with_intervals: bool,
 ) -> HashMap<String, WeaponStatsSummary> {
     let sorted_weapon_vec = sort_weapons_by_kills(weapon_stats);
 
@@ -183,23 +342,111 @@ fn process_top_weapons(
             continue;
         }
 
-        let deaths_percentage =
-            (stats.deaths as f64 / total_deaths as f64 * 10000.0).round() / 100.0;
-        let avg_distance =
-            calculate_average_distance(stats.valid_distances_count, stats.total_distance);
-
         top_weapons.insert(
-            weapon,
-            WeaponStatsSummary {
-                deaths_percentage,
-                average_distance: avg_distance,
-            },
+            weapon.clone(),
+            summarize_weapon_stats(stats, total_deaths, with_intervals),
         );
     }
 
     top_weapons
 }
 
+/// Processes the statistics of every weapon category.
+///
+/// # Arguments
+///
+/// * `category_stats` - A `HashMap` where keys are category names and values are `WeaponStats`.
+/// * `total_deaths` - The total number of deaths across all weapons.
+/// * `with_intervals` - Whether the confidence intervals of the percentages are computed, for a sample of rows.
+///
+/// # Returns
+///
+/// A `HashMap` where keys are category names and values are their `WeaponStatsSummary`.
+fn process_weapon_categories(
+    category_stats: HashMap<String, WeaponStats>,
+    total_deaths: u32,
+    with_intervals: bool,
+) -> HashMap<String, WeaponStatsSummary> {
+    if total_deaths == 0 {
+        return HashMap::new();
+    }
+
+    category_stats
+        .into_iter()
+        .map(|(category, stats)| {
+            (
+                category,
+                summarize_weapon_stats(&stats, total_deaths, with_intervals),
+            )
+        })
+        .collect()
+}
+
+/// Computes the percentage of total deaths, the average distance and the range profile of a weapon or category.
+///
+/// # Arguments
+///
+/// * `stats` - The `WeaponStats` to summarize.
+/// * `total_deaths` - The total number of deaths across all weapons, must be greater than `0`.
+/// * `with_intervals` - Whether the confidence interval of the percentage is computed, for a sample of rows.
+///
+/// # Returns
+///
+/// The `WeaponStatsSummary` of the given stats.
+fn summarize_weapon_stats(
+    stats: &WeaponStats,
+    total_deaths: u32,
+    with_intervals: bool,
+) -> WeaponStatsSummary {
+    let deaths_percentage = (stats.deaths as f64 / total_deaths as f64 * 10000.0).round() / 100.0;
+    let avg_distance = calculate_average_distance(
+        stats.valid_distances_count,
+        stats.total_distance.centimeters(),
+    );
+
+    let range_profile = stats
+        .range_bands
+        .iter()
+        .map(|(map, counts)| (map.clone(), summarize_range_bands(counts)))
+        .collect();
+
+    WeaponStatsSummary {
+        deaths_percentage,
+        deaths_percentage_ci: with_intervals
+            .then(|| ConfidenceInterval::wilson(stats.deaths, total_deaths)),
+        average_distance: avg_distance,
+        range_profile,
+    }
+}
+
+/// Computes the percentage of the kills of a weapon on a map in each range band.
+///
+/// # Arguments
+///
+/// * `counts` - The `RangeBandCounts` of the weapon on the map.
+///
+/// # Returns
+///
+/// The `RangeProfileSummary` of the counts, with the percentages rounded to two decimal places.
+pub(crate) fn summarize_range_bands(counts: &RangeBandCounts) -> RangeProfileSummary {
+    let total = counts.total();
+    let percentage = |count: u32| {
+        if total == 0 {
+            0.0
+        } else {
+            (count as f64 / total as f64 * 10000.0).round() / 100.0
+        }
+    };
+
+    RangeProfileSummary {
+        measured_deaths: total,
+        close_percentage: percentage(counts.close),
+        mid_percentage: percentage(counts.mid),
+        long_percentage: percentage(counts.long),
+        extreme_percentage: percentage(counts.extreme),
+    }
+}
+
 /// Sorts weapons by the number of deaths in descending order.
 ///
 /// # Arguments
@@ -209,9 +456,11 @@ fn process_top_weapons(
 /// # Returns
 ///
 /// A `Vec` of tuples where each tuple contains a weapon name and its `WeaponStats`, sorted by the number of deaths.
-fn sort_weapons_by_kills(used_weapons: HashMap<String, WeaponStats>) -> Vec<(String, WeaponStats)> {
-    let mut weapon_vec: Vec<_> = used_weapons.into_iter().collect();
-    weapon_vec.sort_by(|w1, w2| w2.1.deaths.cmp(&w1.1.deaths).then_with(|| w1.0.cmp(&w2.0)));
+fn sort_weapons_by_kills(
+    used_weapons: &HashMap<String, WeaponStats>,
+) -> Vec<(&String, &WeaponStats)> {
+    let mut weapon_vec: Vec<_> = used_weapons.iter().collect();
+    weapon_vec.sort_by(|w1, w2| w2.1.deaths.cmp(&w1.1.deaths).then_with(|| w1.0.cmp(w2.0)));
     weapon_vec
 }
 
@@ -225,7 +474,7 @@ fn sort_weapons_by_kills(used_weapons: HashMap<String, WeaponStats>) -> Vec<(Str
 /// # Returns
 ///
 /// The average distance, rounded to two decimal places. If there are no valid distances, `0.0` is returned and an error message is printed.
-fn calculate_average_distance(valid_distances_count: u32, total_distance: f64) -> f64 {
+pub(crate) fn calculate_average_distance(valid_distances_count: u32, total_distance: f64) -> f64 {
     if valid_distances_count > 0 {
         (total_distance / valid_distances_count as f64 * 100.0).round() / 100.0
     } else {
@@ -233,3 +482,234 @@ fn calculate_average_distance(valid_distances_count: u32, total_distance: f64) -
         0.0
     }
 }
+
+/// Builds the summary of the rows with invalid positions.
+///
+/// # Arguments
+///
+/// * `coordinate_outliers` - The aggregated counters of rows with invalid positions.
+/// * `policy` - The `OutlierPolicy` applied to those rows.
+///
+/// # Returns
+///
+/// A `CoordinateOutliersSummary` with the counters and the applied policy.
+fn summarize_coordinate_outliers(
+    coordinate_outliers: &CoordinateOutliers,
+    policy: OutlierPolicy,
+) -> CoordinateOutliersSummary {
+    CoordinateOutliersSummary {
+        policy,
+        non_finite: coordinate_outliers.non_finite,
+        placeholder: coordinate_outliers.placeholder,
+        negative: coordinate_outliers.negative,
+        out_of_bounds: coordinate_outliers.out_of_bounds,
+        excluded: coordinate_outliers.excluded,
+        clamped: coordinate_outliers.clamped,
+    }
+}
+
+/// Builds the summary of the deaths that are not player kills.
+///
+/// # Arguments
+///
+/// * `special_deaths` - The aggregated counters of deaths that are not player kills.
+///
+/// # Returns
+///
+/// A `SpecialDeathsSummary` with the environment deaths by cause, the suicides and the team kills.
+fn summarize_special_deaths(special_deaths: &SpecialDeaths) -> SpecialDeathsSummary {
+    let environment = EnvironmentDeathsSummary {
+        total: special_deaths.blue_zone
+            + special_deaths.falling
+            + special_deaths.drowning
+            + special_deaths.vehicle_explosion
+            + special_deaths.other_environment,
+        blue_zone: special_deaths.blue_zone,
+        falling: special_deaths.falling,
+        drowning: special_deaths.drowning,
+        vehicle_explosion: special_deaths.vehicle_explosion,
+        other: special_deaths.other_environment,
+    };
+
+    SpecialDeathsSummary {
+        environment,
+        suicides: special_deaths.suicides,
+        team_kills: special_deaths.team_kills,
+    }
+}
+
+/// Builds the summary of the kills by placement.
+///
+/// # Arguments
+///
+/// * `placement_stats` - The aggregated `PlacementStats` of all the processed files.
+///
+/// # Returns
+///
+/// A `PlacementSummary` with the kills of each placement bracket and the average victim placement of each weapon.
+fn summarize_placement_stats(placement_stats: &PlacementStats) -> PlacementSummary {
+    let total_kills: u32 = placement_stats
+        .brackets
+        .iter()
+        .map(|bracket_stats| bracket_stats.kills)
+        .sum();
+
+    let brackets = PLACEMENT_BRACKETS
+        .iter()
+        .zip(&placement_stats.brackets)
+        .map(|((_, _, name), bracket_stats)| {
+            (
+                name.to_string(),
+                summarize_placement_bracket(bracket_stats, total_kills),
+            )
+        })
+        .collect();
+
+    let average_victim_placement = placement_stats
+        .victim_placements
+        .iter()
+        .filter(|(_, totals)| totals.count > 0)
+        .map(|(weapon, totals)| {
+            let average = totals.total_placement as f64 / totals.count as f64;
+            (weapon.clone(), (average * 100.0).round() / 100.0)
+        })
+        .collect();
+
+    PlacementSummary {
+        brackets,
+        average_victim_placement,
+    }
+}
+
+/// Builds the summary of a placement bracket.
+///
+/// # Arguments
+///
+/// * `bracket_stats` - The `BracketStats` of the bracket.
+/// * `total_kills` - The total number of kills across all brackets.
+///
+/// # Returns
+///
+/// A `PlacementBracketSummary` with the kills of the bracket and its 3 most used weapons.
+fn summarize_placement_bracket(
+    bracket_stats: &BracketStats,
+    total_kills: u32,
+) -> PlacementBracketSummary {
+    let kills_percentage = if total_kills > 0 {
+        (bracket_stats.kills as f64 / total_kills as f64 * 10000.0).round() / 100.0
+    } else {
+        0.0
+    };
+
+    let mut weapon_vec: Vec<_> = bracket_stats.weapons.iter().collect();
+    weapon_vec.sort_by(|w1, w2| w2.1.cmp(w1.1).then_with(|| w1.0.cmp(w2.0)));
+
+    let weapons_percentage = weapon_vec
+        .into_iter()
+        .take(3)
+        .map(|(weapon, kills)| {
+            let percentage = (*kills as f64 / bracket_stats.kills as f64 * 10000.0).round() / 100.0;
+            (weapon.clone(), percentage)
+        })
+        .collect();
+
+    PlacementBracketSummary {
+        kills: bracket_stats.kills,
+        kills_percentage,
+        weapons_percentage,
+    }
+}
+
+/// Builds the summary of every tracked match.
+///
+/// # Arguments
+///
+/// * `matches` - A `HashMap` where keys are match identifiers and values are their `MatchStats`.
+///
+/// # Returns
+///
+/// A `Vec` with the `MatchSummary` of every match, sorted by match identifier.
+fn summarize_matches(matches: &HashMap<String, MatchStats>) -> Vec<MatchSummary> {
+    let mut match_summaries: Vec<_> = matches
+        .iter()
+        .map(|(match_id, match_stats)| summarize_match(match_id, match_stats))
+        .collect();
+    match_summaries.sort_by(|m1, m2| m1.match_id.cmp(&m2.match_id));
+    match_summaries
+}
+
+/// Builds the summary of a single match, with its kill leaderboard, weapon mix, longest kill and MVP.
+///
+/// # Arguments
+///
+/// * `match_id` - The identifier of the match.
+/// * `match_stats` - The `MatchStats` of the match.
+///
+/// # Returns
+///
+/// The `MatchSummary` of the match.
+fn summarize_match(match_id: &str, match_stats: &MatchStats) -> MatchSummary {
+    let mut leaderboard: Vec<_> = match_stats
+        .kills
+        .iter()
+        .map(|(player, kills)| LeaderboardEntry {
+            player: player.clone(),
+            kills: *kills,
+            placement: match_stats.placements.get(player).copied(),
+        })
+        .collect();
+    leaderboard.sort_by(|p1, p2| {
+        p2.kills
+            .cmp(&p1.kills)
+            .then_with(|| p1.player.cmp(&p2.player))
+    });
+
+    let mvp = leaderboard
+        .iter()
+        .map(|entry| MvpSummary {
+            player: entry.player.clone(),
+            score: calculate_mvp_score(entry.kills, entry.placement),
+        })
+        .max_by(|p1, p2| {
+            p1.score
+                .total_cmp(&p2.score)
+                .then_with(|| p2.player.cmp(&p1.player))
+        });
+
+    let longest_kill = match_stats
+        .longest_kill
+        .as_ref()
+        .map(|longest_kill| LongestKillSummary {
+            killer: longest_kill.killer.clone(),
+            victim: longest_kill.victim.clone(),
+            weapon: longest_kill.weapon.clone(),
+            distance: (longest_kill.distance * 100.0).round() / 100.0,
+        });
+
+    MatchSummary {
+        match_id: match_id.to_owned(),
+        map: match_stats.map.clone(),
+        deaths: match_stats.deaths,
+        leaderboard,
+        weapons: match_stats.weapons.clone(),
+        longest_kill,
+        mvp,
+    }
+}
+
+/// Calculates the MVP score of a player, combining their kills with their final placement.
+///
+/// # Arguments
+///
+/// * `kills` - The player kills made by the player in the match.
+/// * `placement` - The final placement of the player, if known. Unknown placements give no points.
+///
+/// # Returns
+///
+/// The MVP score, rounded to two decimal places.
+fn calculate_mvp_score(kills: u32, placement: Option<u32>) -> f64 {
+    let placement_points =
+        placement.map_or(0.0, |placement| MVP_PLACEMENT_POINTS / placement as f64);
+    let score = kills as f64 * MVP_KILL_POINTS + placement_points;
+    (score * 100.0).round() / 100.0
+}
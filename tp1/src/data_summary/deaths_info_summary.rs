@@ -1,13 +1,19 @@
+use crate::data_summary::coordinate_outliers_summary::CoordinateOutliersSummary;
+use crate::data_summary::match_summary::MatchSummary;
+use crate::data_summary::placement_summary::PlacementSummary;
 use crate::data_summary::player_stats_summary::PlayerStatsSummary;
+use crate::data_summary::special_deaths_summary::SpecialDeathsSummary;
 use crate::data_summary::weapon_stats_summary::WeaponStatsSummary;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// A summary of deaths information, including top killers and top weapons.
+/// A summary of deaths information, including top killers, top weapons, the kills by placement, the deaths that
+/// are not player kills and the rows with invalid positions.
 ///
 /// This struct aggregates and serializes stats for the players with the most deaths
-/// and the weapons with the most deaths.
+/// and the weapons with the most deaths. Sections added after the first version of the output default to empty
+/// values when deserializing, so older outputs can still be loaded.
 ///
 /// # Fields
 ///
@@ -18,8 +24,32 @@ use std::collections::HashMap;
 /// * `top_weapons` - A `HashMap` where:
 ///   - The key is the weapon's name (a `String`).
 ///   - The value is a `WeaponStatsSummary` that contains aggregated statistics for the weapon.
-#[derive(Serialize, Debug)]
+///
+/// * `weapon_categories` - If a weapon mapping was loaded, a `HashMap` where:
+///   - The key is the category's name (a `String`).
+///   - The value is a `WeaponStatsSummary` that contains aggregated statistics for every weapon of the category.
+///
+/// * `placement` - A `PlacementSummary` with the kills by placement bracket and the average victim placement of
+///   each weapon.
+///
+/// * `special_deaths` - A `SpecialDeathsSummary` with the environment deaths, suicides and team kills.
+///
+/// * `coordinate_outliers` - A `CoordinateOutliersSummary` with the counters of rows with invalid positions.
+///
+/// * `matches` - The `MatchSummary` of every tracked match. They are not part of the summary JSON, they are
+///   written to their own file instead.
+#[derive(Serialize, Deserialize, Debug)]
 pub struct DeathsInfoSummary {
     pub top_killers: HashMap<String, PlayerStatsSummary>,
     pub top_weapons: HashMap<String, WeaponStatsSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weapon_categories: Option<HashMap<String, WeaponStatsSummary>>,
+    #[serde(default)]
+    pub placement: PlacementSummary,
+    #[serde(default)]
+    pub special_deaths: SpecialDeathsSummary,
+    #[serde(default)]
+    pub coordinate_outliers: CoordinateOutliersSummary,
+    #[serde(skip)]
+    pub matches: Vec<MatchSummary>,
 }
@@ -0,0 +1,66 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A row of the kill leaderboard of a match.
+///
+/// # Fields
+///
+/// * `player` - The name of the player.
+/// * `kills` - The player kills made by the player in the match.
+/// * `placement` - The final placement of the player in the match, if known.
+#[derive(Serialize, Debug)]
+pub struct LeaderboardEntry {
+    pub player: String,
+    pub kills: u32,
+    pub placement: Option<u32>,
+}
+
+/// The kill with the longest distance of a match.
+///
+/// # Fields
+///
+/// * `killer` - The name of the killer.
+/// * `victim` - The name of the victim.
+/// * `weapon` - The name of the weapon.
+/// * `distance` - The distance between the killer and the victim, rounded to two decimal places.
+#[derive(Serialize, Debug)]
+pub struct LongestKillSummary {
+    pub killer: String,
+    pub victim: String,
+    pub weapon: String,
+    pub distance: f64,
+}
+
+/// The most valuable player of a match.
+///
+/// # Fields
+///
+/// * `player` - The name of the player.
+/// * `score` - The MVP score of the player, combining kills and final placement.
+#[derive(Serialize, Debug)]
+pub struct MvpSummary {
+    pub player: String,
+    pub score: f64,
+}
+
+/// Summary of a single match, written as one JSON record per line in the matches file.
+///
+/// # Fields
+///
+/// * `match_id` - The identifier of the match.
+/// * `map` - The map where the match was played.
+/// * `deaths` - The number of deaths in the match.
+/// * `leaderboard` - The players with at least one kill, sorted by kills in descending order.
+/// * `weapons` - A `HashMap` where keys are weapon names and values are the deaths caused by each weapon.
+/// * `longest_kill` - The player kill with the longest valid distance, if any.
+/// * `mvp` - The player of the leaderboard with the highest MVP score, if any.
+#[derive(Serialize, Debug)]
+pub struct MatchSummary {
+    pub match_id: String,
+    pub map: String,
+    pub deaths: u32,
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub weapons: HashMap<String, u32>,
+    pub longest_kill: Option<LongestKillSummary>,
+    pub mvp: Option<MvpSummary>,
+}
@@ -1,4 +1,11 @@
+pub mod confidence_interval;
+pub mod coordinate_outliers_summary;
 pub mod data_summarizer;
 pub mod deaths_info_summary;
+pub mod match_summary;
+pub mod placement_summary;
 pub mod player_stats_summary;
+pub mod range_profile_summary;
+pub mod run_metadata;
+pub mod special_deaths_summary;
 pub mod weapon_stats_summary;
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Summary of the kills made by the players that finished a match within a placement bracket.
+///
+/// # Fields
+///
+/// * `kills` - The number of kills made by those players.
+/// * `kills_percentage` - The percentage of all the player kills with a known killer placement.
+/// * `weapons_percentage` - A `HashMap` where keys are the 3 most used weapons in the bracket and values are the
+///   percentage of the bracket kills made with each one.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlacementBracketSummary {
+    pub kills: u32,
+    pub kills_percentage: f64,
+    pub weapons_percentage: HashMap<String, f64>,
+}
+
+/// Summary of the kills by the final placement of the killer and the victim.
+///
+/// # Fields
+///
+/// * `brackets` - A `HashMap` where keys are bracket names, such as `1` or `2-5`, and values are their
+///   `PlacementBracketSummary`.
+/// * `average_victim_placement` - A `HashMap` where keys are weapon names and values are the average final
+///   placement of the victims of the weapon.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PlacementSummary {
+    pub brackets: HashMap<String, PlacementBracketSummary>,
+    pub average_victim_placement: HashMap<String, f64>,
+}
@@ -1,4 +1,6 @@
-use serde::Serialize;
+use crate::data_summary::confidence_interval::ConfidenceInterval;
+
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Summary of a player's statistics.
@@ -7,8 +9,12 @@ use std::collections::HashMap;
 ///
 /// * `deaths` - The total number of deaths for the player.
 /// * `weapons_percentage` - A `HashMap` where keys are weapon names and values are the percentage of total deaths caused by each weapon.
-#[derive(Serialize, Debug)]
+/// * `weapons_percentage_ci` - Only when the input was sampled, a `HashMap` where keys are weapon names and values
+///   are the 95% `ConfidenceInterval` of their percentage.
+#[derive(Serialize, Deserialize, Debug)]
 pub struct PlayerStatsSummary {
     pub deaths: u32,
     pub weapons_percentage: HashMap<String, f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weapons_percentage_ci: Option<HashMap<String, ConfidenceInterval>>,
 }
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// How the kills of a weapon on a map are split into range bands.
+///
+/// # Fields
+///
+/// * `measured_deaths` - The number of deaths of the weapon on the map with a valid distance.
+/// * `close_percentage` - The percentage of those deaths at close range.
+/// * `mid_percentage` - The percentage of those deaths at mid range.
+/// * `long_percentage` - The percentage of those deaths at long range.
+/// * `extreme_percentage` - The percentage of those deaths at extreme range.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct RangeProfileSummary {
+    pub measured_deaths: u32,
+    pub close_percentage: f64,
+    pub mid_percentage: f64,
+    pub long_percentage: f64,
+    pub extreme_percentage: f64,
+}
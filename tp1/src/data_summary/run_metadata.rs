@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Time spent in each phase of a run, in milliseconds.
+///
+/// The files are parsed and merged concurrently, so `parsing_ms` and `merging_ms` add up the time spent by every
+/// thread and may be larger than the wall time of the run.
+///
+/// # Fields
+///
+/// * `listing_ms` - Time spent listing the CSV files of the input directory.
+/// * `parsing_ms` - Time spent by all the threads reading and parsing the files.
+/// * `merging_ms` - Time spent by all the threads merging the partial results.
+/// * `summarizing_ms` - Time spent building the summary from the merged results.
+/// * `total_ms` - Wall time from the start of the run until the summary was built.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+pub struct PhaseTimings {
+    pub listing_ms: f64,
+    pub parsing_ms: f64,
+    pub merging_ms: f64,
+    pub summarizing_ms: f64,
+    pub total_ms: f64,
+}
+
+/// Information about the run that produced a summary.
+///
+/// # Fields
+///
+/// * `partial` - Whether the run was cancelled before all the files were processed.
+/// * `files_processed` - Number of files fully processed.
+/// * `rows_processed` - Number of rows read.
+/// * `timings` - The `PhaseTimings` of the run.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct RunMetadata {
+    pub partial: bool,
+    pub files_processed: usize,
+    pub rows_processed: u64,
+    pub timings: PhaseTimings,
+}
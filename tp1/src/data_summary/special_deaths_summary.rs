@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// Summary of the deaths caused by the environment.
+///
+/// # Fields
+///
+/// * `total` - The total number of environment deaths.
+/// * `blue_zone` - Deaths outside of the blue zone.
+/// * `falling` - Deaths from a fall.
+/// * `drowning` - Deaths by drowning.
+/// * `vehicle_explosion` - Deaths in a vehicle explosion.
+/// * `other` - Other deaths without a killer.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct EnvironmentDeathsSummary {
+    pub total: u32,
+    pub blue_zone: u32,
+    pub falling: u32,
+    pub drowning: u32,
+    pub vehicle_explosion: u32,
+    pub other: u32,
+}
+
+/// Summary of the deaths that are not counted as kills in the player stats.
+///
+/// # Fields
+///
+/// * `environment` - An `EnvironmentDeathsSummary` with the deaths caused by the environment.
+/// * `suicides` - Deaths where the killer is the victim.
+/// * `team_kills` - Deaths where the killer is a teammate of the victim.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SpecialDeathsSummary {
+    pub environment: EnvironmentDeathsSummary,
+    pub suicides: u32,
+    pub team_kills: u32,
+}
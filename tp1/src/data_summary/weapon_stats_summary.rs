@@ -1,13 +1,24 @@
-use serde::Serialize;
+use crate::data_summary::confidence_interval::ConfidenceInterval;
+use crate::data_summary::range_profile_summary::RangeProfileSummary;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Summary of weapon statistics.
 ///
 /// # Fields
 ///
 /// * `deaths_percentage` - The percentage of total deaths caused by this weapon.
+/// * `deaths_percentage_ci` - Only when the input was sampled, the 95% `ConfidenceInterval` of `deaths_percentage`.
 /// * `average_distance` - The average distance of deaths caused by this weapon.
-#[derive(Serialize, Debug)]
+/// * `range_profile` - A `HashMap` where keys are map names and values are the `RangeProfileSummary` of the
+///   deaths caused by this weapon on that map. Older outputs without it load with an empty profile.
+#[derive(Serialize, Deserialize, Debug)]
 pub struct WeaponStatsSummary {
     pub deaths_percentage: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deaths_percentage_ci: Option<ConfidenceInterval>,
     pub average_distance: f64,
+    #[serde(default)]
+    pub range_profile: HashMap<String, RangeProfileSummary>,
 }
@@ -5,10 +5,13 @@
 /// * `Serialization` - An error occurred while serializing data.
 /// * `FileCreation` - An error occurred while creating the file.
 /// * `FileWrite` - An error occurred while writing to the file.
+/// * `Database` - An error occurred while creating or writing a SQLite database. Holds the message of the SQLite
+///   error.
 
 #[derive(Debug)]
 pub enum FileCreationError {
     Serialization,
     FileCreation,
     FileWrite,
+    Database(String),
 }
@@ -1,37 +1,41 @@
 use crate::data_summary::deaths_info_summary::DeathsInfoSummary;
+use crate::data_summary::match_summary::MatchSummary;
 use crate::file_creation::file_creation_error::FileCreationError;
 use crate::file_creation::json_format::JsonFormat;
+use crate::metadata::output_metadata::OutputMetadata;
 
-use std::fs::File;
+use std::fs::{remove_file, rename, File};
 use std::io::BufWriter;
 use std::io::Write;
 
-const PADRON: u32 = 106841;
-
-/// Serializes `DeathsInfoSummary` into a JSON string with a given `padron`.
+/// Serializes `DeathsInfoSummary` into a JSON string with its metadata block.
 ///
 /// # Arguments
 ///
-/// * `padron` - Student's padron.
+/// * `metadata` - The `OutputMetadata` of the run.
 /// * `deaths_info` - The `DeathsInfoSummary` data to be serialized.
 ///
 /// # Returns
 ///
 /// A `Result` containing the serialized JSON string if successful, or a `FileCreationError` if serialization fails.
-fn generate_json(padron: u32, deaths_info: DeathsInfoSummary) -> Result<String, FileCreationError> {
+fn generate_json(
+    metadata: OutputMetadata,
+    deaths_info: DeathsInfoSummary,
+) -> Result<String, FileCreationError> {
     let wrapper = JsonFormat {
-        padron,
+        metadata,
         deaths_info,
     };
     serde_json::to_string_pretty(&wrapper).map_err(|_| FileCreationError::Serialization)
 }
 
-/// Creates a JSON file from `DeathsInfoSummary` and writes it to the specified filename.
+/// Creates a JSON file from `DeathsInfoSummary` and writes it atomically to the specified filename.
 ///
 /// # Arguments
 ///
 /// * `filename` - The path to the file where the JSON will be written.
 /// * `deaths_info` - The `DeathsInfoSummary` data to be included in the JSON file.
+/// * `metadata` - The `OutputMetadata` of the run.
 ///
 /// # Returns
 ///
@@ -39,14 +43,92 @@ fn generate_json(padron: u32, deaths_info: DeathsInfoSummary) -> Result<String,
 pub fn create_json_file(
     filename: &str,
     deaths_info: DeathsInfoSummary,
+    metadata: OutputMetadata,
 ) -> Result<(), FileCreationError> {
-    let json_output = generate_json(PADRON, deaths_info)?;
+    let json_output = generate_json(metadata, deaths_info)?;
 
-    let file = File::create(filename).map_err(|_| FileCreationError::FileCreation)?;
+    write_atomically(filename, |writer| {
+        writer
+            .write_all(json_output.as_bytes())
+            .map_err(|_| FileCreationError::FileWrite)
+    })
+}
+
+/// Creates a JSON Lines file with one `MatchSummary` record per line.
+///
+/// # Arguments
+///
+/// * `filename` - The path to the file where the records will be written.
+/// * `matches` - The `MatchSummary` of every match.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure. Errors are represented by `FileCreationError`.
+pub fn create_matches_file(
+    filename: &str,
+    matches: &[MatchSummary],
+) -> Result<(), FileCreationError> {
+    write_atomically(filename, |writer| {
+        for match_summary in matches {
+            serde_json::to_writer(&mut *writer, match_summary)
+                .map_err(|_| FileCreationError::Serialization)?;
+            writer
+                .write_all(b"\n")
+                .map_err(|_| FileCreationError::FileWrite)?;
+        }
+        Ok(())
+    })
+}
+
+/// Writes a file atomically: the content is written to a temporary file next to it, which is then renamed.
+///
+/// Readers of `filename` never see a partially written file, which matters when the file is rewritten while
+/// other processes are reading it. If the write or the rename fails, the temporary file is removed.
+///
+/// # Arguments
+///
+/// * `filename` - The path to the file to write.
+/// * `write_content` - A function that writes the content of the file to the given writer.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure. Errors are represented by `FileCreationError`.
+fn write_atomically(
+    filename: &str,
+    write_content: impl FnOnce(&mut BufWriter<File>) -> Result<(), FileCreationError>,
+) -> Result<(), FileCreationError> {
+    let temp_filename = format!("{}.tmp", filename);
+
+    let file = File::create(&temp_filename).map_err(|_| FileCreationError::FileCreation)?;
+    let result = write_temp_file(file, write_content).and_then(|()| {
+        rename(&temp_filename, filename).map_err(|_| FileCreationError::FileCreation)
+    });
+    if result.is_err() {
+        // The temporary file is of no use if it could not be written or renamed.
+        let _ = remove_file(&temp_filename);
+    }
+    result
+}
+
+/// Writes the content to the temporary file and syncs it to disk.
+///
+/// # Arguments
+///
+/// * `file` - The temporary file.
+/// * `write_content` - A function that writes the content of the file to the given writer.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure. Errors are represented by `FileCreationError`.
+fn write_temp_file(
+    file: File,
+    write_content: impl FnOnce(&mut BufWriter<File>) -> Result<(), FileCreationError>,
+) -> Result<(), FileCreationError> {
     let mut writer = BufWriter::new(file);
+    write_content(&mut writer)?;
+    writer.flush().map_err(|_| FileCreationError::FileWrite)?;
     writer
-        .write_all(json_output.as_bytes())
-        .map_err(|_| FileCreationError::FileWrite)?;
-
-    Ok(())
+        .get_ref()
+        .sync_all()
+        .map_err(|_| FileCreationError::FileWrite)
 }
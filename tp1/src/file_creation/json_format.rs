@@ -1,15 +1,19 @@
 use crate::data_summary::deaths_info_summary::DeathsInfoSummary;
-use serde::Serialize;
+use crate::metadata::output_metadata::OutputMetadata;
+use serde::{Deserialize, Serialize};
 
-/// Wrapper structure for serializing `DeathsInfoSummary` with an additional `padron` field.
+/// Wrapper structure for serializing `DeathsInfoSummary` with an additional `metadata` block.
+///
+/// Files written before the metadata block existed are still read, with a default `OutputMetadata`.
 ///
 /// # Fields
 ///
-/// * `padron` - Student's padron.
+/// * `metadata` - The `OutputMetadata` of the run that produced the summary.
 /// * `deaths_info` - The `DeathsInfoSummary` data, flattened into the JSON object.
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct JsonFormat {
-    pub(crate) padron: u32,
+    #[serde(default)]
+    pub(crate) metadata: OutputMetadata,
     #[serde(flatten)]
     pub(crate) deaths_info: DeathsInfoSummary,
 }
@@ -1,3 +1,4 @@
-mod file_creation_error;
+pub mod file_creation_error;
 pub mod file_creator;
-mod json_format;
+pub mod json_format;
+pub mod sqlite_exporter;
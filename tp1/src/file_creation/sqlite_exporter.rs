@@ -0,0 +1,239 @@
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::weapon_stats::WeaponStats;
+use crate::data_summary::data_summarizer::compute_weapon_stats;
+use crate::file_creation::file_creation_error::FileCreationError;
+use crate::metadata::output_metadata::OutputMetadata;
+
+use rusqlite::{params, Connection, Transaction};
+use serde_json::Value;
+use std::fs::{remove_file, rename};
+
+/// Statements that create the tables of the database.
+const SCHEMA: &str = "
+    CREATE TABLE metadata (
+        key TEXT PRIMARY KEY,
+        value
+    );
+    CREATE TABLE players (
+        name TEXT PRIMARY KEY,
+        deaths INTEGER NOT NULL,
+        weapons_used INTEGER NOT NULL
+    );
+    CREATE TABLE weapons (
+        name TEXT PRIMARY KEY,
+        deaths INTEGER NOT NULL,
+        valid_distances_count INTEGER NOT NULL,
+        total_distance REAL NOT NULL,
+        average_distance REAL
+    );
+    CREATE TABLE player_weapons (
+        player TEXT NOT NULL REFERENCES players (name),
+        weapon TEXT NOT NULL,
+        deaths INTEGER NOT NULL,
+        valid_distances_count INTEGER NOT NULL,
+        total_distance REAL NOT NULL,
+        average_distance REAL,
+        PRIMARY KEY (player, weapon)
+    );
+    CREATE TABLE weapon_range_bands (
+        weapon TEXT NOT NULL REFERENCES weapons (name),
+        map TEXT NOT NULL,
+        close INTEGER NOT NULL,
+        mid INTEGER NOT NULL,
+        long INTEGER NOT NULL,
+        extreme INTEGER NOT NULL,
+        PRIMARY KEY (weapon, map)
+    );
+    CREATE INDEX player_weapons_by_weapon ON player_weapons (weapon);
+";
+
+/// Writes the full per-player, per-weapon and per-player-weapon tables and the metadata of the run to a SQLite
+/// database, so they can be queried with SQL.
+///
+/// Unlike the JSON summary, every player and weapon is included. The database is written to a temporary file
+/// which is then renamed, replacing any previous database at `filename`.
+///
+/// # Arguments
+///
+/// * `filename` - The path of the SQLite database.
+/// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
+/// * `metadata` - The `OutputMetadata` of the run.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure. Errors are represented by `FileCreationError`.
+pub fn create_sqlite_file(
+    filename: &str,
+    deaths_info: &DeathsInfo,
+    metadata: &OutputMetadata,
+) -> Result<(), FileCreationError> {
+    let temp_filename = format!("{}.tmp", filename);
+    let _ = remove_file(&temp_filename);
+
+    let result = write_database(&temp_filename, deaths_info, metadata).and_then(|()| {
+        rename(&temp_filename, filename).map_err(|_| FileCreationError::FileCreation)
+    });
+    if result.is_err() {
+        // The temporary database is of no use if it could not be written or renamed.
+        let _ = remove_file(&temp_filename);
+    }
+    result
+}
+
+/// Creates the tables of the database at `temp_filename` and inserts the rows in a single transaction.
+///
+/// # Arguments
+///
+/// * `temp_filename` - The path of the temporary database.
+/// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
+/// * `metadata` - The `OutputMetadata` of the run.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure. Errors are represented by `FileCreationError`.
+fn write_database(
+    temp_filename: &str,
+    deaths_info: &DeathsInfo,
+    metadata: &OutputMetadata,
+) -> Result<(), FileCreationError> {
+    let mut connection = Connection::open(temp_filename).map_err(database_error)?;
+    let transaction = connection.transaction().map_err(database_error)?;
+    transaction.execute_batch(SCHEMA).map_err(database_error)?;
+
+    insert_metadata(&transaction, metadata)?;
+    insert_players(&transaction, deaths_info).map_err(database_error)?;
+    insert_weapons(&transaction, deaths_info).map_err(database_error)?;
+
+    transaction.commit().map_err(database_error)?;
+    connection.close().map_err(|(_, err)| database_error(err))
+}
+
+/// Inserts one row per field of the metadata. Numbers and strings are stored as they are, and objects such as the
+/// filters and the input files are stored as JSON text, which can be read with the SQLite JSON functions.
+///
+/// # Arguments
+///
+/// * `transaction` - The open `Transaction`.
+/// * `metadata` - The `OutputMetadata` of the run.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure. Errors are represented by `FileCreationError`.
+fn insert_metadata(
+    transaction: &Transaction,
+    metadata: &OutputMetadata,
+) -> Result<(), FileCreationError> {
+    let Value::Object(fields) =
+        serde_json::to_value(metadata).map_err(|_| FileCreationError::Serialization)?
+    else {
+        return Err(FileCreationError::Serialization);
+    };
+
+    let mut statement = transaction
+        .prepare("INSERT INTO metadata (key, value) VALUES (?1, ?2)")
+        .map_err(database_error)?;
+    for (key, value) in fields {
+        let result = match value {
+            Value::Number(number) if number.is_i64() => {
+                statement.execute(params![key, number.as_i64()])
+            }
+            Value::Number(number) => statement.execute(params![key, number.as_f64()]),
+            Value::String(text) => statement.execute(params![key, text]),
+            Value::Bool(flag) => statement.execute(params![key, flag]),
+            other => statement.execute(params![key, other.to_string()]),
+        };
+        result.map_err(database_error)?;
+    }
+    Ok(())
+}
+
+/// Inserts every player and the stats of every weapon they used.
+///
+/// # Arguments
+///
+/// * `transaction` - The open `Transaction`.
+/// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure, with the `rusqlite::Error` if an insert fails.
+fn insert_players(transaction: &Transaction, deaths_info: &DeathsInfo) -> rusqlite::Result<()> {
+    let mut insert_player = transaction
+        .prepare("INSERT INTO players (name, deaths, weapons_used) VALUES (?1, ?2, ?3)")?;
+    let mut insert_player_weapon = transaction.prepare(
+        "INSERT INTO player_weapons (player, weapon, deaths, valid_distances_count, total_distance, average_distance) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+
+    for (player, stats) in &deaths_info.players {
+        insert_player.execute(params![
+            player,
+            stats.deaths,
+            stats.used_weapons.len() as i64
+        ])?;
+        for (weapon, weapon_stats) in &stats.used_weapons {
+            insert_player_weapon.execute(params![
+                player,
+                weapon,
+                weapon_stats.deaths,
+                weapon_stats.valid_distances_count,
+                weapon_stats.total_distance.centimeters(),
+                average_distance(weapon_stats),
+            ])?;
+        }
+    }
+    Ok(())
+}
+
+/// Inserts the stats of every weapon, including the deaths that are not player kills, and its kills in each range
+/// band on each map.
+///
+/// # Arguments
+///
+/// * `transaction` - The open `Transaction`.
+/// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure, with the `rusqlite::Error` if an insert fails.
+fn insert_weapons(transaction: &Transaction, deaths_info: &DeathsInfo) -> rusqlite::Result<()> {
+    let mut insert_weapon = transaction.prepare(
+        "INSERT INTO weapons (name, deaths, valid_distances_count, total_distance, average_distance) \
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    let mut insert_range_bands = transaction.prepare(
+        "INSERT INTO weapon_range_bands (weapon, map, close, mid, long, extreme) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+
+    for (weapon, stats) in compute_weapon_stats(deaths_info) {
+        insert_weapon.execute(params![
+            weapon,
+            stats.deaths,
+            stats.valid_distances_count,
+            stats.total_distance.centimeters(),
+            average_distance(&stats),
+        ])?;
+        for (map, counts) in &stats.range_bands {
+            insert_range_bands.execute(params![
+                weapon,
+                map,
+                counts.close,
+                counts.mid,
+                counts.long,
+                counts.extreme,
+            ])?;
+        }
+    }
+    Ok(())
+}
+
+/// Wraps a `rusqlite::Error` in a `FileCreationError`, keeping its message.
+fn database_error(err: rusqlite::Error) -> FileCreationError {
+    FileCreationError::Database(err.to_string())
+}
+
+/// Returns the average distance of the kills with a valid distance, or `None` if there are none.
+fn average_distance(stats: &WeaponStats) -> Option<f64> {
+    (stats.valid_distances_count > 0)
+        .then(|| stats.total_distance.centimeters() / stats.valid_distances_count as f64)
+}
@@ -0,0 +1,40 @@
+use std::io::{self, ErrorKind, Read};
+use std::net::TcpStream;
+use std::time::Instant;
+
+/// Reads from a connection until a deadline.
+///
+/// The read timeout of a socket bounds each `read` on its own, so a client sending a byte at a time could keep a
+/// connection open forever. Before every read the timeout is shrunk to the time left until the deadline, so the
+/// whole request has to arrive before it.
+///
+/// # Fields
+///
+/// * `stream` - The `TcpStream` of the client.
+/// * `deadline` - The `Instant` after which reads fail with `ErrorKind::TimedOut`.
+#[derive(Debug)]
+pub(crate) struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl<'a> DeadlineReader<'a> {
+    /// Builds a reader of the given connection that stops reading at the deadline.
+    pub(crate) fn new(stream: &'a TcpStream, deadline: Instant) -> Self {
+        DeadlineReader { stream, deadline }
+    }
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(
+                ErrorKind::TimedOut,
+                "Request deadline exceeded",
+            ));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// A parsed HTTP request line.
+///
+/// # Fields
+///
+/// * `method` - The HTTP method, for example `GET`.
+/// * `segments` - The decoded, non-empty segments of the path.
+/// * `params` - The decoded query string parameters.
+#[derive(Debug)]
+pub struct HttpRequest {
+    pub(crate) method: String,
+    pub(crate) segments: Vec<String>,
+    pub(crate) params: HashMap<String, String>,
+}
+
+impl HttpRequest {
+    /// Parses the request line of an HTTP request, for example `GET /top/kills?limit=5 HTTP/1.1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `request_line` - The first line of the request.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<HttpRequest>`, or `None` if the line is not a valid request line.
+    pub fn parse(request_line: &str) -> Option<HttpRequest> {
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next()?.to_owned();
+        let target = parts.next()?;
+        parts.next()?;
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| percent_decode(segment, false))
+            .collect();
+        let params = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(key, true), percent_decode(value, true))
+            })
+            .collect();
+
+        Some(HttpRequest {
+            method,
+            segments,
+            params,
+        })
+    }
+
+    /// Parses a query string parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(value))` - If the parameter is present and valid.
+    /// * `Ok(None)` - If the parameter is not present.
+    /// * `Err(String)` - An error message if the parameter cannot be parsed.
+    pub fn param<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.params
+            .get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid value {} for parameter {}", value, name))
+            })
+            .transpose()
+    }
+}
+
+/// Decodes a percent-encoded URL component.
+///
+/// Invalid escapes are kept as they are, and invalid UTF-8 is replaced with the replacement character.
+///
+/// # Arguments
+///
+/// * `component` - The path segment or query string key or value to decode.
+/// * `plus_as_space` - Whether `+` is turned into a space, which only form-encoded query strings do.
+fn percent_decode(component: &str, plus_as_space: bool) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escaped = match bytes[index] {
+            b'%' => component
+                .get(index + 1..index + 3)
+                .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match (escaped, bytes[index]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (None, b'+') if plus_as_space => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
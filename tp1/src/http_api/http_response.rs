@@ -0,0 +1,83 @@
+use serde::Serialize;
+use serde_json::json;
+use std::io::{self, Write};
+
+/// An HTTP response with a JSON body.
+///
+/// # Fields
+///
+/// * `status` - The HTTP status code.
+/// * `body` - The JSON body.
+#[derive(Debug)]
+pub struct HttpResponse {
+    pub(crate) status: u16,
+    pub(crate) body: String,
+}
+
+impl HttpResponse {
+    /// Builds a `200 OK` response with the value serialized as JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to serialize.
+    ///
+    /// # Returns
+    ///
+    /// The `HttpResponse`, or a `500` response if the value cannot be serialized.
+    pub fn json<T: Serialize>(value: &T) -> HttpResponse {
+        match serde_json::to_string_pretty(value) {
+            Ok(body) => HttpResponse { status: 200, body },
+            Err(err) => HttpResponse::error(500, &format!("Failed to serialize result: {}", err)),
+        }
+    }
+
+    /// Builds an error response with a `{"error": message}` body.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - The HTTP status code.
+    /// * `message` - The error message.
+    ///
+    /// # Returns
+    ///
+    /// The `HttpResponse`.
+    pub fn error(status: u16, message: &str) -> HttpResponse {
+        HttpResponse {
+            status,
+            body: json!({ "error": message }).to_string(),
+        }
+    }
+
+    /// Writes the response, closing the connection afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where the response is written, usually the `TcpStream` of the client.
+    ///
+    /// # Returns
+    ///
+    /// An `io::Result` indicating whether the response was written.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )?;
+        writer.flush()
+    }
+
+    /// Returns the reason phrase of the status code.
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            414 => "URI Too Long",
+            _ => "Internal Server Error",
+        }
+    }
+}
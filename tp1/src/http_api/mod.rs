@@ -0,0 +1,5 @@
+pub mod deadline_reader;
+pub mod http_request;
+pub mod http_response;
+pub mod query_server;
+pub mod server_error;
@@ -0,0 +1,208 @@
+use crate::config::processing_config::ProcessingConfig;
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_query::data_querier::{query_player, query_top, query_weapon};
+use crate::data_query::query_data::QueryData;
+use crate::data_query::top_filter::TopFilter;
+use crate::data_query::top_metric::TopMetric;
+use crate::http_api::deadline_reader::DeadlineReader;
+use crate::http_api::http_request::HttpRequest;
+use crate::http_api::http_response::HttpResponse;
+use crate::http_api::server_error::ServerError;
+
+use clap::ValueEnum;
+use std::io::{self, BufRead, BufReader, Read};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::sync_channel;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Number of entries returned by the top endpoint when no `limit` is given.
+const DEFAULT_TOP_LIMIT: usize = 10;
+
+/// Number of threads that handle connections.
+const WORKERS: usize = 8;
+
+/// Number of accepted connections that may wait for a free worker. When it is reached, new connections wait in the
+/// listen backlog of the system.
+const QUEUED_CONNECTIONS: usize = 32;
+
+/// Time a client has to send its whole request. Each write of the response also fails if it takes longer.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest number of bytes read from a request, counting the request line and the headers.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// Serves queries over the aggregated deaths info through a local HTTP API until the process is stopped.
+///
+/// The endpoints are:
+///
+/// * `GET /top/<metric>?limit=<n>&min_value=<v>&name_contains=<text>` - Ranks players or weapons by a metric.
+/// * `GET /players/<name>` - The full weapon breakdown of a player.
+/// * `GET /weapons/<name>` - The stats of a weapon and its top players.
+///
+/// The totals of every weapon are computed once before listening. Connections are handled by a fixed pool of
+/// `WORKERS` threads, and each one is closed if the client does not send its whole request within
+/// `CONNECTION_TIMEOUT` or sends more than `MAX_REQUEST_BYTES`.
+///
+/// # Arguments
+///
+/// * `deaths_info` - The aggregated `DeathsInfo` of all the processed files.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `address` - The address the server listens on, for example `127.0.0.1:8080`.
+///
+/// # Returns
+///
+/// A `ServerError` if the server cannot listen on the address.
+pub fn serve(
+    deaths_info: &DeathsInfo,
+    config: &ProcessingConfig,
+    address: &str,
+) -> Result<(), ServerError> {
+    let listener = TcpListener::bind(address).map_err(|_| ServerError::Bind(address.to_owned()))?;
+    let query_data = QueryData::new(deaths_info, config);
+    println!("Listening on http://{}", address);
+
+    let (sender, receiver) = sync_channel::<TcpStream>(QUEUED_CONNECTIONS);
+    let receiver = Mutex::new(receiver);
+    thread::scope(|scope| {
+        for _ in 0..WORKERS {
+            scope.spawn(|| loop {
+                let stream = match receiver.lock() {
+                    Ok(receiver) => receiver.recv(),
+                    Err(_) => return,
+                };
+                match stream {
+                    Ok(stream) => handle_connection(stream, &query_data),
+                    Err(_) => return,
+                }
+            });
+        }
+
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if sender.send(stream).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => eprintln!("Failed to accept connection: {}", err),
+            }
+        }
+        drop(sender);
+    });
+    Ok(())
+}
+
+/// Reads a request from a connection and writes the response to it.
+///
+/// At most `MAX_REQUEST_BYTES` are read, and the connection is closed without a response if the client does not
+/// send the request line and the headers within `CONNECTION_TIMEOUT` of the connection being handled.
+///
+/// # Arguments
+///
+/// * `stream` - The `TcpStream` of the client.
+/// * `query_data` - The `QueryData` of all the processed files.
+pub fn handle_connection(mut stream: TcpStream, query_data: &QueryData) {
+    let deadline = Instant::now() + CONNECTION_TIMEOUT;
+    if let Err(err) = stream.set_write_timeout(Some(CONNECTION_TIMEOUT)) {
+        eprintln!("Failed to set connection timeouts: {}", err);
+        return;
+    }
+
+    let mut reader = BufReader::new(DeadlineReader::new(&stream, deadline).take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    if !request_line.ends_with('\n') && request_line.len() as u64 == MAX_REQUEST_BYTES {
+        let response = HttpResponse::error(414, "Request line too long");
+        if let Err(err) = response.write_to(&mut stream) {
+            eprintln!("Failed to write response: {}", err);
+        }
+        // Closing with unread data resets the connection, which may drop the response before the client reads it,
+        // so a bounded amount of the rest of the request is discarded first.
+        let _ = stream.shutdown(Shutdown::Write);
+        let _ = io::copy(
+            &mut DeadlineReader::new(&stream, deadline).take(MAX_REQUEST_BYTES),
+            &mut io::sink(),
+        );
+        return;
+    }
+
+    // The headers are not used, but they are read so the client does not get a reset connection.
+    let mut header = String::new();
+    while reader.read_line(&mut header).is_ok_and(|read| read > 0) && header.trim() != "" {
+        header.clear();
+    }
+
+    let response = match HttpRequest::parse(&request_line) {
+        Some(request) => route(&request, query_data),
+        None => HttpResponse::error(400, "Invalid request"),
+    };
+    if let Err(err) = response.write_to(&mut stream) {
+        eprintln!("Failed to write response: {}", err);
+    }
+}
+
+/// Dispatches a request to the query of its endpoint.
+///
+/// # Arguments
+///
+/// * `request` - The parsed `HttpRequest`.
+/// * `query_data` - The `QueryData` of all the processed files.
+///
+/// # Returns
+///
+/// The `HttpResponse` with the result of the query, or an error response.
+fn route(request: &HttpRequest, query_data: &QueryData) -> HttpResponse {
+    if request.method != "GET" {
+        return HttpResponse::error(405, "Only GET requests are supported");
+    }
+
+    let segments: Vec<&str> = request.segments.iter().map(String::as_str).collect();
+    match segments.as_slice() {
+        ["top", metric] => top(request, query_data, metric),
+        ["players", name] => match query_player(query_data, name) {
+            Some(breakdown) => HttpResponse::json(&breakdown),
+            None => HttpResponse::error(404, &format!("Player {} has no kills.", name)),
+        },
+        ["weapons", name] => match query_weapon(query_data, name) {
+            Some(details) => HttpResponse::json(&details),
+            None => HttpResponse::error(404, &format!("Weapon {} caused no deaths.", name)),
+        },
+        _ => HttpResponse::error(404, "Unknown endpoint"),
+    }
+}
+
+/// Answers the top endpoint, reading the limit and the filters from the query string.
+///
+/// # Arguments
+///
+/// * `request` - The parsed `HttpRequest`.
+/// * `query_data` - The `QueryData` of all the processed files.
+/// * `metric` - The name of the `TopMetric`, for example `weapon-deaths`.
+///
+/// # Returns
+///
+/// The `HttpResponse` with the ranked entries, or a `400` response if a parameter is invalid.
+fn top(request: &HttpRequest, query_data: &QueryData, metric: &str) -> HttpResponse {
+    let Ok(metric) = TopMetric::from_str(metric, true) else {
+        return HttpResponse::error(400, &format!("Unknown metric {}", metric));
+    };
+
+    let params = request.param("limit").and_then(|limit| {
+        Ok((
+            limit.unwrap_or(DEFAULT_TOP_LIMIT),
+            TopFilter {
+                min_value: request.param("min_value")?,
+                name_contains: request.param("name_contains")?,
+            },
+        ))
+    });
+
+    match params {
+        Ok((limit, filter)) => HttpResponse::json(&query_top(query_data, metric, limit, &filter)),
+        Err(message) => HttpResponse::error(400, &message),
+    }
+}
@@ -0,0 +1,19 @@
+use std::fmt;
+
+/// Errors that may occur while running the query server.
+///
+/// # Variants
+///
+/// * `Bind` - The server could not listen on the address. Holds the address.
+#[derive(Debug)]
+pub enum ServerError {
+    Bind(String),
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerError::Bind(address) => write!(f, "Could not listen on {}", address),
+        }
+    }
+}
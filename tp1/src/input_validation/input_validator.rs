@@ -0,0 +1,323 @@
+use crate::config::processing_config::ProcessingConfig;
+use crate::config::text_encoding::{TextEncoding, UTF8_BOM};
+use crate::data_processing::columnar_reader::inspect_columnar_file;
+use crate::data_processing::data_processor::{collect_input_files, update_stats_from_line};
+use crate::data_processing::death_record::COLUMN_NAMES;
+use crate::data_processing::deaths_info::DeathsInfo;
+use crate::data_processing::input_format::InputFormat;
+use crate::input_validation::validation_error::ValidationError;
+use crate::input_validation::validation_report::{
+    FileValidation, IssueKind, ValidationIssue, ValidationReport,
+};
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::str;
+use std::time::{Duration, Instant};
+
+/// Maximum number of issues listed for each file. Every row with a problem is still counted.
+const MAX_LISTED_ISSUES: usize = 10;
+
+/// Checks the headers and a sample of the rows of every input file, without aggregating them.
+///
+/// The CSV files are checked for a header with the columns of the deaths files, rows that are not valid UTF-8 and
+/// rows with the wrong number of columns. Parquet and Arrow IPC files are checked for the needed columns. The
+/// sampled CSV rows are also processed, and the time spent is used to estimate the runtime of the whole input.
+///
+/// # Arguments
+///
+/// * `dir_path` - The directory containing the input files.
+/// * `threads` - The number of threads the runtime is estimated for.
+/// * `config` - The `ProcessingConfig` used to process the sampled rows.
+/// * `sample_rows` - The number of rows checked from the start of each CSV file.
+///
+/// # Returns
+///
+/// A `Result` containing the `ValidationReport`, or a `ValidationError` if there are no input files.
+pub fn validate_directory(
+    dir_path: &str,
+    threads: usize,
+    config: &ProcessingConfig,
+    sample_rows: u64,
+) -> Result<ValidationReport, ValidationError> {
+    let mut paths = collect_input_files(dir_path);
+    if paths.is_empty() {
+        return Err(ValidationError::NoInputFiles(dir_path.to_owned()));
+    }
+    paths.sort();
+
+    let (mut timed_rows, mut elapsed) = (0, Duration::ZERO);
+    let files: Vec<_> = paths
+        .iter()
+        .map(|path| match InputFormat::of_path(path) {
+            Some(format @ (InputFormat::Parquet | InputFormat::ArrowIpc)) => {
+                validate_columnar_file(path, format)
+            }
+            _ => {
+                let (validation, file_elapsed) = validate_csv_file(path, config, sample_rows);
+                timed_rows += validation.sampled_rows;
+                elapsed += file_elapsed;
+                validation
+            }
+        })
+        .collect();
+
+    let expected_rows = files.iter().map(|file| file.expected_rows).sum();
+    let parallel_files = threads.clamp(1, files.len());
+    let estimated_runtime_seconds = (timed_rows > 0).then(|| {
+        let seconds_per_row = elapsed.as_secs_f64() / timed_rows as f64;
+        let seconds = expected_rows as f64 * seconds_per_row / parallel_files as f64;
+        (seconds * 100.0).round() / 100.0
+    });
+
+    Ok(ValidationReport {
+        files,
+        expected_rows,
+        threads,
+        estimated_runtime_seconds,
+    })
+}
+
+/// Checks the header and the first rows of a CSV file.
+///
+/// # Arguments
+///
+/// * `path` - The path to the CSV file.
+/// * `config` - The `ProcessingConfig` used to process the sampled rows.
+/// * `sample_rows` - The number of rows checked.
+///
+/// # Returns
+///
+/// The `FileValidation` of the file and the time spent reading and processing the sampled rows.
+fn validate_csv_file(
+    path: &PathBuf,
+    config: &ProcessingConfig,
+    sample_rows: u64,
+) -> (FileValidation, Duration) {
+    let mut validation = new_file_validation(path);
+    let mut reader = match File::open(path) {
+        Ok(file) => BufReader::new(file),
+        Err(err) => {
+            add_issue(
+                &mut validation,
+                IssueKind::Unreadable,
+                None,
+                err.to_string(),
+            );
+            return (validation, Duration::ZERO);
+        }
+    };
+
+    let mut line = Vec::new();
+    let header_bytes = match reader.read_until(b'\n', &mut line) {
+        Ok(0) => {
+            let message = "The file is empty, the header is missing".to_owned();
+            add_issue(&mut validation, IssueKind::SchemaMismatch, Some(1), message);
+            return (validation, Duration::ZERO);
+        }
+        Ok(bytes) => bytes as u64,
+        Err(err) => {
+            add_issue(
+                &mut validation,
+                IssueKind::Unreadable,
+                Some(1),
+                err.to_string(),
+            );
+            return (validation, Duration::ZERO);
+        }
+    };
+    check_header(&mut validation, &line);
+
+    let start = Instant::now();
+    let mut deaths_info = DeathsInfo::default();
+    let (mut sampled_bytes, mut reached_end) = (0, false);
+    while validation.sampled_rows < sample_rows {
+        line.clear();
+        let line_number = validation.sampled_rows + 2;
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => {
+                reached_end = true;
+                break;
+            }
+            Ok(bytes) => sampled_bytes += bytes as u64,
+            Err(err) => {
+                let message = err.to_string();
+                add_issue(
+                    &mut validation,
+                    IssueKind::Unreadable,
+                    Some(line_number),
+                    message,
+                );
+                break;
+            }
+        }
+        validation.sampled_rows += 1;
+        check_row(
+            &mut validation,
+            line_number,
+            trim_line_end(&line),
+            config,
+            &mut deaths_info,
+        );
+    }
+    if !reached_end {
+        reached_end = matches!(reader.fill_buf(), Ok(buffer) if buffer.is_empty());
+    }
+    let elapsed = start.elapsed();
+
+    validation.expected_rows = if reached_end || sampled_bytes == 0 {
+        validation.sampled_rows
+    } else {
+        let remaining_bytes = validation.size.saturating_sub(header_bytes + sampled_bytes);
+        let bytes_per_row = sampled_bytes as f64 / validation.sampled_rows as f64;
+        validation.sampled_rows + (remaining_bytes as f64 / bytes_per_row).round() as u64
+    };
+    (validation, elapsed)
+}
+
+/// Checks that the header of a CSV file has the columns of the deaths files, in order. A byte order mark is
+/// ignored.
+///
+/// # Arguments
+///
+/// * `validation` - The `FileValidation` the problems are added to.
+/// * `header` - The first line of the file.
+fn check_header(validation: &mut FileValidation, header: &[u8]) {
+    let header = trim_line_end(header.strip_prefix(UTF8_BOM).unwrap_or(header));
+    let header = match str::from_utf8(header) {
+        Ok(header) => header,
+        Err(err) => {
+            let message = format!("The header is not valid UTF-8: {}", err);
+            add_issue(validation, IssueKind::Encoding, Some(1), message);
+            return;
+        }
+    };
+
+    let columns: Vec<_> = header.split(',').map(str::trim).collect();
+    if columns == COLUMN_NAMES {
+        return;
+    }
+    let missing: Vec<_> = COLUMN_NAMES
+        .iter()
+        .filter(|name| !columns.contains(name))
+        .copied()
+        .collect();
+    let unexpected: Vec<_> = columns
+        .iter()
+        .filter(|name| !COLUMN_NAMES.contains(name))
+        .copied()
+        .collect();
+    let message = if missing.is_empty() && unexpected.is_empty() {
+        format!(
+            "The columns are not in the expected order: {}",
+            COLUMN_NAMES.join(",")
+        )
+    } else {
+        format!(
+            "Missing columns: [{}], unexpected columns: [{}]",
+            missing.join(", "),
+            unexpected.join(", ")
+        )
+    };
+    add_issue(validation, IssueKind::SchemaMismatch, Some(1), message);
+}
+
+/// Checks the encoding and the number of columns of a row, and processes it decoded with the encoding of the config.
+///
+/// # Arguments
+///
+/// * `validation` - The `FileValidation` the problems are added to.
+/// * `line_number` - The line of the row in the file.
+/// * `row` - The bytes of the row, without the line end.
+/// * `config` - The `ProcessingConfig` used to process the row.
+/// * `deaths_info` - The `DeathsInfo` the row is processed into, which is discarded.
+fn check_row(
+    validation: &mut FileValidation,
+    line_number: u64,
+    row: &[u8],
+    config: &ProcessingConfig,
+    deaths_info: &mut DeathsInfo,
+) {
+    if let Err(err) = str::from_utf8(row.strip_prefix(UTF8_BOM).unwrap_or(row)) {
+        validation.invalid_encoding_rows += 1;
+        let decoded_as = match config.encoding {
+            TextEncoding::Auto | TextEncoding::Latin1 => "Latin-1",
+            TextEncoding::Utf8Lossy => "UTF-8 with replacement characters",
+        };
+        let message = format!(
+            "Invalid UTF-8 after byte {}, the row is read as {}",
+            err.valid_up_to(),
+            decoded_as
+        );
+        add_issue(validation, IssueKind::Encoding, Some(line_number), message);
+    }
+    let row = config.encoding.decode(row);
+
+    let columns = row.split(',').count();
+    if columns != COLUMN_NAMES.len() {
+        validation.wrong_column_count_rows += 1;
+        let message = format!("Expected {} columns, found {}", COLUMN_NAMES.len(), columns);
+        add_issue(
+            validation,
+            IssueKind::SchemaMismatch,
+            Some(line_number),
+            message,
+        );
+    }
+    update_stats_from_line(&row, deaths_info, config);
+}
+
+/// Checks that a Parquet or Arrow IPC file can be read and has the needed columns, and counts its rows.
+///
+/// # Arguments
+///
+/// * `path` - The path to the columnar file.
+/// * `format` - The `InputFormat` of the file.
+///
+/// # Returns
+///
+/// The `FileValidation` of the file.
+fn validate_columnar_file(path: &PathBuf, format: InputFormat) -> FileValidation {
+    let mut validation = new_file_validation(path);
+    match inspect_columnar_file(path, format) {
+        Ok((rows, missing)) => {
+            validation.expected_rows = rows;
+            if !missing.is_empty() {
+                let message = format!("Missing columns: [{}]", missing.join(", "));
+                add_issue(&mut validation, IssueKind::SchemaMismatch, None, message);
+            }
+        }
+        Err(err) => add_issue(&mut validation, IssueKind::Unreadable, None, err),
+    }
+    validation
+}
+
+/// Builds an empty `FileValidation` with the name and size of a file.
+fn new_file_validation(path: &Path) -> FileValidation {
+    FileValidation {
+        file: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        size: path.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+        ..FileValidation::default()
+    }
+}
+
+/// Adds a problem to a `FileValidation`, unless `MAX_LISTED_ISSUES` are already listed.
+fn add_issue(validation: &mut FileValidation, kind: IssueKind, line: Option<u64>, message: String) {
+    if validation.issues.len() < MAX_LISTED_ISSUES {
+        validation.issues.push(ValidationIssue {
+            kind,
+            line,
+            message,
+        });
+    }
+}
+
+/// Removes the `\n` or `\r\n` at the end of a line.
+fn trim_line_end(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
@@ -0,0 +1,3 @@
+pub mod input_validator;
+pub mod validation_error;
+pub mod validation_report;
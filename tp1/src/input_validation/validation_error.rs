@@ -0,0 +1,21 @@
+use std::fmt;
+
+/// Errors that prevent the input from being validated at all.
+///
+/// # Variants
+///
+/// * `NoInputFiles` - The input directory has no input files, or cannot be read. Holds the path of the directory.
+#[derive(Debug)]
+pub enum ValidationError {
+    NoInputFiles(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::NoInputFiles(path) => {
+                write!(f, "Directory {} has no input files", path)
+            }
+        }
+    }
+}
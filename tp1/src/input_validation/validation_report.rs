@@ -0,0 +1,148 @@
+use serde::Serialize;
+use std::fmt;
+
+/// The kinds of problems found in the input files.
+///
+/// # Variants
+///
+/// * `Unreadable` - The file cannot be opened or read.
+/// * `SchemaMismatch` - The header or the columns of the file, or the number of fields of a row, do not match the
+///   deaths files.
+/// * `Encoding` - A row is not valid UTF-8.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    Unreadable,
+    SchemaMismatch,
+    Encoding,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueKind::Unreadable => write!(f, "unreadable"),
+            IssueKind::SchemaMismatch => write!(f, "schema mismatch"),
+            IssueKind::Encoding => write!(f, "encoding"),
+        }
+    }
+}
+
+/// A problem found in an input file.
+///
+/// # Fields
+///
+/// * `kind` - The `IssueKind` of the problem.
+/// * `line` - The line of the file where it was found, starting at `1` for the header, if it is about a line.
+/// * `message` - A description of the problem.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationIssue {
+    pub kind: IssueKind,
+    pub line: Option<u64>,
+    pub message: String,
+}
+
+/// The result of validating an input file.
+///
+/// # Fields
+///
+/// * `file` - The name of the file.
+/// * `size` - The size of the file, in bytes.
+/// * `sampled_rows` - The number of rows checked. Columnar files are not sampled, only their schema is checked.
+/// * `invalid_encoding_rows` - The number of sampled rows that are not valid UTF-8.
+/// * `wrong_column_count_rows` - The number of sampled rows without the 12 columns of the deaths files.
+/// * `expected_rows` - The number of rows of the file, exact if it was read to the end or is columnar, otherwise
+///   extrapolated from the size of the sampled rows.
+/// * `issues` - The first problems found in the file.
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct FileValidation {
+    pub file: String,
+    pub size: u64,
+    pub sampled_rows: u64,
+    pub invalid_encoding_rows: u64,
+    pub wrong_column_count_rows: u64,
+    pub expected_rows: u64,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl FileValidation {
+    /// Returns whether any problem was found in the file.
+    pub fn has_problems(&self) -> bool {
+        !self.issues.is_empty()
+    }
+}
+
+/// The report of the validation of the input files.
+///
+/// # Fields
+///
+/// * `files` - The `FileValidation` of every input file, sorted by name.
+/// * `expected_rows` - The number of rows of all the files.
+/// * `threads` - The number of threads the runtime was estimated for.
+/// * `estimated_runtime_seconds` - The estimated time to process every file, extrapolated from the time spent
+///   processing the sampled CSV rows, or `None` if no CSV row was sampled.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationReport {
+    pub files: Vec<FileValidation>,
+    pub expected_rows: u64,
+    pub threads: usize,
+    pub estimated_runtime_seconds: Option<f64>,
+}
+
+impl ValidationReport {
+    /// Returns the number of files with problems.
+    pub fn files_with_problems(&self) -> usize {
+        self.files.iter().filter(|file| file.has_problems()).count()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for file in &self.files {
+            let status = if file.has_problems() { "FAIL" } else { "ok" };
+            writeln!(
+                f,
+                "{} {} ({} bytes, ~{} rows, {} sampled)",
+                status, file.file, file.size, file.expected_rows, file.sampled_rows
+            )?;
+            if file.invalid_encoding_rows > 0 {
+                writeln!(
+                    f,
+                    "  {} sampled rows are not valid UTF-8",
+                    file.invalid_encoding_rows
+                )?;
+            }
+            if file.wrong_column_count_rows > 0 {
+                writeln!(
+                    f,
+                    "  {} sampled rows have the wrong number of columns",
+                    file.wrong_column_count_rows
+                )?;
+            }
+            for issue in &file.issues {
+                match issue.line {
+                    Some(line) => {
+                        writeln!(f, "  line {}: {}: {}", line, issue.kind, issue.message)?
+                    }
+                    None => writeln!(f, "  {}: {}", issue.kind, issue.message)?,
+                }
+            }
+        }
+        writeln!(f)?;
+
+        writeln!(
+            f,
+            "{} files, {} with problems",
+            self.files.len(),
+            self.files_with_problems()
+        )?;
+        writeln!(f, "Expected rows: {}", self.expected_rows)?;
+        match self.estimated_runtime_seconds {
+            Some(seconds) => writeln!(
+                f,
+                "Estimated runtime with {} threads: {:.2} s",
+                self.threads, seconds
+            ),
+            None => writeln!(f, "Estimated runtime: unknown, no CSV rows were sampled"),
+        }
+    }
+}
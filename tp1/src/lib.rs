@@ -0,0 +1,13 @@
+pub mod cli;
+pub mod cohort_analysis;
+pub mod config;
+pub mod data_processing;
+pub mod data_query;
+pub mod data_summary;
+pub mod file_creation;
+pub mod http_api;
+pub mod input_validation;
+pub mod metadata;
+pub mod summary_diff;
+pub mod synthetic_data;
+pub mod trend_analysis;
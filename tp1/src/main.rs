@@ -1,42 +1,373 @@
-mod data_processing;
-mod data_summary;
-mod file_creation;
+use tp1::cli::cli_args::{rewrite_legacy_args, Cli, Command, IngestArgs, SampleArgs};
+use tp1::cli::output_format::OutputFormat;
+use tp1::cohort_analysis::cohort_analyzer::compare_cohorts;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_directory;
+use tp1::data_processing::deaths_info::DeathsInfo;
+use tp1::data_processing::directory_watcher::watch_directory;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_query::data_querier::{query_player, query_top, query_weapon};
+use tp1::data_query::query_data::QueryData;
+use tp1::data_query::query_result::TopRanking;
+use tp1::data_summary::data_summarizer::summarize;
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+use tp1::file_creation::file_creator::{create_json_file, create_matches_file};
+use tp1::file_creation::sqlite_exporter::create_sqlite_file;
+use tp1::http_api::query_server::serve;
+use tp1::input_validation::input_validator::validate_directory;
+use tp1::metadata::input_fingerprint::InputFingerprint;
+use tp1::metadata::output_metadata::OutputMetadata;
+use tp1::summary_diff::summary_differ::diff_files;
+use tp1::trend_analysis::trend_analyzer::analyze_trends;
 
-use crate::data_processing::data_processor::process_data_in_parallel;
-use crate::file_creation::file_creator::create_json_file;
+use clap::Parser;
+use serde::Serialize;
 use std::env;
+use std::fmt::Display;
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-/// Parses command-line arguments for input path, number of threads, and output file name.
+/// Time between two updates of the progress shown on stderr.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Paths of the files the `summary` subcommand writes.
+///
+/// # Fields
+///
+/// * `summary` - The path of the JSON summary file.
+/// * `matches` - The path of the JSON Lines per-match file, if any.
+/// * `sqlite` - The path of the SQLite database, if any.
+struct SummaryOutputs<'a> {
+    summary: &'a str,
+    matches: Option<&'a str>,
+    sqlite: Option<&'a str>,
+}
+
+/// Processes the CSV files and writes the summary, and optionally the per-match records and the SQLite tables, to
+/// files.
+///
+/// The progress is reported on stderr, and Ctrl-C stops the processing and writes a summary of the files read so
+/// far, marked as partial. In watch mode the input directory keeps being watched and the outputs are rewritten after
+/// every update.
+///
+/// # Arguments
+///
+/// * `ingest` - The `IngestArgs` with the input path, the number of threads and the processing options.
+/// * `sample` - The `SampleArgs` with the fraction of the input that is processed.
+/// * `producer_id` - Identifies who or what produced the summary.
+/// * `hash_input` - Whether the SHA-256 of the processed files is written to the metadata block.
+/// * `outputs` - The `SummaryOutputs` with the paths of the files to write.
+/// * `watch_interval` - The time between two scans of the input directory, if watch mode is enabled.
 ///
 /// # Returns
 ///
-/// * `Ok((input_path, num_threads, output_file_name))` - A tuple containing the input path, number of threads, and output file name.
-/// * `Err(String)` - An error message if the arguments are invalid or missing.
-fn parse_args() -> Result<(String, usize, String), String> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 4 {
-        return Err("Usage: cargo run <input-path> <num-threads> <output-file-name>".into());
-    }
+/// * `Ok(())` - If the files were processed, even if the output could not be written.
+/// * `Err(String)` - An error message if the processing options are invalid or the directory cannot be watched.
+fn run_summary(
+    ingest: &IngestArgs,
+    sample: &SampleArgs,
+    producer_id: &str,
+    hash_input: bool,
+    outputs: &SummaryOutputs,
+    watch_interval: Option<Duration>,
+) -> Result<(), String> {
+    let mut config = ingest.to_config().map_err(|err| err.to_string())?;
+    config.track_matches = outputs.matches.is_some();
+    config.sampling = sample.sampling().map_err(|err| err.to_string())?;
+
+    let mut active_filters = ingest.active_filters();
+    active_filters.sampling = config.sampling;
+    let output_metadata = |input, run| {
+        OutputMetadata::new(
+            producer_id,
+            ingest.threads,
+            active_filters.clone(),
+            input,
+            run,
+        )
+    };
 
-    let input_path = args[1].clone();
-    let num_threads: usize = args[2]
-        .parse()
-        .map_err(|_| "Number of threads must be a valid integer.")?;
-    let output_file_name = args[3].clone();
+    match watch_interval {
+        Some(poll_interval) => {
+            let mut input =
+                InputFingerprint::of_files(&ingest.input_path, &[], ingest.threads, hash_input);
+            watch_directory(
+                &ingest.input_path,
+                ingest.threads,
+                &config,
+                poll_interval,
+                |deaths_info, new_files| {
+                    input.add_files(new_files, ingest.threads);
+                    let summary = summarize(deaths_info, &config);
+                    write_summary_files(
+                        deaths_info,
+                        summary,
+                        output_metadata(input.clone(), None),
+                        outputs,
+                    );
+                    ControlFlow::Continue(())
+                },
+            )
+        }
+        None => {
+            let progress = Arc::new(RunProgress::default());
+            let handler_progress = Arc::clone(&progress);
+            if let Err(err) = ctrlc::set_handler(move || {
+                eprintln!("\nInterrupted, writing a partial summary.");
+                handler_progress.cancel();
+            }) {
+                eprintln!("Could not set the Ctrl-C handler: {}", err);
+            }
+
+            let deaths_info = thread::scope(|scope| {
+                scope.spawn(|| progress.report_until_finished(PROGRESS_INTERVAL));
+                let deaths_info =
+                    process_directory(&ingest.input_path, ingest.threads, &config, &progress);
+                progress.finish();
+                deaths_info
+            });
+            let start = Instant::now();
+            let summary = summarize(&deaths_info, &config);
+            progress.add_summarizing(start.elapsed());
 
-    Ok((input_path, num_threads, output_file_name))
+            // A cancelled run lists the files read until then without hashing them, so the partial summary is
+            // written right away.
+            let cancelled = progress.is_cancelled();
+            let mut input = InputFingerprint::of_files(
+                &ingest.input_path,
+                &progress.read_files(),
+                ingest.threads,
+                hash_input && !cancelled,
+            );
+            input.partial = cancelled;
+            write_summary_files(
+                &deaths_info,
+                summary,
+                output_metadata(input, Some(progress.metadata())),
+                outputs,
+            );
+            Ok(())
+        }
+    }
 }
 
-fn main() -> Result<(), String> {
-    let (input_path, num_threads, output_file_name) = parse_args()?;
+/// Writes the summary, and optionally the per-match records and the SQLite tables, to files, printing the outcome.
+///
+/// # Arguments
+///
+/// * `deaths_info` - The aggregated `DeathsInfo` the summary was built from.
+/// * `summary` - The `DeathsInfoSummary` to write.
+/// * `metadata` - The `OutputMetadata` of the run.
+/// * `outputs` - The `SummaryOutputs` with the paths of the files to write.
+fn write_summary_files(
+    deaths_info: &DeathsInfo,
+    summary: DeathsInfoSummary,
+    metadata: OutputMetadata,
+    outputs: &SummaryOutputs,
+) {
+    if let Some(matches_file_name) = outputs.matches {
+        if let Err(err) = create_matches_file(matches_file_name, &summary.matches) {
+            eprintln!("Failed to create matches file: {:?}", err);
+        }
+    }
 
-    let deaths_info = process_data_in_parallel(&input_path, num_threads);
-    match create_json_file(&output_file_name, deaths_info) {
+    if let Some(sqlite_file_name) = outputs.sqlite {
+        if let Err(err) = create_sqlite_file(sqlite_file_name, deaths_info, &metadata) {
+            eprintln!("Failed to create SQLite file: {:?}", err);
+        }
+    }
+
+    match create_json_file(outputs.summary, summary, metadata) {
         Ok(()) => println!("File created successfully."),
         Err(err) => {
             eprintln!("Failed to create file: {:?}", err);
         }
     }
+}
 
+/// Processes the CSV files and returns the aggregated deaths info, for the query subcommands.
+///
+/// # Arguments
+///
+/// * `ingest` - The `IngestArgs` with the input path, the number of threads and the processing options.
+///
+/// # Returns
+///
+/// * `Ok((deaths_info, config))` - The aggregated `DeathsInfo` and the `ProcessingConfig` used to build it.
+/// * `Err(String)` - An error message if the processing options are invalid.
+fn ingest(ingest: &IngestArgs) -> Result<(DeathsInfo, ProcessingConfig), String> {
+    let config = ingest.to_config().map_err(|err| err.to_string())?;
+    let deaths_info = process_directory(
+        &ingest.input_path,
+        ingest.threads,
+        &config,
+        &RunProgress::default(),
+    );
+    Ok((deaths_info, config))
+}
+
+/// Prints a result in the given format.
+///
+/// # Arguments
+///
+/// * `result` - The result to print.
+/// * `format` - The `OutputFormat` to use.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the result was printed.
+/// * `Err(String)` - An error message if the result could not be serialized.
+fn print_result<T: Serialize + Display>(result: &T, format: OutputFormat) -> Result<(), String> {
+    match format {
+        OutputFormat::Text => print!("{}", result),
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(result)
+                .map_err(|err| format!("Failed to serialize result: {}", err))?;
+            println!("{}", json);
+        }
+    }
     Ok(())
 }
+
+fn main() -> Result<(), String> {
+    let cli = Cli::parse_from(rewrite_legacy_args(env::args().collect()));
+
+    match cli.command {
+        Command::Summary {
+            ingest,
+            sample,
+            output,
+            matches_output,
+            sqlite_output,
+            producer_id,
+            no_hash_input,
+            watch,
+            watch_interval,
+        } => run_summary(
+            &ingest,
+            &sample,
+            &producer_id,
+            !no_hash_input,
+            &SummaryOutputs {
+                summary: &output,
+                matches: matches_output.as_deref(),
+                sqlite: sqlite_output.as_deref(),
+            },
+            watch.then_some(Duration::from_secs(watch_interval)),
+        ),
+        Command::Player {
+            name,
+            ingest: ingest_args,
+            format,
+        } => {
+            let (deaths_info, config) = ingest(&ingest_args)?;
+            let breakdown = query_player(&QueryData::new(&deaths_info, &config), &name)
+                .ok_or_else(|| format!("Player {} has no kills.", name))?;
+            print_result(&breakdown, format)
+        }
+        Command::Weapon {
+            name,
+            ingest: ingest_args,
+            format,
+        } => {
+            let (deaths_info, config) = ingest(&ingest_args)?;
+            let details = query_weapon(&QueryData::new(&deaths_info, &config), &name)
+                .ok_or_else(|| format!("Weapon {} caused no deaths.", name))?;
+            print_result(&details, format)
+        }
+        Command::Top {
+            by,
+            limit,
+            filter,
+            ingest: ingest_args,
+            format,
+        } => {
+            let (deaths_info, config) = ingest(&ingest_args)?;
+            let entries = query_top(&QueryData::new(&deaths_info, &config), by, limit, &filter);
+            print_result(&TopRanking(entries), format)
+        }
+        Command::Serve {
+            ingest: ingest_args,
+            address,
+        } => {
+            let (deaths_info, config) = ingest(&ingest_args)?;
+            serve(&deaths_info, &config, &address).map_err(|err| err.to_string())
+        }
+        Command::Trend {
+            ingest: ingest_args,
+            top,
+            format,
+        } => {
+            let config = ingest_args.to_config().map_err(|err| err.to_string())?;
+            let report = analyze_trends(&ingest_args.input_path, ingest_args.threads, &config, top)
+                .map_err(|err| err.to_string())?;
+            print_result(&report, format)
+        }
+        Command::Validate {
+            ingest: ingest_args,
+            sample_rows,
+            format,
+        } => {
+            let config = ingest_args.to_config().map_err(|err| err.to_string())?;
+            let report = validate_directory(
+                &ingest_args.input_path,
+                ingest_args.threads,
+                &config,
+                sample_rows,
+            )
+            .map_err(|err| err.to_string())?;
+            print_result(&report, format)?;
+            match report.files_with_problems() {
+                0 => Ok(()),
+                files => Err(format!(
+                    "Validation found problems in {} of {} files.",
+                    files,
+                    report.files.len()
+                )),
+            }
+        }
+        Command::Cohort {
+            ingest: ingest_args,
+            cohort_a,
+            cohort_b,
+            top,
+            format,
+        } => {
+            let mut config = ingest_args.to_config().map_err(|err| err.to_string())?;
+            config.track_killer_placements =
+                cohort_a.needs_killer_placements() || cohort_b.needs_killer_placements();
+            let deaths_info = process_directory(
+                &ingest_args.input_path,
+                ingest_args.threads,
+                &config,
+                &RunProgress::default(),
+            );
+            let report = compare_cohorts(&deaths_info, &cohort_a, &cohort_b, &config, top)
+                .map_err(|err| err.to_string())?;
+            print_result(&report, format)
+        }
+        Command::Generate(generate) => {
+            let expected = generate
+                .to_dataset()
+                .write_to(Path::new(&generate.output_dir))
+                .map_err(|err| format!("Failed to write the dataset: {}", err))?;
+            println!(
+                "Wrote {} rows ({} malformed) of {} matches to {}.",
+                expected.rows, expected.malformed_rows, expected.matches, generate.output_dir
+            );
+            Ok(())
+        }
+        Command::Diff {
+            old,
+            new,
+            top,
+            format,
+        } => {
+            let report = diff_files(&old, &new, top).map_err(|err| err.to_string())?;
+            print_result(&report, format)
+        }
+    }
+}
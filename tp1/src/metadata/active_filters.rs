@@ -0,0 +1,32 @@
+use crate::config::outlier_policy::OutlierPolicy;
+use crate::config::sampling::Sampling;
+use crate::config::text_encoding::TextEncoding;
+use serde::{Deserialize, Serialize};
+
+/// The processing options that change which rows are counted and how, so a consumer can tell whether two outputs
+/// are comparable.
+///
+/// # Fields
+///
+/// * `outlier_policy` - What was done with rows whose positions are not valid.
+/// * `map_sizes` - The file with the size of each map, if one was given.
+/// * `weapon_mapping` - The file with the canonical name and category of each weapon, if one was given.
+/// * `player_aliases` - The file with the aliases of each player, if one was given.
+/// * `normalize_names` - The normalisation applied to player names.
+/// * `range_bands` - The limits of the close, mid and long range bands.
+/// * `sampling` - The `Sampling` of the input, if only a fraction of it was processed.
+/// * `encoding` - How the lines of the CSV files were decoded.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ActiveFilters {
+    pub outlier_policy: OutlierPolicy,
+    pub map_sizes: Option<String>,
+    pub weapon_mapping: Option<String>,
+    pub player_aliases: Option<String>,
+    pub normalize_names: String,
+    #[serde(default)]
+    pub range_bands: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<Sampling>,
+    #[serde(default)]
+    pub encoding: TextEncoding,
+}
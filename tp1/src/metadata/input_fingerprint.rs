@@ -0,0 +1,151 @@
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use rayon::ThreadPoolBuilder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::PathBuf;
+
+/// Size of the buffer used to read the files while hashing them.
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The size and hash of an input file.
+///
+/// # Fields
+///
+/// * `name` - The name of the file.
+/// * `size` - The size of the file in bytes.
+/// * `sha256` - The SHA-256 of the content of the file, in hexadecimal, or `None` if the input was not hashed or the
+///   file could not be read.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FileFingerprint {
+    pub name: String,
+    pub size: u64,
+    pub sha256: Option<String>,
+}
+
+/// The list of processed files, optionally with their hashes, which identifies the data an output was built from.
+///
+/// # Fields
+///
+/// * `directory` - The input directory.
+/// * `files` - The `FileFingerprint` of each processed file, sorted by name. Files left out of a sample are not
+///   listed.
+/// * `hashed` - Whether the SHA-256 of the files was computed.
+/// * `partial` - Whether the run was cancelled, so only the files read until then are listed and none is hashed.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct InputFingerprint {
+    pub directory: String,
+    pub files: Vec<FileFingerprint>,
+    #[serde(default)]
+    pub hashed: bool,
+    #[serde(default)]
+    pub partial: bool,
+}
+
+impl InputFingerprint {
+    /// Builds the fingerprint of the processed files of a directory, hashing them in parallel if asked to.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir_path` - The input directory.
+    /// * `paths` - The paths of the files that were processed.
+    /// * `num_threads` - The number of threads used to hash the files.
+    /// * `hashed` - Whether the SHA-256 of the files is computed. Hashing reads the whole input again.
+    ///
+    /// # Returns
+    ///
+    /// The `InputFingerprint` of the processed files.
+    pub fn of_files(dir_path: &str, paths: &[PathBuf], num_threads: usize, hashed: bool) -> Self {
+        let mut fingerprint = InputFingerprint {
+            directory: dir_path.to_owned(),
+            files: Vec::new(),
+            hashed,
+            partial: false,
+        };
+        fingerprint.add_files(paths, num_threads);
+        fingerprint
+    }
+
+    /// Adds new files to the fingerprint, hashing them in parallel if the fingerprint is hashed, and keeps the files
+    /// sorted by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The paths of the new files.
+    /// * `num_threads` - The number of threads used to hash the files.
+    pub fn add_files(&mut self, paths: &[PathBuf], num_threads: usize) {
+        let hashed = self.hashed;
+        let fingerprint_files = || {
+            paths
+                .par_iter()
+                .map(|path| fingerprint_file(path, hashed))
+                .collect::<Vec<_>>()
+        };
+        let new_files = match ThreadPoolBuilder::new().num_threads(num_threads).build() {
+            Ok(pool) => pool.install(fingerprint_files),
+            Err(err) => {
+                eprintln!("Error creating thread pool: {}", err);
+                paths
+                    .iter()
+                    .map(|path| fingerprint_file(path, hashed))
+                    .collect()
+            }
+        };
+
+        self.files.extend(new_files);
+        self.files.sort_by(|f1, f2| f1.name.cmp(&f2.name));
+    }
+}
+
+/// Builds the `FileFingerprint` of a file, hashing it if `hashed` is set and printing an error message if it cannot
+/// be read.
+fn fingerprint_file(path: &PathBuf, hashed: bool) -> FileFingerprint {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let size = path.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+
+    if !hashed {
+        return FileFingerprint {
+            name,
+            size,
+            sha256: None,
+        };
+    }
+    let sha256 = match hash_file(path) {
+        Ok(hash) => Some(hash),
+        Err(err) => {
+            eprintln!("Error hashing file {}: {}", path.display(), err);
+            None
+        }
+    };
+
+    FileFingerprint { name, size, sha256 }
+}
+
+/// Computes the SHA-256 of the content of a file.
+///
+/// # Returns
+///
+/// An `io::Result` with the hash in hexadecimal.
+fn hash_file(path: &PathBuf) -> io::Result<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; HASH_BUFFER_SIZE];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
@@ -0,0 +1,3 @@
+pub mod active_filters;
+pub mod input_fingerprint;
+pub mod output_metadata;
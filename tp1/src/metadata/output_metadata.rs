@@ -0,0 +1,72 @@
+use crate::data_summary::run_metadata::RunMetadata;
+use crate::metadata::active_filters::ActiveFilters;
+use crate::metadata::input_fingerprint::InputFingerprint;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Version of the layout of the summary file, described by `output.schema.json`. It changes whenever a field is
+/// removed or changes its meaning.
+pub const OUTPUT_SCHEMA_VERSION: u32 = 1;
+
+/// Producer ID used when none is given.
+pub const DEFAULT_PRODUCER_ID: &str = "tp1";
+
+/// The metadata block written at the start of every summary file.
+///
+/// # Fields
+///
+/// * `schema_version` - The `OUTPUT_SCHEMA_VERSION` of the file.
+/// * `producer_id` - Identifies who or what produced the file.
+/// * `tool_version` - The version of the tool that wrote the file.
+/// * `generated_at` - When the file was written, in RFC 3339 format and UTC.
+/// * `threads` - The number of threads used to process the files.
+/// * `filters` - The `ActiveFilters` of the run.
+/// * `input` - The `InputFingerprint` of the processed files.
+/// * `run` - The `RunMetadata` with the progress and timings of the run, if known.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct OutputMetadata {
+    pub schema_version: u32,
+    pub producer_id: String,
+    pub tool_version: String,
+    pub generated_at: String,
+    pub threads: usize,
+    pub filters: ActiveFilters,
+    pub input: InputFingerprint,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<RunMetadata>,
+}
+
+impl OutputMetadata {
+    /// Builds the metadata of a file written now by this version of the tool.
+    ///
+    /// # Arguments
+    ///
+    /// * `producer_id` - Identifies who or what produced the file.
+    /// * `threads` - The number of threads used to process the files.
+    /// * `filters` - The `ActiveFilters` of the run.
+    /// * `input` - The `InputFingerprint` of the processed files.
+    /// * `run` - The `RunMetadata` of the run, if known.
+    ///
+    /// # Returns
+    ///
+    /// The new `OutputMetadata`.
+    pub fn new(
+        producer_id: &str,
+        threads: usize,
+        filters: ActiveFilters,
+        input: InputFingerprint,
+        run: Option<RunMetadata>,
+    ) -> Self {
+        OutputMetadata {
+            schema_version: OUTPUT_SCHEMA_VERSION,
+            producer_id: producer_id.to_owned(),
+            tool_version: env!("CARGO_PKG_VERSION").to_owned(),
+            generated_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            threads,
+            filters,
+            input,
+            run,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// Errors that may occur while comparing two summaries.
+///
+/// # Variants
+///
+/// * `FileRead` - A summary file could not be read. Holds the path of the file.
+/// * `InvalidFormat` - A summary file is not a valid output of the tool. Holds the path of the file.
+#[derive(Debug)]
+pub enum DiffError {
+    FileRead(String),
+    InvalidFormat(String),
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffError::FileRead(path) => write!(f, "Could not read summary file {}", path),
+            DiffError::InvalidFormat(path) => write!(f, "Invalid summary file {}", path),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use serde::Serialize;
+use std::fmt;
+
+/// An entry that entered or left the top-N between two runs.
+///
+/// # Fields
+///
+/// * `name` - The name of the player or weapon.
+/// * `rank` - Its rank in the run where it is in the top-N, starting at `1`.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct RankedEntry {
+    pub name: String,
+    pub rank: usize,
+}
+
+/// The change of rank of an entry that is in the top-N of both runs.
+///
+/// # Fields
+///
+/// * `name` - The name of the player or weapon.
+/// * `old_rank` - Its rank in the old run.
+/// * `new_rank` - Its rank in the new run.
+/// * `change` - The number of positions it went up, negative if it went down.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct RankChange {
+    pub name: String,
+    pub old_rank: usize,
+    pub new_rank: usize,
+    pub change: i64,
+}
+
+/// The change of a value of an entry between two runs.
+///
+/// # Fields
+///
+/// * `name` - The name of the entry.
+/// * `old` - The value in the old run, if the entry is present.
+/// * `new` - The value in the new run, if the entry is present.
+/// * `change` - `new - old`, rounded to two decimal places, if the entry is present in both runs.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValueChange {
+    pub name: String,
+    pub old: Option<f64>,
+    pub new: Option<f64>,
+    pub change: Option<f64>,
+}
+
+/// The changes of the top-N of a ranking between two runs.
+///
+/// # Fields
+///
+/// * `entered` - The entries that are in the new top-N but not in the old one.
+/// * `left` - The entries that were in the old top-N but not in the new one.
+/// * `rank_changes` - The rank changes of the entries in both top-N, including unchanged ones.
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct RankingDiff {
+    pub entered: Vec<RankedEntry>,
+    pub left: Vec<RankedEntry>,
+    pub rank_changes: Vec<RankChange>,
+}
+
+/// The changes of a player between two runs.
+///
+/// # Fields
+///
+/// * `player` - The name of the player.
+/// * `weapons_percentage` - The percentage-point changes of each weapon in `weapons_percentage`.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PlayerDiff {
+    pub player: String,
+    pub weapons_percentage: Vec<ValueChange>,
+}
+
+/// The report of the differences between two summaries.
+///
+/// # Fields
+///
+/// * `top` - The size of the compared rankings.
+/// * `players` - The changes of the top-N players by kills.
+/// * `weapons` - The changes of the top-N weapons by deaths.
+/// * `player_weapons_percentage` - The percentage-point changes of the weapons of the players in both runs.
+/// * `deaths_percentage` - The percentage-point changes of `deaths_percentage` of each weapon.
+/// * `average_distance` - The changes of `average_distance` of each weapon.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct DiffReport {
+    pub top: usize,
+    pub players: RankingDiff,
+    pub weapons: RankingDiff,
+    pub player_weapons_percentage: Vec<PlayerDiff>,
+    pub deaths_percentage: Vec<ValueChange>,
+    pub average_distance: Vec<ValueChange>,
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Top {} players", self.top)?;
+        write_ranking(f, &self.players)?;
+        writeln!(f)?;
+
+        writeln!(f, "Top {} weapons", self.top)?;
+        write_ranking(f, &self.weapons)?;
+        writeln!(f)?;
+
+        writeln!(f, "Player weapons percentage (pp)")?;
+        for player_diff in &self.player_weapons_percentage {
+            writeln!(f, "  {}", player_diff.player)?;
+            for value_change in &player_diff.weapons_percentage {
+                write_value_change(f, "    ", value_change)?;
+            }
+        }
+        writeln!(f)?;
+
+        writeln!(f, "Weapons deaths percentage (pp)")?;
+        for value_change in &self.deaths_percentage {
+            write_value_change(f, "  ", value_change)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "Weapons average distance")?;
+        for value_change in &self.average_distance {
+            write_value_change(f, "  ", value_change)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the entered, left and moved entries of a ranking.
+fn write_ranking(f: &mut fmt::Formatter<'_>, ranking: &RankingDiff) -> fmt::Result {
+    for entry in &ranking.entered {
+        writeln!(f, "  + {} entered at #{}", entry.name, entry.rank)?;
+    }
+    for entry in &ranking.left {
+        writeln!(f, "  - {} left (was #{})", entry.name, entry.rank)?;
+    }
+    for rank_change in ranking.rank_changes.iter().filter(|r| r.change != 0) {
+        writeln!(
+            f,
+            "  {} #{} -> #{} ({:+})",
+            rank_change.name, rank_change.old_rank, rank_change.new_rank, rank_change.change
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the change of a value, using `-` for a missing value.
+fn write_value_change(
+    f: &mut fmt::Formatter<'_>,
+    indent: &str,
+    value_change: &ValueChange,
+) -> fmt::Result {
+    let format_value = |value: Option<f64>| value.map_or("-".to_owned(), |value| value.to_string());
+    let change = value_change
+        .change
+        .map_or("-".to_owned(), |change| format!("{:+.2}", change));
+    writeln!(
+        f,
+        "{}{}: {} -> {} ({})",
+        indent,
+        value_change.name,
+        format_value(value_change.old),
+        format_value(value_change.new),
+        change
+    )
+}
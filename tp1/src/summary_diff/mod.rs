@@ -0,0 +1,3 @@
+pub mod diff_error;
+pub mod diff_report;
+pub mod summary_differ;
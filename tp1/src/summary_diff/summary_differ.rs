@@ -0,0 +1,216 @@
+use crate::data_summary::deaths_info_summary::DeathsInfoSummary;
+use crate::file_creation::json_format::JsonFormat;
+use crate::summary_diff::diff_error::DiffError;
+use crate::summary_diff::diff_report::{
+    DiffReport, PlayerDiff, RankChange, RankedEntry, RankingDiff, ValueChange,
+};
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+
+/// Loads two summary files and compares them.
+///
+/// # Arguments
+///
+/// * `old_path` - The path to the summary of the old run.
+/// * `new_path` - The path to the summary of the new run.
+/// * `top` - The size of the compared rankings.
+///
+/// # Returns
+///
+/// A `Result` containing the `DiffReport`, or a `DiffError` if a file cannot be read or parsed.
+pub fn diff_files(old_path: &str, new_path: &str, top: usize) -> Result<DiffReport, DiffError> {
+    let old_summary = load_summary(old_path)?;
+    let new_summary = load_summary(new_path)?;
+    Ok(diff_summaries(
+        &old_summary.deaths_info,
+        &new_summary.deaths_info,
+        top,
+    ))
+}
+
+/// Loads a summary file written by the tool.
+///
+/// # Arguments
+///
+/// * `path` - The path to the summary file.
+///
+/// # Returns
+///
+/// A `Result` containing the loaded `JsonFormat`, or a `DiffError` if the file cannot be read or parsed.
+fn load_summary(path: &str) -> Result<JsonFormat, DiffError> {
+    let content = fs::read_to_string(path).map_err(|_| DiffError::FileRead(path.to_owned()))?;
+    serde_json::from_str(&content).map_err(|_| DiffError::InvalidFormat(path.to_owned()))
+}
+
+/// Compares the summaries of two runs.
+///
+/// # Arguments
+///
+/// * `old` - The `DeathsInfoSummary` of the old run.
+/// * `new` - The `DeathsInfoSummary` of the new run.
+/// * `top` - The size of the compared rankings.
+///
+/// # Returns
+///
+/// A `DiffReport` with the ranking changes of players and weapons and the changes of their values.
+pub fn diff_summaries(old: &DeathsInfoSummary, new: &DeathsInfoSummary, top: usize) -> DiffReport {
+    let players = diff_rankings(
+        rank_by(&old.top_killers, |stats| stats.deaths as f64),
+        rank_by(&new.top_killers, |stats| stats.deaths as f64),
+        top,
+    );
+    let weapons = diff_rankings(
+        rank_by(&old.top_weapons, |stats| stats.deaths_percentage),
+        rank_by(&new.top_weapons, |stats| stats.deaths_percentage),
+        top,
+    );
+
+    let player_weapons_percentage = old
+        .top_killers
+        .iter()
+        .filter_map(|(player, old_stats)| {
+            let new_stats = new.top_killers.get(player)?;
+            Some(PlayerDiff {
+                player: player.clone(),
+                weapons_percentage: diff_values(
+                    &old_stats.weapons_percentage,
+                    &new_stats.weapons_percentage,
+                    |percentage| *percentage,
+                ),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let deaths_percentage = diff_values(&old.top_weapons, &new.top_weapons, |stats| {
+        stats.deaths_percentage
+    });
+    let average_distance = diff_values(&old.top_weapons, &new.top_weapons, |stats| {
+        stats.average_distance
+    });
+
+    let mut report = DiffReport {
+        top,
+        players,
+        weapons,
+        player_weapons_percentage,
+        deaths_percentage,
+        average_distance,
+    };
+    report
+        .player_weapons_percentage
+        .sort_by(|p1, p2| p1.player.cmp(&p2.player));
+    report
+}
+
+/// Ranks the entries of a map by a value in descending order, breaking ties by name.
+///
+/// # Arguments
+///
+/// * `entries` - A `HashMap` where keys are names and values are their stats.
+/// * `value_of` - A function that returns the value used to rank an entry.
+///
+/// # Returns
+///
+/// A `Vec` with the names of the entries, sorted by rank.
+fn rank_by<T>(entries: &HashMap<String, T>, value_of: impl Fn(&T) -> f64) -> Vec<String> {
+    let mut ranked: Vec<_> = entries
+        .iter()
+        .map(|(name, stats)| (name, value_of(stats)))
+        .collect();
+    ranked.sort_by(|e1, e2| e2.1.total_cmp(&e1.1).then_with(|| e1.0.cmp(e2.0)));
+    ranked.into_iter().map(|(name, _)| name.clone()).collect()
+}
+
+/// Compares the top-N of two rankings.
+///
+/// # Arguments
+///
+/// * `old_ranking` - The names sorted by rank in the old run.
+/// * `new_ranking` - The names sorted by rank in the new run.
+/// * `top` - The size of the compared rankings.
+///
+/// # Returns
+///
+/// A `RankingDiff` with the entries that entered or left the top-N and the rank changes of the others.
+fn diff_rankings(old_ranking: Vec<String>, new_ranking: Vec<String>, top: usize) -> RankingDiff {
+    let old_ranks: HashMap<_, _> = old_ranking
+        .into_iter()
+        .take(top)
+        .enumerate()
+        .map(|(index, name)| (name, index + 1))
+        .collect();
+    let new_ranks: HashMap<_, _> = new_ranking
+        .into_iter()
+        .take(top)
+        .enumerate()
+        .map(|(index, name)| (name, index + 1))
+        .collect();
+
+    let mut ranking_diff = RankingDiff::default();
+    for (name, &new_rank) in &new_ranks {
+        match old_ranks.get(name) {
+            Some(&old_rank) => ranking_diff.rank_changes.push(RankChange {
+                name: name.clone(),
+                old_rank,
+                new_rank,
+                change: old_rank as i64 - new_rank as i64,
+            }),
+            None => ranking_diff.entered.push(RankedEntry {
+                name: name.clone(),
+                rank: new_rank,
+            }),
+        }
+    }
+    for (name, &old_rank) in &old_ranks {
+        if !new_ranks.contains_key(name) {
+            ranking_diff.left.push(RankedEntry {
+                name: name.clone(),
+                rank: old_rank,
+            });
+        }
+    }
+
+    ranking_diff.entered.sort_by_key(|entry| entry.rank);
+    ranking_diff.left.sort_by_key(|entry| entry.rank);
+    ranking_diff
+        .rank_changes
+        .sort_by_key(|change| change.new_rank);
+    ranking_diff
+}
+
+/// Compares a value of the entries present in either of two maps.
+///
+/// # Arguments
+///
+/// * `old` - A `HashMap` with the entries of the old run.
+/// * `new` - A `HashMap` with the entries of the new run.
+/// * `value_of` - A function that returns the compared value of an entry.
+///
+/// # Returns
+///
+/// A `Vec` of `ValueChange`, sorted by name.
+fn diff_values<T>(
+    old: &HashMap<String, T>,
+    new: &HashMap<String, T>,
+    value_of: impl Fn(&T) -> f64,
+) -> Vec<ValueChange> {
+    let names: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let old_value = old.get(name).map(&value_of);
+            let new_value = new.get(name).map(&value_of);
+            let change = old_value
+                .zip(new_value)
+                .map(|(old_value, new_value)| ((new_value - old_value) * 100.0).round() / 100.0);
+            ValueChange {
+                name: name.clone(),
+                old: old_value,
+                new: new_value,
+                change,
+            }
+        })
+        .collect()
+}
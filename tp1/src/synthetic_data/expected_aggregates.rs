@@ -0,0 +1,50 @@
+use crate::synthetic_data::expected_weapon_stats::ExpectedWeaponStats;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The aggregates that processing a synthetic dataset must produce, tallied while the dataset is generated.
+///
+/// # Fields
+///
+/// * `rows` - The number of data rows written, including the malformed ones.
+/// * `malformed_rows` - The number of malformed rows. Half of them are truncated and must be skipped, the other
+///   half have an unreadable killer position and must be counted without a distance.
+/// * `matches` - The number of different matches.
+/// * `player_kills` - The number of kills of each killer, by player name.
+/// * `weapons` - The `ExpectedWeaponStats` of each weapon, by weapon name.
+/// * `environment_deaths` - The number of deaths caused by the environment.
+/// * `suicides` - The number of deaths where the killer is the victim.
+/// * `team_kills` - The number of deaths where the killer and the victim share their final placement.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ExpectedAggregates {
+    pub rows: u64,
+    pub malformed_rows: u64,
+    pub matches: usize,
+    pub player_kills: BTreeMap<String, u32>,
+    pub weapons: BTreeMap<String, ExpectedWeaponStats>,
+    pub environment_deaths: u32,
+    pub suicides: u32,
+    pub team_kills: u32,
+}
+
+impl ExpectedAggregates {
+    /// Returns the total number of deaths that are counted, that is every row except the truncated ones.
+    pub fn total_deaths(&self) -> u32 {
+        self.weapons.values().map(|stats| stats.deaths).sum()
+    }
+
+    /// Returns the kill counts of the players with the most kills, in descending order.
+    ///
+    /// Only the counts are returned, as players with the same number of kills may be ranked in any order.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The number of counts returned.
+    pub fn top_kill_counts(&self, limit: usize) -> Vec<u32> {
+        let mut kills: Vec<u32> = self.player_kills.values().copied().collect();
+        kills.sort_unstable_by(|a, b| b.cmp(a));
+        kills.truncate(limit);
+        kills
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// The stats a weapon must have after processing a synthetic dataset.
+///
+/// # Fields
+///
+/// * `deaths` - The number of deaths caused by the weapon, including the deaths that are not player kills.
+/// * `valid_distances_count` - The number of those deaths whose positions are valid.
+/// * `total_distance` - The sum of the distances of those deaths. It may differ from the processed total in the
+///   last digits, as the distances are added in a different order.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ExpectedWeaponStats {
+    pub deaths: u32,
+    pub valid_distances_count: u32,
+    pub total_distance: f64,
+}
+
+impl ExpectedWeaponStats {
+    /// Returns the average distance of the deaths with valid positions, or `0.0` if there are none.
+    pub fn average_distance(&self) -> f64 {
+        if self.valid_distances_count == 0 {
+            return 0.0;
+        }
+        self.total_distance / self.valid_distances_count as f64
+    }
+}
@@ -0,0 +1,3 @@
+pub mod expected_aggregates;
+pub mod expected_weapon_stats;
+pub mod synthetic_dataset;
@@ -0,0 +1,344 @@
+use crate::synthetic_data::expected_aggregates::ExpectedAggregates;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Header of the deaths CSV files.
+pub const CSV_HEADER: &str = "killed_by,killer_name,killer_placement,killer_position_x,killer_position_y,map,match_id,time,victim_name,victim_placement,victim_position_x,victim_position_y";
+
+/// Name of the file with the `ExpectedAggregates` of the dataset, written next to the CSV files.
+pub const EXPECTED_AGGREGATES_FILE: &str = "expected_aggregates.json";
+
+/// Weapons used by the generated kills, from the most to the least used when the distribution is skewed.
+const WEAPONS: [&str; 16] = [
+    "M416",
+    "AKM",
+    "SCAR-L",
+    "M16A4",
+    "UMP9",
+    "Kar98k",
+    "Mini 14",
+    "S1897",
+    "Micro UZI",
+    "SKS",
+    "Vector",
+    "Tommy Gun",
+    "Crossbow",
+    "Grenade",
+    "Punch",
+    "Down and Out",
+];
+
+/// Maps of the generated matches, with their size as in the default map bounds.
+const MAPS: [(&str, f64); 4] = [
+    ("ERANGEL", 800000.0),
+    ("MIRAMAR", 800000.0),
+    ("VIKENDI", 600000.0),
+    ("SANHOK", 400000.0),
+];
+
+/// Values of the `killed_by` column of the generated environment deaths.
+const ENVIRONMENT_CAUSES: [&str; 4] = ["Bluezone", "Falling", "Drown", "Buggy"];
+
+/// Number of teams of each generated match, which is also the worst final placement.
+const TEAMS_PER_MATCH: u32 = 100;
+
+/// A synthetic dataset of deaths CSV files, generated from a seed so that every run writes the same files.
+///
+/// Every row is generated as a known kind of death, so the aggregates the processing must produce are tallied
+/// while the files are written.
+///
+/// # Fields
+///
+/// * `seed` - The seed of the random number generator, a `ChaCha8Rng`, whose output is the same on every
+///   platform.
+/// * `players` - Number of different player names.
+/// * `weapons` - Number of different weapons, up to 16.
+/// * `maps` - Number of different maps, up to 4.
+/// * `deaths_per_match` - Number of rows of each match. Every file has its own matches.
+/// * `file_rows` - Number of rows of each file.
+/// * `malformed_rate` - Probability of a row being malformed, either truncated or with an unreadable position.
+/// * `special_death_rate` - Probability of a row being an environment death, a suicide or a team kill.
+/// * `skew` - Exponent of the Zipf distribution used to pick the killers and the weapons. `0.0` picks them
+///   uniformly, and greater values concentrate the kills on fewer players and weapons.
+#[derive(Debug, Clone)]
+pub struct SyntheticDataset {
+    pub seed: u64,
+    pub players: usize,
+    pub weapons: usize,
+    pub maps: usize,
+    pub deaths_per_match: usize,
+    pub file_rows: Vec<usize>,
+    pub malformed_rate: f64,
+    pub special_death_rate: f64,
+    pub skew: f64,
+}
+
+impl Default for SyntheticDataset {
+    fn default() -> Self {
+        SyntheticDataset {
+            seed: 0,
+            players: 1000,
+            weapons: WEAPONS.len(),
+            maps: MAPS.len(),
+            deaths_per_match: 90,
+            file_rows: vec![10_000],
+            malformed_rate: 0.0,
+            special_death_rate: 0.0,
+            skew: 0.0,
+        }
+    }
+}
+
+/// The values of a generated row. The killer and the victim are given as their name, final placement and position.
+struct Row {
+    killed_by: &'static str,
+    killer: Option<(String, u32, (f64, f64))>,
+    victim: (String, u32, (f64, f64)),
+}
+
+impl SyntheticDataset {
+    /// Writes the files of the dataset to a directory, creating it if needed.
+    ///
+    /// The files are named `deaths_<index>.csv`, and the `ExpectedAggregates` are written next to them to
+    /// `EXPECTED_AGGREGATES_FILE`, which is not read as input.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir_path` - The directory where the files are written.
+    ///
+    /// # Returns
+    ///
+    /// An `io::Result` with the `ExpectedAggregates` of the dataset.
+    pub fn write_to(&self, dir_path: &Path) -> io::Result<ExpectedAggregates> {
+        create_dir_all(dir_path)?;
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let killer_weights = zipf_weights(self.players.max(1), self.skew);
+        let weapon_weights = zipf_weights(self.weapons.clamp(1, WEAPONS.len()), self.skew);
+        let mut expected = ExpectedAggregates::default();
+        let mut matches = HashSet::new();
+
+        for (index, rows) in self.file_rows.iter().enumerate() {
+            let file = File::create(dir_path.join(format!("deaths_{}.csv", index)))?;
+            let mut writer = BufWriter::new(file);
+            writeln!(writer, "{}", CSV_HEADER)?;
+            for row in 0..*rows {
+                let match_number = row / self.deaths_per_match.max(1);
+                let match_id = format!("{}-{}", index, match_number);
+                let map = MAPS[match_number % self.maps.clamp(1, MAPS.len())];
+                let generated = self.random_row(&mut rng, &killer_weights, &weapon_weights, map.1);
+
+                expected.rows += 1;
+                if rng.random_bool(self.malformed_rate.clamp(0.0, 1.0)) {
+                    expected.malformed_rows += 1;
+                    if expected.malformed_rows % 2 == 1 {
+                        writeln!(writer, "{}", generated.killed_by)?;
+                        continue;
+                    }
+                    write_row(&mut writer, &generated, map.0, &match_id, row, false)?;
+                    tally(&mut expected, &generated, false);
+                } else {
+                    write_row(&mut writer, &generated, map.0, &match_id, row, true)?;
+                    tally(&mut expected, &generated, true);
+                }
+                matches.insert(match_id);
+            }
+            writer.flush()?;
+        }
+
+        expected.matches = matches.len();
+        let file = File::create(dir_path.join(EXPECTED_AGGREGATES_FILE))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &expected)?;
+        Ok(expected)
+    }
+
+    /// Generates a random death: usually a player kill, and with `special_death_rate` an environment death, a
+    /// suicide or a team kill.
+    fn random_row(
+        &self,
+        rng: &mut ChaCha8Rng,
+        killer_weights: &Option<Vec<f64>>,
+        weapon_weights: &Option<Vec<f64>>,
+        map_size: f64,
+    ) -> Row {
+        let players = self.players.max(1);
+        let killer = pick(rng, players, killer_weights);
+        let victim = (killer + rng.random_range(1..players.max(2))) % players;
+        let killer_placement = rng.random_range(1..=TEAMS_PER_MATCH);
+        let victim_placement =
+            (killer_placement + rng.random_range(1..TEAMS_PER_MATCH) - 1) % TEAMS_PER_MATCH + 1;
+        let weapon = WEAPONS[pick(rng, self.weapons.clamp(1, WEAPONS.len()), weapon_weights)];
+        let killer_position = random_position(rng, map_size);
+        let victim_position = random_position(rng, map_size);
+
+        let special_death = rng
+            .random_bool(self.special_death_rate.clamp(0.0, 1.0))
+            .then(|| rng.random_range(0..3));
+        match special_death {
+            Some(0) => Row {
+                killed_by: ENVIRONMENT_CAUSES[rng.random_range(0..ENVIRONMENT_CAUSES.len())],
+                killer: None,
+                victim: (player_name(victim), victim_placement, victim_position),
+            },
+            Some(1) => Row {
+                killed_by: weapon,
+                killer: Some((player_name(killer), killer_placement, killer_position)),
+                victim: (player_name(killer), killer_placement, killer_position),
+            },
+            Some(_) => Row {
+                killed_by: weapon,
+                killer: Some((player_name(killer), killer_placement, killer_position)),
+                victim: (player_name(victim), killer_placement, victim_position),
+            },
+            None => Row {
+                killed_by: weapon,
+                killer: Some((player_name(killer), killer_placement, killer_position)),
+                victim: (player_name(victim), victim_placement, victim_position),
+            },
+        }
+    }
+}
+
+/// Writes a row in the format of the deaths CSV files.
+///
+/// # Arguments
+///
+/// * `writer` - Where the row is written.
+/// * `row` - The `Row` to write.
+/// * `map` - The map of the match.
+/// * `match_id` - The identifier of the match.
+/// * `time` - The value of the `time` column.
+/// * `readable_killer_position` - Whether the killer position is written, or replaced by unreadable text.
+fn write_row(
+    writer: &mut impl Write,
+    row: &Row,
+    map: &str,
+    match_id: &str,
+    time: usize,
+    readable_killer_position: bool,
+) -> io::Result<()> {
+    let (killer_name, killer_placement, killer_x, killer_y) = match &row.killer {
+        Some((name, _, _)) if !readable_killer_position => (
+            name.as_str(),
+            String::new(),
+            "n/a".to_owned(),
+            "n/a".to_owned(),
+        ),
+        Some((name, placement, (x, y))) => (
+            name.as_str(),
+            format!("{}.0", placement),
+            format!("{:.1}", x),
+            format!("{:.1}", y),
+        ),
+        None => ("", String::new(), String::new(), String::new()),
+    };
+    let (victim_name, victim_placement, (victim_x, victim_y)) = &row.victim;
+    writeln!(
+        writer,
+        "{},{},{},{},{},{},{},{},{},{}.0,{:.1},{:.1}",
+        row.killed_by,
+        killer_name,
+        killer_placement,
+        killer_x,
+        killer_y,
+        map,
+        match_id,
+        time,
+        victim_name,
+        victim_placement,
+        victim_x,
+        victim_y,
+    )
+}
+
+/// Adds a written row to the expected aggregates, classifying it the same way the processing does.
+///
+/// # Arguments
+///
+/// * `expected` - The `ExpectedAggregates` being tallied.
+/// * `row` - The `Row` that was written.
+/// * `readable_killer_position` - Whether the killer position was written, so the row has a distance.
+fn tally(expected: &mut ExpectedAggregates, row: &Row, readable_killer_position: bool) {
+    let weapon = expected
+        .weapons
+        .entry(row.killed_by.to_owned())
+        .or_default();
+    weapon.deaths += 1;
+
+    let Some((killer_name, killer_placement, killer_position)) = &row.killer else {
+        expected.environment_deaths += 1;
+        return;
+    };
+    let (victim_name, victim_placement, victim_position) = &row.victim;
+
+    if readable_killer_position {
+        weapon.valid_distances_count += 1;
+        weapon.total_distance += ((killer_position.0 - victim_position.0).powi(2)
+            + (killer_position.1 - victim_position.1).powi(2))
+        .sqrt();
+    }
+
+    if killer_name == victim_name {
+        expected.suicides += 1;
+    } else if readable_killer_position && killer_placement == victim_placement {
+        expected.team_kills += 1;
+    } else {
+        *expected
+            .player_kills
+            .entry(killer_name.clone())
+            .or_default() += 1;
+    }
+}
+
+/// Returns the name of the player with the given index.
+fn player_name(index: usize) -> String {
+    format!("player{}", index)
+}
+
+/// Returns a random position inside a map, rounded to the single decimal written to the files so that the
+/// expected distances are computed from the same values the processing reads.
+fn random_position(rng: &mut ChaCha8Rng, map_size: f64) -> (f64, f64) {
+    let tenths = (map_size * 10.0) as u64;
+    (
+        rng.random_range(1..tenths) as f64 / 10.0,
+        rng.random_range(1..tenths) as f64 / 10.0,
+    )
+}
+
+/// Computes the cumulative weights of a Zipf distribution over `count` elements, or `None` if the distribution
+/// is uniform.
+///
+/// # Arguments
+///
+/// * `count` - The number of elements.
+/// * `skew` - The exponent of the distribution.
+fn zipf_weights(count: usize, skew: f64) -> Option<Vec<f64>> {
+    if skew <= 0.0 {
+        return None;
+    }
+    let mut total = 0.0;
+    let weights = (1..=count)
+        .map(|rank| {
+            total += 1.0 / (rank as f64).powf(skew);
+            total
+        })
+        .collect();
+    Some(weights)
+}
+
+/// Picks a random index lower than `count`, following the cumulative weights if there are any.
+fn pick(rng: &mut ChaCha8Rng, count: usize, weights: &Option<Vec<f64>>) -> usize {
+    match weights {
+        Some(weights) => {
+            let target = rng.random_range(0.0..weights[count - 1]);
+            weights
+                .partition_point(|&weight| weight <= target)
+                .min(count - 1)
+        }
+        None => rng.random_range(0..count),
+    }
+}
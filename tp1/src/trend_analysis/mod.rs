@@ -0,0 +1,3 @@
+pub mod trend_analyzer;
+pub mod trend_error;
+pub mod trend_report;
@@ -0,0 +1,228 @@
+use crate::config::processing_config::ProcessingConfig;
+use crate::data_processing::data_processor::{collect_input_files, process_directory};
+use crate::data_processing::run_progress::RunProgress;
+use crate::data_summary::data_summarizer::summarize;
+use crate::trend_analysis::trend_error::TrendError;
+use crate::trend_analysis::trend_report::{SeriesTrend, Trend, TrendReport};
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::read_dir;
+
+/// Change per period, relative to the mean of a series, above which the series is rising or falling.
+const TREND_THRESHOLD: f64 = 0.05;
+
+/// The stats of a period needed by the trend report.
+///
+/// # Fields
+///
+/// * `deaths_percentage` - The `deaths_percentage` of the top weapons of the summary of the period.
+/// * `top_players` - The names of the top players by kills.
+/// * `player_kills` - The kills of the top killers of the summary of the period.
+struct PeriodStats {
+    deaths_percentage: HashMap<String, f64>,
+    top_players: Vec<String>,
+    player_kills: HashMap<String, u32>,
+}
+
+/// Processes each dated partition of a directory and reports how the weapons and the top players evolve.
+///
+/// Every subdirectory with input files is a period, such as `2024-05-01/`, and periods are sorted by name, so
+/// ISO dates are in chronological order. Each period is processed and summarized on its own with the same options,
+/// so the values are the ones of the summary of the period, scaled if the input is sampled.
+///
+/// # Arguments
+///
+/// * `dir_path` - The directory containing one subdirectory per period.
+/// * `num_threads` - The number of threads used to process each period.
+/// * `config` - The `ProcessingConfig` used to process the periods.
+/// * `top` - The number of top players by kills taken from each period, up to the top killers of the summary.
+///
+/// # Returns
+///
+/// A `Result` containing the `TrendReport`, or a `TrendError` if the directory cannot be read or has no periods.
+pub fn analyze_trends(
+    dir_path: &str,
+    num_threads: usize,
+    config: &ProcessingConfig,
+    top: usize,
+) -> Result<TrendReport, TrendError> {
+    let periods = list_periods(dir_path)?;
+
+    let period_stats: Vec<PeriodStats> = periods
+        .values()
+        .map(|period_path| period_stats_of(period_path, num_threads, config, top))
+        .collect();
+
+    let weapons: BTreeSet<&String> = period_stats
+        .iter()
+        .flat_map(|stats| stats.deaths_percentage.keys())
+        .collect();
+    let weapons_deaths_percentage = weapons
+        .into_iter()
+        .map(|weapon| {
+            let values = period_stats
+                .iter()
+                .map(|stats| stats.deaths_percentage.get(weapon).copied().unwrap_or(0.0))
+                .collect();
+            series(weapon, values)
+        })
+        .collect();
+
+    let players: BTreeSet<&String> = period_stats
+        .iter()
+        .flat_map(|stats| &stats.top_players)
+        .collect();
+    let mut players_kills: Vec<SeriesTrend> = players
+        .into_iter()
+        .map(|player| {
+            let values = period_stats
+                .iter()
+                .map(|stats| stats.player_kills.get(player).copied().unwrap_or(0) as f64)
+                .collect();
+            series(player, values)
+        })
+        .collect();
+    players_kills.sort_by(|p1, p2| {
+        let total = |series: &SeriesTrend| series.values.iter().sum::<f64>();
+        total(p2)
+            .total_cmp(&total(p1))
+            .then_with(|| p1.name.cmp(&p2.name))
+    });
+
+    Ok(TrendReport {
+        periods: periods.into_keys().collect(),
+        top,
+        weapons_deaths_percentage,
+        players_kills,
+    })
+}
+
+/// Lists the subdirectories of a directory that contain input files.
+///
+/// # Arguments
+///
+/// * `dir_path` - The directory containing one subdirectory per period.
+///
+/// # Returns
+///
+/// A `Result` containing a `BTreeMap` where keys are the period names and values are their paths, or a
+/// `TrendError` if the directory cannot be read or has no periods.
+fn list_periods(dir_path: &str) -> Result<BTreeMap<String, String>, TrendError> {
+    let entries = read_dir(dir_path).map_err(|_| TrendError::DirectoryRead(dir_path.to_owned()))?;
+
+    let periods: BTreeMap<String, String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_owned();
+            let path = path.to_str()?.to_owned();
+            Some((name, path))
+        })
+        .filter(|(_, path)| !collect_input_files(path).is_empty())
+        .collect();
+
+    if periods.is_empty() {
+        return Err(TrendError::NoPeriods(dir_path.to_owned()));
+    }
+    Ok(periods)
+}
+
+/// Processes the input files of a period.
+///
+/// # Arguments
+///
+/// * `period_path` - The directory of the period.
+/// * `num_threads` - The number of threads used to process the files.
+/// * `config` - The `ProcessingConfig` used to process the files.
+/// * `top` - The number of top players by kills kept, up to the top killers of the summary.
+///
+/// # Returns
+///
+/// The `PeriodStats` of the period.
+fn period_stats_of(
+    period_path: &str,
+    num_threads: usize,
+    config: &ProcessingConfig,
+    top: usize,
+) -> PeriodStats {
+    let deaths_info = process_directory(period_path, num_threads, config, &RunProgress::default());
+    let summary = summarize(&deaths_info, config);
+
+    let deaths_percentage = summary
+        .top_weapons
+        .into_iter()
+        .map(|(weapon, stats)| (weapon, stats.deaths_percentage))
+        .collect();
+
+    let mut killers: Vec<(String, u32)> = summary
+        .top_killers
+        .into_iter()
+        .map(|(player, stats)| (player, stats.deaths))
+        .collect();
+    killers.sort_by(|(name1, kills1), (name2, kills2)| {
+        kills2.cmp(kills1).then_with(|| name1.cmp(name2))
+    });
+    let top_players = killers
+        .iter()
+        .take(top)
+        .map(|(player, _)| player.clone())
+        .collect();
+
+    PeriodStats {
+        deaths_percentage,
+        top_players,
+        player_kills: killers.into_iter().collect(),
+    }
+}
+
+/// Builds the series of a weapon or player with its trend.
+fn series(name: &str, values: Vec<f64>) -> SeriesTrend {
+    SeriesTrend {
+        name: name.to_owned(),
+        trend: trend_of(&values),
+        values,
+    }
+}
+
+/// Finds the trend of a series of values with the slope of their least-squares line.
+///
+/// The slope is divided by the mean of the values, so the threshold is the same for small and large values: a
+/// series is rising or falling when it changes by more than `TREND_THRESHOLD` of its mean per period.
+///
+/// # Arguments
+///
+/// * `values` - The values of the series, one per period.
+///
+/// # Returns
+///
+/// The `Trend` of the series, `Stable` if it has fewer than two values or its mean is `0`.
+pub fn trend_of(values: &[f64]) -> Trend {
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    if values.len() < 2 || mean == 0.0 {
+        return Trend::Stable;
+    }
+
+    let mean_period = (count - 1.0) / 2.0;
+    let (covariance, variance) =
+        values
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(covariance, variance), (period, value)| {
+                let offset = period as f64 - mean_period;
+                (
+                    covariance + offset * (value - mean),
+                    variance + offset * offset,
+                )
+            });
+
+    let relative_slope = covariance / variance / mean;
+    if relative_slope > TREND_THRESHOLD {
+        Trend::Rising
+    } else if relative_slope < -TREND_THRESHOLD {
+        Trend::Falling
+    } else {
+        Trend::Stable
+    }
+}
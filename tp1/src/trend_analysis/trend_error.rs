@@ -0,0 +1,28 @@
+use std::fmt;
+
+/// Errors that may occur while analysing the trends of dated partitions.
+///
+/// # Variants
+///
+/// * `DirectoryRead` - The input directory could not be read. Holds the path of the directory.
+/// * `NoPeriods` - The input directory has no subdirectory with input files. Holds the path of the directory.
+#[derive(Debug)]
+pub enum TrendError {
+    DirectoryRead(String),
+    NoPeriods(String),
+}
+
+impl fmt::Display for TrendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrendError::DirectoryRead(path) => write!(f, "Could not read directory {}", path),
+            TrendError::NoPeriods(path) => {
+                write!(
+                    f,
+                    "Directory {} has no subdirectories with input files",
+                    path
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use serde::Serialize;
+use std::fmt;
+
+/// The direction of a series of values over the periods.
+///
+/// # Variants
+///
+/// * `Rising` - The values grow by more than the trend threshold per period.
+/// * `Falling` - The values shrink by more than the trend threshold per period.
+/// * `Stable` - The values change less than the threshold, or there are fewer than two periods.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Trend {
+    Rising,
+    Falling,
+    Stable,
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Trend::Rising => "rising",
+            Trend::Falling => "falling",
+            Trend::Stable => "stable",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The values of a weapon or player in every period.
+///
+/// # Fields
+///
+/// * `name` - The name of the weapon or player.
+/// * `values` - The value in each period, in the order of the periods of the report. Periods where the weapon or
+///   player is not in the summary have a value of `0`.
+/// * `trend` - The `Trend` of the values.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct SeriesTrend {
+    pub name: String,
+    pub values: Vec<f64>,
+    pub trend: Trend,
+}
+
+/// The report of how the weapons and the top players evolve over dated partitions of the input.
+///
+/// # Fields
+///
+/// * `periods` - The names of the periods, sorted.
+/// * `top` - The number of top players taken from each period.
+/// * `weapons_deaths_percentage` - The `deaths_percentage` in each period of the weapons that are in the summary of
+///   any period, sorted by name.
+/// * `players_kills` - The kills in each period of the players that are in the top of any period, sorted by total
+///   kills.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct TrendReport {
+    pub periods: Vec<String>,
+    pub top: usize,
+    pub weapons_deaths_percentage: Vec<SeriesTrend>,
+    pub players_kills: Vec<SeriesTrend>,
+}
+
+impl fmt::Display for TrendReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Periods: {}", self.periods.join(", "))?;
+        writeln!(f)?;
+
+        writeln!(f, "Weapons deaths percentage (%)")?;
+        for series in &self.weapons_deaths_percentage {
+            write_series(f, series)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "Kills of the top {} players of each period", self.top)?;
+        for series in &self.players_kills {
+            write_series(f, series)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the values of a series separated by arrows, followed by its trend.
+fn write_series(f: &mut fmt::Formatter<'_>, series: &SeriesTrend) -> fmt::Result {
+    let values: Vec<String> = series
+        .values
+        .iter()
+        .map(|value| value.to_string())
+        .collect();
+    writeln!(
+        f,
+        "  {}: {} ({})",
+        series.name,
+        values.join(" -> "),
+        series.trend
+    )
+}
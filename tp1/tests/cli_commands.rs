@@ -0,0 +1,227 @@
+mod common;
+
+use clap::{CommandFactory, Parser};
+use common::{test_dir, write_rows};
+use std::fs::remove_dir_all;
+use tp1::cli::cli_args::{rewrite_legacy_args, Cli, Command};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_directory;
+use tp1::data_processing::deaths_info::DeathsInfo;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_query::data_querier::{query_player, query_top, query_weapon};
+use tp1::data_query::query_data::QueryData;
+use tp1::data_query::top_metric::TopMetric;
+
+/// Converts a list of arguments to the `Vec<String>` received by `rewrite_legacy_args`.
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Parses the arguments of a query subcommand over `dir` and processes the directory like the binary does.
+fn ingest(command_args: &[&str], dir: &str) -> (Command, DeathsInfo, ProcessingConfig) {
+    let mut cli_args = vec!["tp1"];
+    cli_args.extend_from_slice(command_args);
+    cli_args.push(dir);
+    let command = Cli::parse_from(cli_args).command;
+    let ingest = match &command {
+        Command::Player { ingest, .. }
+        | Command::Weapon { ingest, .. }
+        | Command::Top { ingest, .. } => ingest,
+        _ => panic!("Expected a query subcommand"),
+    };
+    let config = ingest.to_config().unwrap();
+    let deaths_info = process_directory(dir, ingest.threads, &config, &RunProgress::default());
+    (command, deaths_info, config)
+}
+
+/// Writes a dataset where `alice` has 3 kills, `bob` 2 and `carl` 1, with a blue zone death, and returns its
+/// directory.
+fn write_dataset(name: &str) -> String {
+    let dir = test_dir(name);
+    let row = |weapon: &str, killer: &str, distance: f64| {
+        format!(
+            "{},{},1,100.0,100.0,ERANGEL,m1,10,victim,2,{},100.0\n",
+            weapon,
+            killer,
+            100.0 + distance
+        )
+    };
+    let rows = [
+        row("M416", "alice", 100.0),
+        row("M416", "alice", 100.0),
+        row("AKM", "alice", 400.0),
+        row("AKM", "bob", 400.0),
+        row("Pan", "bob", 1.0),
+        row("M416", "carl", 100.0),
+        row("Bluezone", "", 50.0),
+    ];
+    write_rows(&dir.join("deaths.csv"), &rows);
+    dir.to_str().unwrap().to_owned()
+}
+
+#[test]
+fn legacy_invocation_becomes_a_summary() {
+    let rewritten = rewrite_legacy_args(args(&[
+        "tp1",
+        "data",
+        "4",
+        "out.json",
+        "--strategy",
+        "sharded",
+    ]));
+    assert_eq!(
+        rewritten,
+        args(&[
+            "tp1",
+            "summary",
+            "data",
+            "--threads",
+            "4",
+            "--output",
+            "out.json",
+            "--strategy",
+            "sharded"
+        ])
+    );
+
+    let Command::Summary { ingest, output, .. } = Cli::parse_from(rewritten).command else {
+        panic!("Expected the summary subcommand");
+    };
+    assert_eq!(ingest.input_path, "data");
+    assert_eq!(ingest.threads, 4);
+    assert_eq!(output, "out.json");
+}
+
+#[test]
+fn other_invocations_are_left_untouched() {
+    let invocations = [
+        args(&["tp1", "summary", "data", "-o", "out.json"]),
+        args(&["tp1", "data", "four", "out.json"]),
+        args(&["tp1", "data", "4"]),
+        args(&["tp1", "--threads", "4", "out.json"]),
+    ];
+    for invocation in invocations {
+        assert_eq!(rewrite_legacy_args(invocation.clone()), invocation);
+    }
+
+    // A subcommand followed by a number, such as `top 4 out.json`, is never taken for a legacy input directory.
+    for subcommand in Cli::command().get_subcommands() {
+        let invocation = args(&["tp1", subcommand.get_name(), "4", "out.json"]);
+        assert_eq!(rewrite_legacy_args(invocation.clone()), invocation);
+    }
+}
+
+#[test]
+fn player_query_breaks_down_the_weapons_of_a_player() {
+    let dir = write_dataset("cli-player");
+    let (command, deaths_info, config) = ingest(&["player", "alice", "--threads", "2"], &dir);
+    remove_dir_all(&dir).unwrap();
+    let Command::Player { name, .. } = command else {
+        panic!("Expected the player subcommand");
+    };
+    let data = QueryData::new(&deaths_info, &config);
+
+    let breakdown = query_player(&data, &name).unwrap();
+    assert_eq!(breakdown.kills, 3);
+    let weapons: Vec<_> = breakdown
+        .weapons
+        .iter()
+        .map(|weapon| {
+            (
+                weapon.weapon.as_str(),
+                weapon.kills,
+                weapon.kills_percentage,
+                weapon.average_distance,
+            )
+        })
+        .collect();
+    assert_eq!(
+        weapons,
+        vec![("M416", 2, 66.67, 100.0), ("AKM", 1, 33.33, 400.0)]
+    );
+    assert!(query_player(&data, "nobody").is_none());
+}
+
+#[test]
+fn weapon_query_lists_its_top_players() {
+    let dir = write_dataset("cli-weapon");
+    let (_, deaths_info, config) = ingest(&["weapon", "M416"], &dir);
+    remove_dir_all(&dir).unwrap();
+    let data = QueryData::new(&deaths_info, &config);
+
+    let details = query_weapon(&data, "M416").unwrap();
+    assert_eq!(details.deaths, 3);
+    assert_eq!(details.deaths_percentage, 42.86);
+    assert_eq!(details.average_distance, 100.0);
+    let top_players: Vec<_> = details
+        .top_players
+        .iter()
+        .map(|player| (player.player.as_str(), player.kills))
+        .collect();
+    assert_eq!(top_players, vec![("alice", 2), ("carl", 1)]);
+    assert!(query_weapon(&data, "Crossbow").is_none());
+}
+
+#[test]
+fn top_query_ranks_by_each_metric() {
+    let dir = write_dataset("cli-top");
+    let (command, deaths_info, config) = ingest(
+        &[
+            "top",
+            "--by",
+            "average-distance",
+            "--limit",
+            "2",
+            "--min-value",
+            "10",
+        ],
+        &dir,
+    );
+    remove_dir_all(&dir).unwrap();
+    let Command::Top {
+        by, limit, filter, ..
+    } = command
+    else {
+        panic!("Expected the top subcommand");
+    };
+    let data = QueryData::new(&deaths_info, &config);
+    let ranking = |metric, limit| {
+        query_top(&data, metric, limit, &Default::default())
+            .into_iter()
+            .map(|entry| (entry.rank, entry.name, entry.value))
+            .collect::<Vec<_>>()
+    };
+
+    let entries: Vec<_> = query_top(&data, by, limit, &filter)
+        .into_iter()
+        .map(|entry| (entry.name, entry.value))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![("AKM".to_owned(), 400.0), ("M416".to_owned(), 100.0)]
+    );
+
+    assert_eq!(
+        ranking(TopMetric::Kills, 2),
+        vec![(1, "alice".to_owned(), 3.0), (2, "bob".to_owned(), 2.0)]
+    );
+    // Ties are broken by name.
+    assert_eq!(
+        ranking(TopMetric::WeaponVariety, 3),
+        vec![
+            (1, "alice".to_owned(), 2.0),
+            (2, "bob".to_owned(), 2.0),
+            (3, "carl".to_owned(), 1.0)
+        ]
+    );
+    // Deaths that are not player kills count towards their weapon.
+    assert_eq!(
+        ranking(TopMetric::WeaponDeaths, 4),
+        vec![
+            (1, "M416".to_owned(), 3.0),
+            (2, "AKM".to_owned(), 2.0),
+            (3, "Bluezone".to_owned(), 1.0),
+            (4, "Pan".to_owned(), 1.0)
+        ]
+    );
+}
@@ -0,0 +1,195 @@
+mod common;
+
+use common::{test_dir, write_rows};
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+use tp1::cohort_analysis::chi_square::{chi_square_survival, chi_square_test};
+use tp1::cohort_analysis::cohort_analyzer::compare_cohorts;
+use tp1::cohort_analysis::cohort_error::CohortError;
+use tp1::cohort_analysis::cohort_spec::CohortSpec;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_directory;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::synthetic_data::synthetic_dataset::CSV_HEADER;
+
+/// Writes a dataset where `ace` has 3 close kills with a shotgun, `bob` 2 long kills with a rifle and `cat` 1 long
+/// kill with a rifle, and a players file with `bob` and `cat`.
+fn write_cohort_dataset(dir: &PathBuf) -> String {
+    create_dir_all(dir).unwrap();
+    let row = |weapon: &str, killer: &str, distance: f64| {
+        format!(
+            "{},{},1.0,1000.0,1000.0,ERANGEL,m1,1,victim,2.0,{},1000.0\n",
+            weapon,
+            killer,
+            1000.0 + distance * 100.0
+        )
+    };
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for _ in 0..3 {
+        csv += &row("S12K", "ace", 5.0);
+    }
+    for _ in 0..2 {
+        csv += &row("M416", "bob", 300.0);
+    }
+    csv += &row("M416", "cat", 300.0);
+    write(dir.join("deaths.csv"), csv).unwrap();
+
+    let players_path = dir.join("players.txt");
+    write(&players_path, "# Rifle players\nbob\n\ncat\nnobody\n").unwrap();
+    players_path.to_str().unwrap().to_owned()
+}
+
+#[test]
+fn chi_square_survival_matches_known_critical_values() {
+    assert!((chi_square_survival(3.841_458_820_694_124, 1) - 0.05).abs() < 1e-6);
+    assert!((chi_square_survival(18.307_038_053_275_146, 10) - 0.05).abs() < 1e-6);
+    assert!((chi_square_survival(2.0, 2) - (-1.0f64).exp()).abs() < 1e-9);
+    assert_eq!(chi_square_survival(0.0, 3), 1.0);
+}
+
+#[test]
+fn chi_square_test_needs_two_columns_with_kills() {
+    assert_eq!(chi_square_test(&[5, 0], &[3, 0]), None);
+    assert_eq!(chi_square_test(&[0, 0], &[3, 4]), None);
+
+    let test = chi_square_test(&[10, 10], &[10, 10]).unwrap();
+    assert_eq!(test.statistic, 0.0);
+    assert_eq!(test.degrees_of_freedom, 1);
+    assert_eq!(test.p_value, 1.0);
+}
+
+#[test]
+fn rank_and_players_cohorts_are_compared() {
+    let dir = test_dir("cohort-compare");
+    let players_path = write_cohort_dataset(&dir);
+    let config = ProcessingConfig::default();
+    let deaths_info = process_directory(dir.to_str().unwrap(), 1, &config, &RunProgress::default());
+
+    let cohort_a = "rank:1-1".parse::<CohortSpec>().unwrap();
+    let cohort_b = format!("players:{}", players_path)
+        .parse::<CohortSpec>()
+        .unwrap();
+    let report = compare_cohorts(&deaths_info, &cohort_a, &cohort_b, &config, 1).unwrap();
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(report.cohort_a.name, "rank:1-1");
+    assert_eq!(report.cohort_a.players, 1);
+    assert_eq!(report.cohort_a.kills, 3);
+    assert_eq!(report.cohort_a.average_distance, Some(500.0));
+    assert_eq!(report.cohort_a.range_profile.close_percentage, 100.0);
+    assert_eq!(report.cohort_b.players, 2);
+    assert_eq!(report.cohort_b.kills, 3);
+    assert_eq!(report.cohort_b.average_distance, Some(30000.0));
+    assert_eq!(report.average_distance_difference, Some(29500.0));
+
+    let weapons: Vec<_> = report
+        .weapon_mix
+        .iter()
+        .map(|share| {
+            (
+                share.weapon.as_str(),
+                share.a_percentage,
+                share.b_percentage,
+            )
+        })
+        .collect();
+    assert_eq!(weapons, vec![("M416", 0.0, 100.0), ("Other", 100.0, 0.0)]);
+
+    let weapon_test = report.weapon_mix_test.unwrap();
+    assert_eq!(weapon_test.degrees_of_freedom, 1);
+    assert!((weapon_test.statistic - 6.0).abs() < 1e-9);
+    assert_eq!(report.range_band_test.unwrap().degrees_of_freedom, 1);
+}
+
+#[test]
+fn placement_cohorts_keep_the_kills_made_with_those_placements() {
+    let dir = test_dir("cohort-placement");
+    let row = |weapon: &str, killer: &str, placement: u32, distance: f64| {
+        format!(
+            "{},{},{}.0,1000.0,1000.0,ERANGEL,m1,1,victim,20.0,{},1000.0\n",
+            weapon,
+            killer,
+            placement,
+            1000.0 + distance * 100.0
+        )
+    };
+    // ace wins a match with 2 rifle kills and finishes 30th in another with a shotgun kill, and bob finishes 3rd
+    // with a rifle kill and 40th with 2 shotgun kills. The last kill has no known placement.
+    let rows = [
+        row("M416", "ace", 1, 300.0),
+        row("M416", "ace", 1, 300.0),
+        row("S12K", "ace", 30, 5.0),
+        row("M416", "bob", 3, 300.0),
+        row("S12K", "bob", 40, 5.0),
+        row("S12K", "bob", 40, 5.0),
+        "S12K,bob,,1000.0,1000.0,ERANGEL,m1,1,victim,20.0,1500.0,1000.0\n".to_owned(),
+    ];
+    write_rows(&dir.join("deaths.csv"), &rows);
+
+    let cohort_a = "placement:1-10".parse::<CohortSpec>().unwrap();
+    let cohort_b = "placement:11-".parse::<CohortSpec>().unwrap();
+    let mut config = ProcessingConfig::default();
+    config.track_killer_placements = true;
+    let deaths_info = process_directory(dir.to_str().unwrap(), 2, &config, &RunProgress::default());
+    let report = compare_cohorts(&deaths_info, &cohort_a, &cohort_b, &config, 1).unwrap();
+
+    // Without the kills collected by placement, the placement cohorts have no kills.
+    let untracked = process_directory(
+        dir.to_str().unwrap(),
+        2,
+        &ProcessingConfig::default(),
+        &RunProgress::default(),
+    );
+    let untracked_result = compare_cohorts(&untracked, &cohort_a, &cohort_b, &config, 1);
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(report.cohort_a.name, "placement:1-10");
+    assert_eq!(report.cohort_a.players, 2);
+    assert_eq!(report.cohort_a.kills, 3);
+    assert_eq!(report.cohort_a.average_distance, Some(30000.0));
+    assert_eq!(report.cohort_b.name, "placement:11-");
+    assert_eq!(report.cohort_b.players, 2);
+    assert_eq!(report.cohort_b.kills, 3);
+    assert_eq!(report.cohort_b.average_distance, Some(500.0));
+    let weapons: Vec<_> = report
+        .weapon_mix
+        .iter()
+        .map(|share| {
+            (
+                share.weapon.as_str(),
+                share.a_percentage,
+                share.b_percentage,
+            )
+        })
+        .collect();
+    assert_eq!(weapons, vec![("M416", 100.0, 0.0), ("Other", 0.0, 100.0)]);
+    assert!(matches!(
+        untracked_result,
+        Err(CohortError::EmptyCohort(cohort)) if cohort == "placement:1-10"
+    ));
+}
+
+#[test]
+fn cohorts_without_kills_or_players_file_are_errors() {
+    let dir = test_dir("cohort-errors");
+    write_cohort_dataset(&dir);
+    let config = ProcessingConfig::default();
+    let deaths_info = process_directory(dir.to_str().unwrap(), 1, &config, &RunProgress::default());
+    remove_dir_all(&dir).unwrap();
+
+    let top = "rank:1-".parse::<CohortSpec>().unwrap();
+    let beyond = "rank:4-".parse::<CohortSpec>().unwrap();
+    assert!(matches!(
+        compare_cohorts(&deaths_info, &top, &beyond, &config, 10),
+        Err(CohortError::EmptyCohort(cohort)) if cohort == "rank:4-"
+    ));
+
+    let missing = CohortSpec::Players(dir.join("missing.txt").to_str().unwrap().to_owned());
+    assert!(matches!(
+        compare_cohorts(&deaths_info, &missing, &top, &config, 10),
+        Err(CohortError::FileRead(_))
+    ));
+    assert!("rank:3-2".parse::<CohortSpec>().is_err());
+    assert!("placement:0-10".parse::<CohortSpec>().is_err());
+    assert!("top:1-2".parse::<CohortSpec>().is_err());
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{DataType, Field, Schema};
+use common::test_dir;
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+use std::fs::{create_dir_all, read_dir, read_to_string, remove_dir_all, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::synthetic_data::synthetic_dataset::{SyntheticDataset, CSV_HEADER};
+
+/// Columns written as `f64`, the rest are written as strings.
+const NUMERIC_COLUMNS: [&str; 7] = [
+    "killer_placement",
+    "killer_position_x",
+    "killer_position_y",
+    "time",
+    "victim_placement",
+    "victim_position_x",
+    "victim_position_y",
+];
+
+/// Reads a CSV file of the synthetic dataset into a record batch.
+fn csv_to_batch(path: &Path) -> RecordBatch {
+    let content = read_to_string(path).unwrap();
+    let rows: Vec<Vec<&str>> = content
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').collect())
+        .collect();
+
+    let (fields, columns): (Vec<Field>, Vec<ArrayRef>) = CSV_HEADER
+        .split(',')
+        .enumerate()
+        .map(|(index, name)| {
+            let values = rows.iter().map(|row| row[index]);
+            if NUMERIC_COLUMNS.contains(&name) {
+                let column: Float64Array = values.map(|value| value.parse().ok()).collect();
+                (
+                    Field::new(name, DataType::Float64, true),
+                    Arc::new(column) as ArrayRef,
+                )
+            } else {
+                let column: StringArray = values.map(Some).collect();
+                (
+                    Field::new(name, DataType::Utf8, true),
+                    Arc::new(column) as ArrayRef,
+                )
+            }
+        })
+        .unzip();
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+}
+
+/// Lists the CSV files of a directory, leaving out the expected aggregates written next to them.
+fn csv_files(dir_path: &Path) -> Vec<PathBuf> {
+    read_dir(dir_path)
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "csv"))
+        .collect()
+}
+
+/// Converts every CSV file of a directory to Parquet, with several row groups per file.
+fn write_parquet(csv_dir: &Path, parquet_dir: &Path) {
+    create_dir_all(parquet_dir).unwrap();
+    for path in csv_files(csv_dir) {
+        let batch = csv_to_batch(&path);
+        let file =
+            File::create(parquet_dir.join(path.with_extension("parquet").file_name().unwrap()))
+                .unwrap();
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(500)
+            .build();
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+}
+
+/// Converts every CSV file of a directory to Arrow IPC, with several record batches per file.
+fn write_arrow_ipc(csv_dir: &Path, ipc_dir: &Path) {
+    create_dir_all(ipc_dir).unwrap();
+    for path in csv_files(csv_dir) {
+        let batch = csv_to_batch(&path);
+        let file =
+            File::create(ipc_dir.join(path.with_extension("arrow").file_name().unwrap())).unwrap();
+        let mut writer = FileWriter::try_new(file, &batch.schema()).unwrap();
+        for offset in (0..batch.num_rows()).step_by(700) {
+            writer
+                .write(&batch.slice(offset, 700.min(batch.num_rows() - offset)))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+    }
+}
+
+/// Processes a directory with the given strategy, returning the summary as JSON.
+fn summarize_with(dir_path: &Path, strategy: ProcessingStrategy) -> serde_json::Value {
+    let mut config = ProcessingConfig::default();
+    config.strategy = strategy;
+    let summary = process_data_in_parallel(
+        dir_path.to_str().unwrap(),
+        3,
+        &config,
+        &RunProgress::default(),
+    );
+    serde_json::to_value(summary).unwrap()
+}
+
+#[test]
+fn columnar_files_produce_the_same_summary_as_csv() {
+    let base_dir = test_dir("columnar");
+    let (csv_dir, parquet_dir, ipc_dir) = (
+        base_dir.join("csv"),
+        base_dir.join("parquet"),
+        base_dir.join("ipc"),
+    );
+    let dataset = SyntheticDataset {
+        seed: 41,
+        players: 200,
+        file_rows: vec![2_000, 1_300, 900],
+        special_death_rate: 0.05,
+        ..SyntheticDataset::default()
+    };
+    dataset.write_to(&csv_dir).unwrap();
+    write_parquet(&csv_dir, &parquet_dir);
+    write_arrow_ipc(&csv_dir, &ipc_dir);
+
+    let expected = summarize_with(&csv_dir, ProcessingStrategy::Reduce);
+    for strategy in [ProcessingStrategy::Reduce, ProcessingStrategy::Pipeline] {
+        assert_eq!(
+            summarize_with(&parquet_dir, strategy),
+            expected,
+            "Parquet with {:?}",
+            strategy
+        );
+        assert_eq!(
+            summarize_with(&ipc_dir, strategy),
+            expected,
+            "Arrow IPC with {:?}",
+            strategy
+        );
+    }
+
+    remove_dir_all(base_dir).unwrap();
+}
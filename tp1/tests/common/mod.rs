@@ -0,0 +1,119 @@
+// Each test crate compiles this module on its own and uses only some of the helpers.
+#![allow(dead_code)]
+
+use clap::{Args, Command, FromArgMatches};
+use serde_json::Value;
+use std::env::temp_dir;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::{Path, PathBuf};
+use tp1::cli::cli_args::IngestArgs;
+use tp1::config::config_error::ConfigError;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+use tp1::synthetic_data::synthetic_dataset::CSV_HEADER;
+
+/// Returns a directory in the system temporary directory only used by the test with the given name.
+pub fn test_dir(name: &str) -> PathBuf {
+    temp_dir().join(format!("tp1-test-{}-{}", name, std::process::id()))
+}
+
+/// Writes a CSV file with the header of the dumps and the given rows, creating its directory if needed.
+pub fn write_rows(path: &Path, rows: &[String]) {
+    if let Some(dir) = path.parent() {
+        create_dir_all(dir).unwrap();
+    }
+    write(path, format!("{}\n{}", CSV_HEADER, rows.concat())).unwrap();
+}
+
+/// Writes the rows to a directory only used by the test with the given name and processes them with the config.
+pub fn summarize_rows(name: &str, rows: &[String], config: &ProcessingConfig) -> DeathsInfoSummary {
+    let dir = test_dir(name);
+    write_rows(&dir.join("deaths.csv"), rows);
+    let summary =
+        process_data_in_parallel(dir.to_str().unwrap(), 2, config, &RunProgress::default());
+    remove_dir_all(&dir).unwrap();
+    summary
+}
+
+/// Builds the `ProcessingConfig` of the given ingest options, such as `["--outlier-policy", "drop"]`, as the
+/// subcommands that read the input do.
+pub fn try_config_from_args(options: &[&str]) -> Result<ProcessingConfig, ConfigError> {
+    let matches = IngestArgs::augment_args(Command::new("tp1"))
+        .try_get_matches_from(["tp1", "unused"].iter().chain(options))
+        .unwrap();
+    IngestArgs::from_arg_matches(&matches).unwrap().to_config()
+}
+
+/// Builds the `ProcessingConfig` of the given ingest options, which must be valid.
+pub fn config_from_args(options: &[&str]) -> ProcessingConfig {
+    try_config_from_args(options).unwrap()
+}
+
+/// Writes a configuration file to a directory only used by the test with the given name, and builds the
+/// `ProcessingConfig` of the given option with the path of the file, followed by the other options.
+pub fn try_config_with_file(
+    name: &str,
+    option: &str,
+    content: &str,
+    options: &[&str],
+) -> Result<ProcessingConfig, ConfigError> {
+    let dir = test_dir(&format!("{}-config", name));
+    create_dir_all(&dir).unwrap();
+    let path = dir.join("config.json");
+    write(&path, content).unwrap();
+
+    let mut args = vec![option, path.to_str().unwrap()];
+    args.extend_from_slice(options);
+    let config = try_config_from_args(&args);
+    remove_dir_all(&dir).unwrap();
+    config
+}
+
+/// Builds the `ProcessingConfig` of a valid configuration file, as `try_config_with_file` does.
+pub fn config_with_file(
+    name: &str,
+    option: &str,
+    content: &str,
+    options: &[&str],
+) -> ProcessingConfig {
+    try_config_with_file(name, option, content, options).unwrap()
+}
+
+/// Processes a directory with the given strategy and thread count, returning the summary as JSON.
+pub fn summarize_with(dir_path: &Path, strategy: ProcessingStrategy, threads: usize) -> Value {
+    let mut config = ProcessingConfig::default();
+    config.strategy = strategy;
+    let summary = process_data_in_parallel(
+        dir_path.to_str().unwrap(),
+        threads,
+        &config,
+        &RunProgress::default(),
+    );
+    serde_json::to_value(summary).expect("Failed to serialize the summary")
+}
+
+/// Checks that two JSON values are exactly equal, reporting the path of the first difference.
+pub fn assert_json_eq(expected: &Value, actual: &Value, path: &str) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            let mut expected_keys: Vec<_> = expected.keys().collect();
+            let mut actual_keys: Vec<_> = actual.keys().collect();
+            expected_keys.sort();
+            actual_keys.sort();
+            assert_eq!(expected_keys, actual_keys, "{}: keys", path);
+            for (key, value) in expected {
+                assert_json_eq(value, &actual[key], &format!("{}.{}", path, key));
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            assert_eq!(expected.len(), actual.len(), "{}: length", path);
+            for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                assert_json_eq(expected, actual, &format!("{}[{}]", path, index));
+            }
+        }
+        _ => assert_eq!(expected, actual, "{}", path),
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::{config_from_args, config_with_file, summarize_rows};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+
+/// Rows on Sanhok, whose default size is 400000, with one row per kind of position issue.
+///
+/// As is, the distances are 500, 500, 200, 1000 and NaN. Clamped to the map, the negative and out of bounds rows are
+/// 100 and 500 apart.
+const ROWS: &str = "\
+M416,alice,1,100.0,100.0,SANHOK,m1,10,bob,2,400.0,500.0
+M416,alice,1,0.0,0.0,SANHOK,m1,20,carl,3,300.0,400.0
+M416,alice,1,-100.0,100.0,SANHOK,m1,30,dan,4,100.0,100.0
+M416,alice,1,400500.0,100.0,SANHOK,m1,40,erin,5,399500.0,100.0
+M416,alice,1,NaN,100.0,SANHOK,m1,50,fay,6,100.0,100.0
+";
+
+/// Processes `ROWS` with the given config in a directory only used by the test with the given name.
+fn summarize_outliers(name: &str, config: &ProcessingConfig) -> DeathsInfoSummary {
+    summarize_rows(name, &[ROWS.to_owned()], config)
+}
+
+#[test]
+fn every_issue_is_counted_once() {
+    let summary = summarize_outliers("outliers-count", &ProcessingConfig::default());
+
+    let outliers = &summary.coordinate_outliers;
+    assert_eq!(outliers.non_finite, 1);
+    assert_eq!(outliers.placeholder, 1);
+    assert_eq!(outliers.negative, 1);
+    assert_eq!(outliers.out_of_bounds, 1);
+    assert_eq!(outliers.clamped, 0);
+    // Non-finite positions are never averaged, whatever the policy.
+    assert_eq!(outliers.excluded, 1);
+    assert_eq!(summary.top_weapons["M416"].average_distance, 550.0);
+}
+
+#[test]
+fn drop_leaves_every_outlier_out_of_the_average() {
+    let summary = summarize_outliers(
+        "outliers-drop",
+        &config_from_args(&["--outlier-policy", "drop"]),
+    );
+
+    assert_eq!(summary.coordinate_outliers.excluded, 4);
+    assert_eq!(summary.coordinate_outliers.clamped, 0);
+    assert_eq!(summary.top_weapons["M416"].average_distance, 500.0);
+    // The rows still count as kills.
+    assert_eq!(summary.top_killers["alice"].deaths, 5);
+}
+
+#[test]
+fn clamp_moves_positions_inside_the_map_but_drops_placeholders() {
+    let summary = summarize_outliers(
+        "outliers-clamp",
+        &config_from_args(&["--outlier-policy", "clamp"]),
+    );
+
+    assert_eq!(summary.coordinate_outliers.excluded, 2);
+    assert_eq!(summary.coordinate_outliers.clamped, 2);
+    assert_eq!(summary.top_weapons["M416"].average_distance, 366.67);
+}
+
+#[test]
+fn map_sizes_file_replaces_the_default_bounds() {
+    let config = config_with_file(
+        "outliers-map-sizes",
+        "--map-sizes",
+        r#"{"SANHOK": 500000}"#,
+        &[],
+    );
+    let summary = summarize_outliers("outliers-map-sizes", &config);
+
+    assert_eq!(summary.coordinate_outliers.out_of_bounds, 0);
+    assert_eq!(summary.coordinate_outliers.negative, 1);
+}
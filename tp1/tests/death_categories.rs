@@ -0,0 +1,108 @@
+mod common;
+
+use common::{config_with_file, summarize_rows};
+use tp1::config::processing_config::ProcessingConfig;
+
+/// Returns a row of the given weapon, killer and victim, with their final placements.
+fn row(
+    weapon: &str,
+    killer: &str,
+    killer_placement: &str,
+    victim: &str,
+    victim_placement: &str,
+) -> String {
+    format!(
+        "{},{},{},100.0,100.0,ERANGEL,m1,10,{},{},200.0,200.0\n",
+        weapon, killer, killer_placement, victim, victim_placement
+    )
+}
+
+#[test]
+fn every_death_is_tallied_in_its_category() {
+    let summary = summarize_rows(
+        "categories",
+        &[
+            row("M416", "alice", "1", "bob", "2"),
+            row("Bluezone", "", "", "carl", "3"),
+            row("Falling", "", "", "dan", "4"),
+            row("Drown", "", "", "erin", "5"),
+            row("Uaz", "", "", "fay", "6"),
+            row("RedZone", "", "", "gus", "7"),
+            row("Grenade", "hal", "8", "hal", "8"),
+            row("AKM", "ivy", "9", "joe", "9"),
+        ],
+        &ProcessingConfig::default(),
+    );
+
+    let special_deaths = &summary.special_deaths;
+    assert_eq!(special_deaths.environment.blue_zone, 1);
+    assert_eq!(special_deaths.environment.falling, 1);
+    assert_eq!(special_deaths.environment.drowning, 1);
+    assert_eq!(special_deaths.environment.vehicle_explosion, 1);
+    assert_eq!(special_deaths.environment.other, 1);
+    assert_eq!(special_deaths.environment.total, 5);
+    assert_eq!(special_deaths.suicides, 1);
+    assert_eq!(special_deaths.team_kills, 1);
+
+    // Only the player kill is counted in the player stats, but every death counts in the weapon totals.
+    assert_eq!(summary.top_killers.len(), 1);
+    assert_eq!(summary.top_killers["alice"].deaths, 1);
+    assert_eq!(summary.top_weapons.len(), 8);
+}
+
+#[test]
+fn environment_causes_win_over_a_killer_name() {
+    let summary = summarize_rows(
+        "environment-killer",
+        &[
+            row("Bluezone", "alice", "1", "alice", "1"),
+            row("Falling", "bob", "2", "carl", "2"),
+        ],
+        &ProcessingConfig::default(),
+    );
+
+    assert_eq!(summary.special_deaths.environment.blue_zone, 1);
+    assert_eq!(summary.special_deaths.environment.falling, 1);
+    assert_eq!(summary.special_deaths.suicides, 0);
+    assert_eq!(summary.special_deaths.team_kills, 0);
+    assert!(summary.top_killers.is_empty());
+}
+
+#[test]
+fn vehicle_deaths_with_a_killer_are_player_kills() {
+    let summary = summarize_rows(
+        "roadkill",
+        &[
+            row("Uaz", "alice", "1", "bob", "2"),
+            row("Motorbike", "alice", "1", "carl", "3"),
+            row("Dacia", "", "", "dan", "4"),
+        ],
+        &ProcessingConfig::default(),
+    );
+
+    assert_eq!(summary.special_deaths.environment.vehicle_explosion, 1);
+    assert_eq!(summary.top_killers["alice"].deaths, 2);
+}
+
+#[test]
+fn causes_are_recognised_through_weapon_aliases() {
+    let mapping = r#"{
+        "Bluezone": { "category": "Environment", "aliases": ["Blue Zone"] },
+        "Uaz": { "category": "Vehicle", "aliases": ["UAZ (open top)"] }
+    }"#;
+    let config = config_with_file("cause-aliases", "--weapon-mapping", mapping, &[]);
+    let summary = summarize_rows(
+        "cause-aliases",
+        &[
+            row("Blue Zone", "", "", "alice", "1"),
+            row("UAZ (open top)", "", "", "bob", "2"),
+            row("UAZ (open top)", "carl", "3", "dan", "4"),
+        ],
+        &config,
+    );
+
+    assert_eq!(summary.special_deaths.environment.blue_zone, 1);
+    assert_eq!(summary.special_deaths.environment.vehicle_explosion, 1);
+    assert_eq!(summary.special_deaths.environment.other, 0);
+    assert_eq!(summary.top_killers["carl"].deaths, 1);
+}
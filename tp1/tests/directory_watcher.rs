@@ -0,0 +1,113 @@
+mod common;
+
+use common::test_dir;
+use std::fs::{create_dir_all, read_dir, remove_dir_all, write};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::thread::{self, sleep};
+use std::time::Duration;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::data_processing::directory_watcher::watch_directory;
+use tp1::data_summary::data_summarizer::summarize;
+use tp1::file_creation::file_creator::create_matches_file;
+use tp1::synthetic_data::synthetic_dataset::CSV_HEADER;
+
+/// Writes a CSV file with the given number of kills of `M416`.
+fn write_kills(path: &Path, kills: usize) {
+    let rows: String = (0..kills)
+        .map(|index| {
+            format!(
+                "M416,player{},1.0,100.0,100.0,ERANGEL,m1,{},victim,2.0,200.0,200.0\n",
+                index, index
+            )
+        })
+        .collect();
+    write(path, format!("{}\n{}", CSV_HEADER, rows)).unwrap();
+}
+
+#[test]
+fn files_dropped_while_watching_update_the_summary() {
+    for strategy in [ProcessingStrategy::Reduce, ProcessingStrategy::Pipeline] {
+        let dir = test_dir(&format!("watch-{:?}", strategy));
+        create_dir_all(&dir).unwrap();
+        write_kills(&dir.join("first.csv"), 3);
+
+        let mut config = ProcessingConfig::default();
+        config.strategy = strategy;
+        let mut updates = Vec::new();
+        watch_directory(
+            dir.to_str().unwrap(),
+            2,
+            &config,
+            Duration::from_millis(20),
+            |deaths_info, new_files| {
+                let summary = summarize(deaths_info, &config);
+                let kills: u32 = summary.placement.brackets.values().map(|b| b.kills).sum();
+                updates.push((new_files.len(), kills));
+                if updates.len() == 1 {
+                    write_kills(&dir.join("second.csv"), 5);
+                    return ControlFlow::Continue(());
+                }
+                ControlFlow::Break(())
+            },
+        )
+        .unwrap();
+        remove_dir_all(&dir).unwrap();
+
+        assert_eq!(updates, vec![(1, 3), (1, 8)], "{:?}", strategy);
+    }
+}
+
+#[test]
+fn empty_directories_are_only_written_once() {
+    let dir = test_dir("watch-empty");
+    create_dir_all(&dir).unwrap();
+
+    let config = ProcessingConfig::default();
+    let mut updates = Vec::new();
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            // Several polls find the directory empty before the file arrives.
+            sleep(Duration::from_millis(200));
+            write_kills(&dir.join("late.csv"), 2);
+        });
+        watch_directory(
+            dir.to_str().unwrap(),
+            1,
+            &config,
+            Duration::from_millis(20),
+            |deaths_info, new_files| {
+                let summary = summarize(deaths_info, &config);
+                let kills: u32 = summary.placement.brackets.values().map(|b| b.kills).sum();
+                updates.push((new_files.len(), kills));
+                if updates.len() == 1 {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            },
+        )
+        .unwrap();
+    });
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(updates, vec![(0, 0), (1, 2)]);
+}
+
+#[test]
+fn failed_writes_leave_no_temporary_file() {
+    let dir = test_dir("atomic-write");
+    let target = dir.join("matches.jsonl");
+    // A file cannot be renamed over a directory, so the write fails after creating the temporary file.
+    create_dir_all(&target).unwrap();
+
+    assert!(create_matches_file(target.to_str().unwrap(), &[]).is_err());
+    let entries: Vec<_> = read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(entries, vec!["matches.jsonl"]);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use clap::ValueEnum;
+use common::{assert_json_eq, summarize_with};
+use serde_json::Value;
+use std::env;
+use std::fs::{read_dir, read_to_string, write};
+use std::path::{Path, PathBuf};
+use tp1::config::processing_strategy::ProcessingStrategy;
+
+/// Thread counts every case is processed with.
+const THREAD_COUNTS: [usize; 5] = [1, 2, 3, 4, 8];
+
+/// Environment variable that makes the test rewrite the expected outputs instead of checking them.
+const UPDATE_VARIABLE: &str = "UPDATE_GOLDEN";
+
+/// Returns the directories of the golden cases, sorted by name. Each one has an `input` directory and the
+/// `expected.json` summary of it.
+fn golden_cases() -> Vec<PathBuf> {
+    let golden_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let mut cases: Vec<_> = read_dir(golden_dir)
+        .expect("Failed to read the golden directory")
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    cases.sort();
+    cases
+}
+
+#[test]
+fn golden_summaries_match_with_every_strategy_and_thread_count() {
+    let update = env::var_os(UPDATE_VARIABLE).is_some();
+    let cases = golden_cases();
+    assert!(!cases.is_empty(), "No golden cases found");
+
+    for case in cases {
+        let name = case.file_name().unwrap().to_string_lossy().into_owned();
+        let reference = summarize_with(&case.join("input"), ProcessingStrategy::Reduce, 1);
+        for strategy in ProcessingStrategy::value_variants() {
+            for threads in THREAD_COUNTS {
+                assert_json_eq(
+                    &reference,
+                    &summarize_with(&case.join("input"), *strategy, threads),
+                    &format!(
+                        "{} with strategy {:?} and {} threads",
+                        name, strategy, threads
+                    ),
+                );
+            }
+        }
+
+        let expected_path = case.join("expected.json");
+        if update {
+            let json = serde_json::to_string_pretty(&reference).unwrap();
+            write(&expected_path, json + "\n").expect("Failed to write the expected output");
+            continue;
+        }
+        let expected = read_to_string(&expected_path).unwrap_or_else(|_| {
+            panic!(
+                "Missing {}, run with {}=1 to write it",
+                expected_path.display(),
+                UPDATE_VARIABLE
+            )
+        });
+        let expected: Value = serde_json::from_str(&expected).expect("Invalid expected output");
+        assert_json_eq(&expected, &reference, &name);
+    }
+}
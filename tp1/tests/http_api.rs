@@ -0,0 +1,173 @@
+mod common;
+
+use common::test_dir;
+use serde_json::Value;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_directory;
+use tp1::data_processing::deaths_info::DeathsInfo;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_query::query_data::QueryData;
+use tp1::http_api::query_server::handle_connection;
+use tp1::synthetic_data::synthetic_dataset::CSV_HEADER;
+
+/// Processes a dataset where `José` has 2 kills and `bob` has 1.
+fn deaths_info() -> DeathsInfo {
+    let dir = test_dir("http-api");
+    create_dir_all(&dir).unwrap();
+    let row = |weapon: &str, killer: &str| {
+        format!(
+            "{},{},1,1000.0,1000.0,ERANGEL,m1,10,victim,2,2000.0,1000.0\n",
+            weapon, killer
+        )
+    };
+    let csv = format!(
+        "{}\n{}{}{}",
+        CSV_HEADER,
+        row("M416", "José"),
+        row("AKM", "José"),
+        row("M416", "bob")
+    );
+    write(dir.join("deaths.csv"), csv).unwrap();
+
+    let deaths_info = process_directory(
+        dir.to_str().unwrap(),
+        1,
+        &ProcessingConfig::default(),
+        &RunProgress::default(),
+    );
+    remove_dir_all(&dir).unwrap();
+    deaths_info
+}
+
+/// Sends raw bytes to a connection handled by the server, returning the status code and the JSON body of the
+/// response.
+fn send(deaths_info: &DeathsInfo, request: &[u8]) -> (u16, Value) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let response = thread::scope(|scope| {
+        let client = scope.spawn(|| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            // The server may answer and close before reading everything, so write errors are expected.
+            let _ = stream.write_all(request);
+            let _ = stream.shutdown(Shutdown::Write);
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let config = ProcessingConfig::default();
+        handle_connection(stream, &QueryData::new(deaths_info, &config));
+        client.join().unwrap()
+    });
+
+    let status = response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .expect("Missing status code");
+    let body = response.split_once("\r\n\r\n").map_or("", |(_, body)| body);
+    (
+        status,
+        serde_json::from_str(body).expect("Invalid JSON body"),
+    )
+}
+
+/// Sends a `GET` request for a target.
+fn get(deaths_info: &DeathsInfo, target: &str) -> (u16, Value) {
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target);
+    send(deaths_info, request.as_bytes())
+}
+
+#[test]
+fn endpoints_answer_queries() {
+    let deaths_info = deaths_info();
+
+    let (status, body) = get(&deaths_info, "/players/Jos%C3%A9");
+    assert_eq!(status, 200);
+    assert_eq!(body["player"], "José");
+    assert_eq!(body["kills"], 2);
+
+    let (status, body) = get(&deaths_info, "/top/kills?limit=1");
+    assert_eq!(status, 200);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["name"], "José");
+
+    let (status, body) = get(&deaths_info, "/weapons/M416");
+    assert_eq!(status, 200);
+    assert_eq!(body["deaths"], 2);
+}
+
+#[test]
+fn path_segments_and_parameters_are_percent_decoded() {
+    let deaths_info = deaths_info();
+
+    let (status, body) = get(&deaths_info, "/players/a%2Bb%20c%zz");
+    assert_eq!(status, 404);
+    assert_eq!(body["error"], "Player a+b c%zz has no kills.");
+
+    // A sign after `%` is not a hexadecimal digit, so the escape is kept as it is.
+    let (_, body) = get(&deaths_info, "/players/%+1");
+    assert_eq!(body["error"], "Player %+1 has no kills.");
+
+    // `+` is only a space in the query string.
+    let (_, body) = get(&deaths_info, "/players/Jos%C3%A9+");
+    assert_eq!(body["error"], "Player José+ has no kills.");
+    let (status, body) = get(&deaths_info, "/top/kills?name_contains=o+");
+    assert_eq!(status, 200);
+    assert_eq!(body.as_array().unwrap().len(), 0);
+
+    let (status, body) = get(&deaths_info, "/top/kills?name_contains=Jos%C3%A9&limit=5");
+    assert_eq!(status, 200);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn invalid_requests_are_rejected() {
+    let deaths_info = deaths_info();
+
+    assert_eq!(get(&deaths_info, "/nothing/here").0, 404);
+    assert_eq!(get(&deaths_info, "/weapons/Crossbow").0, 404);
+    assert_eq!(get(&deaths_info, "/top/unknown").0, 400);
+    assert_eq!(get(&deaths_info, "/top/kills?limit=many").0, 400);
+    assert_eq!(
+        send(&deaths_info, b"POST /top/kills HTTP/1.1\r\n\r\n").0,
+        405
+    );
+    assert_eq!(send(&deaths_info, b"garbage\r\n\r\n").0, 400);
+
+    let long_target = format!("/players/{}", "a".repeat(10_000));
+    assert_eq!(get(&deaths_info, &long_target).0, 414);
+}
+
+#[test]
+fn slow_clients_are_cut_off_at_the_request_deadline() {
+    let deaths_info = deaths_info();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let elapsed = thread::scope(|scope| {
+        scope.spawn(|| {
+            // Each byte arrives well within the timeout of a single read, but the request never ends.
+            let mut stream = TcpStream::connect(address).unwrap();
+            for _ in 0..40 {
+                if stream.write_all(b"G").is_err() {
+                    return;
+                }
+                thread::sleep(Duration::from_millis(250));
+            }
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let config = ProcessingConfig::default();
+        let start = Instant::now();
+        handle_connection(stream, &QueryData::new(&deaths_info, &config));
+        start.elapsed()
+    });
+
+    assert!(elapsed < Duration::from_secs(8), "Took {:?}", elapsed);
+}
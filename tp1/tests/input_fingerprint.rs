@@ -0,0 +1,88 @@
+mod common;
+
+use clap::Parser;
+use common::test_dir;
+use std::fs::remove_dir_all;
+use tp1::cli::cli_args::{Cli, Command};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_directory;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::metadata::input_fingerprint::InputFingerprint;
+use tp1::synthetic_data::synthetic_dataset::SyntheticDataset;
+
+#[test]
+fn only_files_in_the_sample_are_fingerprinted() {
+    let dir = test_dir("fingerprint-sample");
+    let dataset = SyntheticDataset {
+        seed: 40,
+        file_rows: vec![50; 12],
+        ..SyntheticDataset::default()
+    };
+    dataset.write_to(&dir).unwrap();
+    let dir_str = dir.to_str().unwrap();
+
+    let cli = Cli::parse_from([
+        "tp1",
+        "summary",
+        dir_str,
+        "-o",
+        "unused.json",
+        "--sample",
+        "0.5",
+        "--sample-by",
+        "files",
+    ]);
+    let Command::Summary { ingest, sample, .. } = cli.command else {
+        panic!("Expected the summary subcommand");
+    };
+    let mut config = ingest.to_config().unwrap();
+    config.sampling = sample.sampling().unwrap();
+    let progress = RunProgress::default();
+    process_directory(dir_str, 2, &config, &progress);
+    let read_files = progress.read_files();
+    assert!(!read_files.is_empty() && read_files.len() < 12);
+
+    let hashed = InputFingerprint::of_files(dir_str, &read_files, 2, true);
+    let unhashed = InputFingerprint::of_files(dir_str, &read_files, 2, false);
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(hashed.files.len(), read_files.len());
+    assert!(hashed.files.iter().all(|file| file.sha256.is_some()));
+    assert!(unhashed.files.iter().all(|file| file.sha256.is_none()));
+    assert_eq!(
+        hashed
+            .files
+            .iter()
+            .map(|file| file.size)
+            .collect::<Vec<_>>(),
+        unhashed
+            .files
+            .iter()
+            .map(|file| file.size)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn cancelled_run_reads_no_more_files() {
+    let dir = test_dir("fingerprint-cancelled");
+    let dataset = SyntheticDataset {
+        seed: 41,
+        file_rows: vec![50; 3],
+        ..SyntheticDataset::default()
+    };
+    dataset.write_to(&dir).unwrap();
+
+    let progress = RunProgress::default();
+    progress.cancel();
+    process_directory(
+        dir.to_str().unwrap(),
+        2,
+        &ProcessingConfig::default(),
+        &progress,
+    );
+    remove_dir_all(&dir).unwrap();
+
+    assert!(progress.read_files().is_empty());
+    assert!(progress.metadata().partial);
+}
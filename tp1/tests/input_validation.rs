@@ -0,0 +1,66 @@
+mod common;
+
+use common::test_dir;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::input_validation::input_validator::validate_directory;
+use tp1::input_validation::validation_report::IssueKind;
+use tp1::synthetic_data::synthetic_dataset::{SyntheticDataset, CSV_HEADER};
+
+#[test]
+fn valid_dataset_has_no_problems_and_expected_rows() {
+    let dir = test_dir("validate-valid");
+    let dataset = SyntheticDataset {
+        seed: 5,
+        players: 50,
+        file_rows: vec![300, 700],
+        ..SyntheticDataset::default()
+    };
+    dataset.write_to(&dir).unwrap();
+
+    let report = validate_directory(
+        dir.to_str().unwrap(),
+        2,
+        &ProcessingConfig::default(),
+        10_000,
+    )
+    .unwrap();
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(report.files_with_problems(), 0);
+    assert_eq!(report.expected_rows, 1000);
+    assert!(report.estimated_runtime_seconds.is_some());
+}
+
+#[test]
+fn schema_and_encoding_problems_are_reported() {
+    let dir = test_dir("validate-problems");
+    create_dir_all(&dir).unwrap();
+    write(
+        dir.join("short_header.csv"),
+        "killed_by,killer_name\nM416,a\n",
+    )
+    .unwrap();
+    let mut latin1 = format!("\u{feff}{}\n", CSV_HEADER).into_bytes();
+    latin1.extend_from_slice(b"M416,Jos\xe9,1.0,1.0,1.0,ERANGEL,m,1,b,2.0,3.0,3.0\n");
+    write(dir.join("latin1.csv"), latin1).unwrap();
+
+    let report =
+        validate_directory(dir.to_str().unwrap(), 1, &ProcessingConfig::default(), 100).unwrap();
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(report.files_with_problems(), 2);
+    let latin1 = &report.files[0];
+    assert_eq!(latin1.invalid_encoding_rows, 1);
+    assert_eq!(latin1.issues.len(), 1);
+    assert_eq!(latin1.issues[0].kind, IssueKind::Encoding);
+    assert_eq!(latin1.issues[0].line, Some(2));
+
+    let short_header = &report.files[1];
+    assert_eq!(short_header.wrong_column_count_rows, 1);
+    assert!(short_header
+        .issues
+        .iter()
+        .all(|issue| issue.kind == IssueKind::SchemaMismatch));
+    assert_eq!(short_header.issues[0].line, Some(1));
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use clap::ValueEnum;
+use common::test_dir;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::synthetic_data::synthetic_dataset::CSV_HEADER;
+
+/// Returns a CSV row of a kill in match `m1` at the given distance.
+fn kill_row(
+    weapon: &str,
+    killer: &str,
+    killer_placement: u32,
+    victim: &str,
+    distance: f64,
+) -> String {
+    format!(
+        "{},{},{},1000.0,1000.0,ERANGEL,m1,10,{},20,{},1000.0\n",
+        weapon,
+        killer,
+        killer_placement,
+        victim,
+        1000.0 + distance
+    )
+}
+
+#[test]
+fn match_split_across_files_is_merged() {
+    let dir = test_dir("match-split");
+    create_dir_all(&dir).unwrap();
+    // Both files have a kill at the longest distance. `ace` wins the tie by name, whichever file is merged first.
+    let first = [
+        kill_row("M416", "bob", 3, "v1", 10_000.0),
+        kill_row("M416", "bob", 3, "v2", 40_000.0),
+        kill_row("AKM", "ace", 2, "v3", 500.0),
+    ];
+    let second = [
+        kill_row("AKM", "ace", 1, "v4", 40_000.0),
+        kill_row("M416", "bob", 3, "v5", 2_000.0),
+        kill_row("S12K", "cat", 5, "v6", 100.0),
+    ];
+    for (first_file, second_file) in [("a.csv", "b.csv"), ("b.csv", "a.csv")] {
+        write(
+            dir.join(first_file),
+            format!("{}\n{}", CSV_HEADER, first.concat()),
+        )
+        .unwrap();
+        write(
+            dir.join(second_file),
+            format!("{}\n{}", CSV_HEADER, second.concat()),
+        )
+        .unwrap();
+
+        for strategy in ProcessingStrategy::value_variants() {
+            for threads in [1, 2] {
+                let mut config = ProcessingConfig::default();
+                config.strategy = *strategy;
+                config.track_matches = true;
+                let summary = process_data_in_parallel(
+                    dir.to_str().unwrap(),
+                    threads,
+                    &config,
+                    &RunProgress::default(),
+                );
+                let context = format!(
+                    "{:?} with {} threads, {} first",
+                    strategy, threads, first_file
+                );
+
+                assert_eq!(summary.matches.len(), 1, "{}", context);
+                let match_summary = &summary.matches[0];
+                assert_eq!(match_summary.match_id, "m1");
+                assert_eq!(match_summary.deaths, 6, "{}", context);
+                assert_eq!(match_summary.weapons["M416"], 3, "{}", context);
+                assert_eq!(match_summary.weapons["AKM"], 2, "{}", context);
+
+                let leaderboard: Vec<_> = match_summary
+                    .leaderboard
+                    .iter()
+                    .map(|entry| (entry.player.as_str(), entry.kills, entry.placement))
+                    .collect();
+                assert_eq!(
+                    leaderboard,
+                    vec![
+                        ("bob", 3, Some(3)),
+                        ("ace", 2, Some(1)),
+                        ("cat", 1, Some(5))
+                    ],
+                    "{}",
+                    context
+                );
+
+                let longest_kill = match_summary.longest_kill.as_ref().unwrap();
+                assert_eq!(
+                    (
+                        longest_kill.killer.as_str(),
+                        longest_kill.victim.as_str(),
+                        longest_kill.weapon.as_str()
+                    ),
+                    ("ace", "v4", "AKM"),
+                    "{}",
+                    context
+                );
+                assert_eq!(longest_kill.distance, 40_000.0, "{}", context);
+            }
+        }
+    }
+
+    remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::test_dir;
+use serde_json::Value;
+use std::fs::{read_to_string, remove_dir_all};
+use std::path::Path;
+use std::process::Command;
+use tp1::synthetic_data::synthetic_dataset::SyntheticDataset;
+
+/// Reads a JSON file.
+fn read_json(path: &Path) -> Value {
+    serde_json::from_str(&read_to_string(path).unwrap()).unwrap()
+}
+
+/// Checks a JSON value against the schema of the summary files, reporting every error with its path.
+fn assert_valid(value: &Value, description: &str) {
+    let schema = read_json(&Path::new(env!("CARGO_MANIFEST_DIR")).join("output.schema.json"));
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let errors: Vec<_> = validator
+        .iter_errors(value)
+        .map(|error| format!("{}: {}", error.instance_path, error))
+        .collect();
+    assert!(errors.is_empty(), "{}: {:#?}", description, errors);
+}
+
+#[test]
+fn summary_files_follow_the_schema() {
+    let dir = test_dir("output-schema");
+    let input_dir = dir.join("input");
+    SyntheticDataset {
+        seed: 40,
+        players: 50,
+        file_rows: vec![500, 300],
+        special_death_rate: 0.1,
+        ..SyntheticDataset::default()
+    }
+    .write_to(&input_dir)
+    .unwrap();
+    let weapon_mapping = Path::new(env!("CARGO_MANIFEST_DIR")).join("weapon_mapping.example.json");
+
+    // The sections that are only written with some options are checked too.
+    let runs: [(&str, Vec<&str>); 3] = [
+        ("default", vec![]),
+        (
+            "weapon-mapping",
+            vec!["--weapon-mapping", weapon_mapping.to_str().unwrap()],
+        ),
+        ("sampled", vec!["--sample", "0.5", "--sample-by", "rows"]),
+    ];
+    for (name, options) in runs {
+        let output = dir.join(format!("{}.json", name));
+        let status = Command::new(env!("CARGO_BIN_EXE_TP1_BenitezNahuelTomas"))
+            .args(["summary", input_dir.to_str().unwrap(), "--threads", "2"])
+            .args(["--output", output.to_str().unwrap()])
+            .args(options)
+            .status()
+            .unwrap();
+        assert!(status.success(), "{}", name);
+        assert_valid(&read_json(&output), name);
+    }
+    remove_dir_all(&dir).unwrap();
+}
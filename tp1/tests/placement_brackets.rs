@@ -0,0 +1,89 @@
+mod common;
+
+use common::summarize_rows;
+use tp1::config::processing_config::ProcessingConfig;
+
+/// Returns a row of the given weapon, killer and victim, with their final placements as written in the dumps.
+fn row(
+    weapon: &str,
+    killer: &str,
+    killer_placement: &str,
+    victim: &str,
+    victim_placement: &str,
+) -> String {
+    format!(
+        "{},{},{},100.0,100.0,ERANGEL,m1,10,{},{},200.0,200.0\n",
+        weapon, killer, killer_placement, victim, victim_placement
+    )
+}
+
+#[test]
+fn kills_are_counted_in_the_bracket_of_the_killer() {
+    let summary = summarize_rows(
+        "placement-brackets",
+        &[
+            row("M416", "a", "1.0", "v1", "2"),
+            row("M416", "a", "1", "v2", "10"),
+            row("AKM", "b", "5", "v3", "30"),
+            row("AKM", "b", "5", "v4", "31"),
+            row("UMP9", "c", "2", "v5", "32"),
+            row("Vector", "d", "3", "v6", "33"),
+            row("Win94", "e", "4", "v7", "34"),
+            row("AKM", "f", "6.0", "v8", "7"),
+            row("Kar98k", "g", "10", "v9", "4"),
+            row("SKS", "h", "25", "v10", "60"),
+            row("S686", "i", "26", "v11", "70"),
+            row("Vector", "j", "51", "v12", "80"),
+            row("Groza", "k", "99", "v13", "1"),
+        ],
+        &ProcessingConfig::default(),
+    );
+
+    let brackets = &summary.placement.brackets;
+    let mut names: Vec<_> = brackets.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(names, vec!["1", "11-25", "2-5", "26-50", "51+", "6-10"]);
+    let kills: Vec<_> = ["1", "2-5", "6-10", "11-25", "26-50", "51+"]
+        .iter()
+        .map(|name| brackets[*name].kills)
+        .collect();
+    assert_eq!(kills, vec![2, 5, 2, 1, 1, 2]);
+    assert_eq!(brackets["1"].kills_percentage, 15.38);
+    assert_eq!(brackets["2-5"].kills_percentage, 38.46);
+
+    // Only the 3 most used weapons of a bracket are listed, breaking ties by name.
+    let top_weapons = &brackets["2-5"].weapons_percentage;
+    assert_eq!(top_weapons.len(), 3);
+    assert_eq!(top_weapons["AKM"], 40.0);
+    assert_eq!(top_weapons["UMP9"], 20.0);
+    assert_eq!(top_weapons["Vector"], 20.0);
+    assert_eq!(brackets["1"].weapons_percentage["M416"], 100.0);
+}
+
+#[test]
+fn only_player_kills_with_a_valid_placement_have_a_bracket() {
+    let summary = summarize_rows(
+        "placement-invalid",
+        &[
+            row("M416", "a", "1", "v1", "2"),
+            row("M416", "b", "", "v2", "5"),
+            row("M416", "c", "0", "v3", "nan"),
+            row("M416", "d", "3", "e", "3"),
+            row("Bluezone", "", "", "v4", "40"),
+        ],
+        &ProcessingConfig::default(),
+    );
+
+    let bracket_kills: u32 = summary
+        .placement
+        .brackets
+        .values()
+        .map(|bracket| bracket.kills)
+        .sum();
+    assert_eq!(bracket_kills, 1);
+
+    // Every death with a known victim placement counts, team kills and environment deaths included.
+    let average_victim_placement = &summary.placement.average_victim_placement;
+    assert_eq!(average_victim_placement["M416"], 3.33);
+    assert_eq!(average_victim_placement["Bluezone"], 40.0);
+}
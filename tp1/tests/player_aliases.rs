@@ -0,0 +1,97 @@
+mod common;
+
+use common::{config_with_file, summarize_rows};
+use tp1::config::player_names::{NameNormalization, PlayerNames};
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+
+const ALIASES: &str = r#"{ "GhostDL": ["GhostDL_old", "ghost_dl"] }"#;
+
+/// Returns a row of a kill of `victim` by `killer`, in different teams.
+fn row(killer: &str, victim: &str) -> String {
+    format!(
+        "M416,{},1,100.0,100.0,ERANGEL,m1,10,{},2,200.0,200.0\n",
+        killer, victim
+    )
+}
+
+/// Processes the rows with `ALIASES` and the given normalisation in a directory only used by the test with the
+/// given name.
+fn summarize_aliased(name: &str, rows: &[String], normalization: &str) -> DeathsInfoSummary {
+    let config = config_with_file(
+        name,
+        "--player-aliases",
+        ALIASES,
+        &["--normalize-names", normalization],
+    );
+    summarize_rows(name, rows, &config)
+}
+
+#[test]
+fn names_are_normalised_as_requested() {
+    let name = "ＧｈｏｓｔＤＬ";
+    let canonical_name = |normalization: &str| {
+        let player_names = PlayerNames::new(None, normalization.parse().unwrap()).unwrap();
+        player_names.canonical_name(name).into_owned()
+    };
+
+    assert_eq!(canonical_name("none"), name);
+    assert_eq!(canonical_name("case"), "ｇｈｏｓｔｄｌ");
+    assert_eq!(canonical_name("unicode"), "GhostDL");
+    assert_eq!(canonical_name("all"), "ghostdl");
+    assert!("upper".parse::<NameNormalization>().is_err());
+}
+
+#[test]
+fn aliases_are_merged_before_ranking() {
+    // Ten rivals with two kills each fill the top 10 unless the three aliases are merged.
+    let mut rows: Vec<_> = (0..10)
+        .flat_map(|rival| {
+            [
+                row(&format!("rival{}", rival), "a"),
+                row(&format!("rival{}", rival), "b"),
+            ]
+        })
+        .collect();
+    rows.extend([
+        row("GhostDL", "c"),
+        row("GhostDL_old", "d"),
+        row("ghost_dl", "e"),
+    ]);
+    let summary = summarize_aliased("aliases-merge", &rows, "none");
+
+    assert_eq!(summary.top_killers.len(), 10);
+    assert_eq!(summary.top_killers["GhostDL"].deaths, 3);
+    assert!(!summary.top_killers.contains_key("GhostDL_old"));
+    assert!(!summary.top_killers.contains_key("ghost_dl"));
+}
+
+#[test]
+fn normalised_names_match_the_normalised_aliases() {
+    let rows = [
+        row("GHOST_DL", "a"),
+        row("ghostdl", "b"),
+        row("Rival", "c"),
+        row("rival", "d"),
+    ];
+
+    let summary = summarize_aliased("aliases-case", &rows, "case");
+    assert_eq!(summary.top_killers["GhostDL"].deaths, 2);
+    assert_eq!(summary.top_killers["rival"].deaths, 2);
+
+    // Without normalisation the casing has to match an alias exactly.
+    let summary = summarize_aliased("aliases-exact", &rows, "none");
+    assert!(!summary.top_killers.contains_key("GhostDL"));
+    assert_eq!(summary.top_killers.len(), 4);
+}
+
+#[test]
+fn a_kill_of_an_alias_by_another_alias_is_a_suicide() {
+    let summary = summarize_aliased(
+        "aliases-suicide",
+        &[row("GhostDL_old", "ghost_dl"), row("GhostDL", "a")],
+        "none",
+    );
+
+    assert_eq!(summary.special_deaths.suicides, 1);
+    assert_eq!(summary.top_killers["GhostDL"].deaths, 1);
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::{assert_json_eq, summarize_with, test_dir};
+use std::fs::remove_dir_all;
+use std::path::PathBuf;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::synthetic_data::synthetic_dataset::SyntheticDataset;
+
+/// Writes a small synthetic dataset to a directory only used by this test.
+fn write_dataset(name: &str) -> PathBuf {
+    let dir_path = test_dir(name);
+    let dataset = SyntheticDataset {
+        seed: 38,
+        players: 300,
+        file_rows: vec![4_000, 2_500, 0, 1_200, 3_100],
+        malformed_rate: 0.01,
+        special_death_rate: 0.05,
+        skew: 1.0,
+        ..SyntheticDataset::default()
+    };
+    dataset
+        .write_to(&dir_path)
+        .expect("Failed to write the test dataset");
+    dir_path
+}
+
+#[test]
+fn every_strategy_produces_the_same_summary() {
+    let dir_path = write_dataset("strategies");
+    let expected = summarize_with(&dir_path, ProcessingStrategy::Reduce, 1);
+
+    for strategy in [
+        ProcessingStrategy::Reduce,
+        ProcessingStrategy::LocalMaps,
+        ProcessingStrategy::Channel,
+        ProcessingStrategy::Pipeline,
+        ProcessingStrategy::Sharded,
+    ] {
+        for threads in [1, 2, 4, 7] {
+            assert_json_eq(
+                &expected,
+                &summarize_with(&dir_path, strategy, threads),
+                &format!("{:?} with {} threads", strategy, threads),
+            );
+        }
+    }
+
+    remove_dir_all(dir_path).unwrap();
+}
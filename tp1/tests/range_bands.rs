@@ -0,0 +1,110 @@
+mod common;
+
+use common::{config_from_args, test_dir, write_rows};
+use std::fs::remove_dir_all;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::config::range_bands::{RangeBand, RangeBands};
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+use tp1::data_summary::range_profile_summary::RangeProfileSummary;
+
+/// Returns a row of a kill with `M416` on the given map, whose positions are `distance` apart.
+fn row(map: &str, distance: f64) -> String {
+    format!(
+        "M416,alice,1,100.0,100.0,{},m1,10,victim,2,{},100.0\n",
+        map,
+        100.0 + distance
+    )
+}
+
+/// Writes two files of kills on Erangel and Miramar to a directory only used by the test with the given name, and
+/// processes them with the sharded strategy and the given extra options.
+fn summarize_kills(name: &str, options: &[&str]) -> DeathsInfoSummary {
+    let dir = test_dir(name);
+    let first_file = [
+        row("ERANGEL", 1_000.0),
+        row("ERANGEL", 10_000.0),
+        row("MIRAMAR", 1_000.0),
+        // A row without positions counts as a death, but not in the range profile.
+        "M416,alice,1,,,ERANGEL,m1,10,victim,2,,\n".to_owned(),
+    ];
+    let second_file = [
+        row("ERANGEL", 30_000.0),
+        row("ERANGEL", 60_000.0),
+        row("MIRAMAR", 4_999.0),
+    ];
+    write_rows(&dir.join("first.csv"), &first_file);
+    write_rows(&dir.join("second.csv"), &second_file);
+
+    let mut config = config_from_args(options);
+    config.strategy = ProcessingStrategy::Sharded;
+    let summary =
+        process_data_in_parallel(dir.to_str().unwrap(), 2, &config, &RunProgress::default());
+    remove_dir_all(&dir).unwrap();
+    summary
+}
+
+/// Returns a `RangeProfileSummary` with the given measured deaths and percentages.
+fn profile(measured_deaths: u32, percentages: [f64; 4]) -> RangeProfileSummary {
+    let [close_percentage, mid_percentage, long_percentage, extreme_percentage] = percentages;
+    RangeProfileSummary {
+        measured_deaths,
+        close_percentage,
+        mid_percentage,
+        long_percentage,
+        extreme_percentage,
+    }
+}
+
+#[test]
+fn limits_are_the_lower_bound_of_the_next_band() {
+    let bands = RangeBands::default();
+    assert_eq!(bands.band_of(0.0), RangeBand::Close);
+    assert_eq!(bands.band_of(4_999.9), RangeBand::Close);
+    assert_eq!(bands.band_of(5_000.0), RangeBand::Mid);
+    assert_eq!(bands.band_of(20_000.0), RangeBand::Long);
+    assert_eq!(bands.band_of(49_999.9), RangeBand::Long);
+    assert_eq!(bands.band_of(50_000.0), RangeBand::Extreme);
+}
+
+#[test]
+fn limits_must_be_three_increasing_positive_distances() {
+    let bands: RangeBands = "1000, 2000,3000".parse().unwrap();
+    assert_eq!(bands.to_string(), "1000,2000,3000");
+    assert_eq!(bands.band_of(2_500.0), RangeBand::Long);
+
+    for invalid in [
+        "1000,2000",
+        "1000,2000,3000,4000",
+        "3000,2000,1000",
+        "0,1000,2000",
+        "1000,2000,inf",
+        "a,b,c",
+    ] {
+        assert!(invalid.parse::<RangeBands>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn kills_are_split_into_bands_per_map() {
+    let summary = summarize_kills("range-bands-default", &[]);
+
+    let range_profile = &summary.top_weapons["M416"].range_profile;
+    assert_eq!(range_profile.len(), 2);
+    assert_eq!(
+        range_profile["ERANGEL"],
+        profile(4, [25.0, 25.0, 25.0, 25.0])
+    );
+    assert_eq!(range_profile["MIRAMAR"], profile(2, [100.0, 0.0, 0.0, 0.0]));
+    assert_eq!(summary.top_killers["alice"].deaths, 7);
+}
+
+#[test]
+fn custom_limits_move_the_kills_between_bands() {
+    let summary = summarize_kills("range-bands-custom", &["--range-bands", "2000,5000,100000"]);
+
+    let range_profile = &summary.top_weapons["M416"].range_profile;
+    assert_eq!(range_profile["ERANGEL"], profile(4, [25.0, 0.0, 75.0, 0.0]));
+    assert_eq!(range_profile["MIRAMAR"], profile(2, [50.0, 50.0, 0.0, 0.0]));
+}
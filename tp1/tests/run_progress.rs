@@ -0,0 +1,122 @@
+mod common;
+
+use common::test_dir;
+use std::fs::remove_dir_all;
+use std::path::PathBuf;
+use std::thread;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+use tp1::synthetic_data::expected_aggregates::ExpectedAggregates;
+use tp1::synthetic_data::synthetic_dataset::SyntheticDataset;
+
+const STRATEGIES: [ProcessingStrategy; 5] = [
+    ProcessingStrategy::Reduce,
+    ProcessingStrategy::LocalMaps,
+    ProcessingStrategy::Channel,
+    ProcessingStrategy::Pipeline,
+    ProcessingStrategy::Sharded,
+];
+
+/// Writes a dataset of 30 files to a directory only used by the test with the given name.
+fn write_dataset(name: &str) -> (PathBuf, ExpectedAggregates) {
+    let dir = test_dir(name);
+    let expected = SyntheticDataset {
+        seed: 36,
+        players: 100,
+        file_rows: vec![2_000; 30],
+        ..SyntheticDataset::default()
+    }
+    .write_to(&dir)
+    .unwrap();
+    (dir, expected)
+}
+
+/// Returns the number of player kills with a known killer placement in a summary.
+fn bracket_kills(summary: &DeathsInfoSummary) -> u32 {
+    summary
+        .placement
+        .brackets
+        .values()
+        .map(|bracket| bracket.kills)
+        .sum()
+}
+
+#[test]
+fn finished_runs_report_every_file_and_row() {
+    let (dir, expected) = write_dataset("progress-finished");
+    let progress = RunProgress::default();
+    process_data_in_parallel(
+        dir.to_str().unwrap(),
+        2,
+        &ProcessingConfig::default(),
+        &progress,
+    );
+    remove_dir_all(&dir).unwrap();
+
+    let metadata = progress.metadata();
+    assert!(!metadata.partial);
+    assert_eq!(metadata.files_processed, 30);
+    assert_eq!(metadata.rows_processed, expected.rows);
+    assert_eq!(progress.read_files().len(), 30);
+    assert!(metadata.timings.parsing_ms > 0.0);
+    assert!(metadata.timings.total_ms > 0.0);
+    assert!(progress.to_string().starts_with("30/30 files, "));
+}
+
+#[test]
+fn runs_cancelled_before_starting_read_nothing() {
+    let (dir, _) = write_dataset("progress-cancelled");
+
+    for strategy in STRATEGIES {
+        let mut config = ProcessingConfig::default();
+        config.strategy = strategy;
+        let progress = RunProgress::default();
+        progress.cancel();
+        let summary = process_data_in_parallel(dir.to_str().unwrap(), 3, &config, &progress);
+
+        let metadata = progress.metadata();
+        assert!(metadata.partial, "{:?}", strategy);
+        assert_eq!(metadata.files_processed, 0, "{:?}", strategy);
+        assert_eq!(metadata.rows_processed, 0, "{:?}", strategy);
+        assert!(progress.read_files().is_empty(), "{:?}", strategy);
+        assert!(summary.top_killers.is_empty(), "{:?}", strategy);
+    }
+    remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn runs_cancelled_midway_keep_the_rows_read_so_far() {
+    let (dir, _) = write_dataset("progress-midway");
+    let full_kills = bracket_kills(&process_data_in_parallel(
+        dir.to_str().unwrap(),
+        1,
+        &ProcessingConfig::default(),
+        &RunProgress::default(),
+    ));
+
+    for strategy in [ProcessingStrategy::Reduce, ProcessingStrategy::Pipeline] {
+        let mut config = ProcessingConfig::default();
+        config.strategy = strategy;
+        let progress = RunProgress::default();
+        let summary = thread::scope(|scope| {
+            scope.spawn(|| {
+                while progress.metadata().rows_processed == 0 {
+                    thread::yield_now();
+                }
+                progress.cancel();
+            });
+            process_data_in_parallel(dir.to_str().unwrap(), 1, &config, &progress)
+        });
+
+        let metadata = progress.metadata();
+        let kills = bracket_kills(&summary);
+        assert!(metadata.partial, "{:?}", strategy);
+        assert!(metadata.files_processed < 30, "{:?}", strategy);
+        assert!(progress.read_files().len() < 30, "{:?}", strategy);
+        assert!(0 < kills && kills < full_kills, "{:?}: {}", strategy, kills);
+    }
+    remove_dir_all(&dir).unwrap();
+}
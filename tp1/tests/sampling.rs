@@ -0,0 +1,169 @@
+mod common;
+
+use clap::Parser;
+use common::test_dir;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::{Path, PathBuf};
+use tp1::cli::cli_args::{Cli, Command};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+use tp1::synthetic_data::synthetic_dataset::{SyntheticDataset, CSV_HEADER};
+
+/// Builds the `ProcessingConfig` of a `summary` invocation over a directory with the given sampling options.
+fn sampled_config(dir: &str, sample_options: &[&str]) -> ProcessingConfig {
+    let mut args = vec!["tp1", "summary", dir, "-o", "unused.json"];
+    args.extend_from_slice(sample_options);
+    let Command::Summary { ingest, sample, .. } = Cli::parse_from(args).command else {
+        panic!("Expected the summary subcommand");
+    };
+    let mut config = ingest.to_config().unwrap();
+    config.sampling = sample.sampling().unwrap();
+    config
+}
+
+/// Processes a directory with the given configuration.
+fn summarize(dir: &Path, config: &ProcessingConfig) -> DeathsInfoSummary {
+    process_data_in_parallel(dir.to_str().unwrap(), 4, config, &RunProgress::default())
+}
+
+/// Writes a dataset of 2000 rows per file whose weapon mix is skewed, so the top weapons have different shares.
+fn write_skewed_dataset(name: &str) -> PathBuf {
+    let dir = test_dir(name);
+    SyntheticDataset {
+        seed: 9,
+        players: 200,
+        file_rows: vec![2000; 8],
+        skew: 1.0,
+        ..SyntheticDataset::default()
+    }
+    .write_to(&dir)
+    .unwrap();
+    dir
+}
+
+#[test]
+fn seed_samples_a_known_set_of_rows() {
+    let dir = test_dir("sampling-known-rows");
+    create_dir_all(&dir).unwrap();
+    let rows: String = (0..10)
+        .map(|index| {
+            format!(
+                "M416,player{},1.0,100.0,100.0,ERANGEL,m1,{},victim,2.0,200.0,200.0\n",
+                index, index
+            )
+        })
+        .collect();
+    write(dir.join("deaths.csv"), format!("{}\n{}", CSV_HEADER, rows)).unwrap();
+
+    let config = sampled_config(
+        dir.to_str().unwrap(),
+        &["--sample", "0.5", "--sample-seed", "7"],
+    );
+    let summary = summarize(&dir, &config);
+    remove_dir_all(&dir).unwrap();
+
+    // Changes if the seeding or the random number generator of the sampling changes.
+    let mut kept: Vec<_> = summary.top_killers.into_keys().collect();
+    kept.sort();
+    assert_eq!(
+        kept,
+        vec!["player0", "player1", "player4", "player7", "player8"]
+    );
+}
+
+#[test]
+fn row_sample_estimates_fall_within_their_intervals() {
+    let dir = write_skewed_dataset("sampling-rows");
+    let full = summarize(&dir, &ProcessingConfig::default());
+    let config = sampled_config(
+        dir.to_str().unwrap(),
+        &["--sample", "0.25", "--sample-seed", "3"],
+    );
+    let sampled = summarize(&dir, &config);
+    remove_dir_all(&dir).unwrap();
+
+    for (weapon, stats) in &sampled.top_weapons {
+        let interval = stats
+            .deaths_percentage_ci
+            .expect("Sampled rows report intervals");
+        let Some(full_stats) = full.top_weapons.get(weapon) else {
+            continue;
+        };
+        assert!(
+            interval.low <= full_stats.deaths_percentage
+                && full_stats.deaths_percentage <= interval.high,
+            "{}: {} outside [{}, {}]",
+            weapon,
+            full_stats.deaths_percentage,
+            interval.low,
+            interval.high
+        );
+    }
+
+    // The scaled counts estimate the counts of the whole input.
+    let full_environment = full.special_deaths.environment.total as f64;
+    let sampled_environment = sampled.special_deaths.environment.total as f64;
+    assert!((sampled_environment - full_environment).abs() <= 0.2 * full_environment);
+    let full_kills: u32 = full.placement.brackets.values().map(|b| b.kills).sum();
+    let sampled_kills: u32 = sampled.placement.brackets.values().map(|b| b.kills).sum();
+    assert!((sampled_kills as f64 - full_kills as f64).abs() <= 0.05 * full_kills as f64);
+}
+
+#[test]
+fn file_sample_scales_counts_without_intervals() {
+    let dir = write_skewed_dataset("sampling-files");
+    let full = summarize(&dir, &ProcessingConfig::default());
+    let config = sampled_config(
+        dir.to_str().unwrap(),
+        &[
+            "--sample",
+            "0.5",
+            "--sample-by",
+            "files",
+            "--sample-seed",
+            "1",
+        ],
+    );
+    let progress = RunProgress::default();
+    let sampled = process_data_in_parallel(dir.to_str().unwrap(), 4, &config, &progress);
+    let kept_files = progress.read_files().len();
+    remove_dir_all(&dir).unwrap();
+
+    assert!(sampled
+        .top_weapons
+        .values()
+        .all(|stats| stats.deaths_percentage_ci.is_none()));
+    assert!(sampled
+        .top_killers
+        .values()
+        .all(|stats| stats.weapons_percentage_ci.is_none()));
+
+    // Every file has the same number of rows, so the scaled kills are close to twice the kept files' share of the
+    // kills of the whole input.
+    let full_kills: u32 = full.placement.brackets.values().map(|b| b.kills).sum();
+    let expected_kills = full_kills as f64 * kept_files as f64 / 8.0 * 2.0;
+    let sampled_kills: u32 = sampled.placement.brackets.values().map(|b| b.kills).sum();
+    assert!(kept_files > 0 && kept_files < 8);
+    assert!((sampled_kills as f64 - expected_kills).abs() <= 0.05 * expected_kills);
+}
+
+#[test]
+fn sample_is_a_summary_only_option_without_sqlite_output() {
+    let result = Cli::try_parse_from([
+        "tp1",
+        "summary",
+        "data",
+        "-o",
+        "summary.json",
+        "--sample",
+        "0.5",
+        "--sqlite-output",
+        "stats.db",
+    ]);
+    assert!(result.is_err());
+
+    let result = Cli::try_parse_from(["tp1", "player", "alice", "data", "--sample", "0.5"]);
+    assert!(result.is_err());
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use common::test_dir;
+use rusqlite::Connection;
+use std::fs::{create_dir_all, read_dir, remove_dir_all};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_directory;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_summary::data_summarizer::summarize;
+use tp1::file_creation::file_creation_error::FileCreationError;
+use tp1::file_creation::sqlite_exporter::create_sqlite_file;
+use tp1::metadata::active_filters::ActiveFilters;
+use tp1::metadata::input_fingerprint::InputFingerprint;
+use tp1::metadata::output_metadata::{OutputMetadata, DEFAULT_PRODUCER_ID};
+use tp1::synthetic_data::synthetic_dataset::SyntheticDataset;
+
+/// Returns the result of a query with a single integer value.
+fn count(connection: &Connection, query: &str) -> i64 {
+    connection.query_row(query, [], |row| row.get(0)).unwrap()
+}
+
+#[test]
+fn database_rows_match_the_processed_input() {
+    let dir = test_dir("sqlite-export");
+    let dataset = SyntheticDataset {
+        seed: 42,
+        players: 50,
+        file_rows: vec![400, 300],
+        special_death_rate: 0.1,
+        ..SyntheticDataset::default()
+    };
+    let expected = dataset.write_to(&dir).unwrap();
+    let dir_str = dir.to_str().unwrap();
+
+    let config = ProcessingConfig::default();
+    let deaths_info = process_directory(dir_str, 2, &config, &RunProgress::default());
+    let summary = summarize(&deaths_info, &config);
+    let metadata = OutputMetadata::new(
+        DEFAULT_PRODUCER_ID,
+        2,
+        ActiveFilters::default(),
+        InputFingerprint::of_files(dir_str, &[], 2, false),
+        None,
+    );
+    let database = dir.join("deaths.sqlite");
+    create_sqlite_file(database.to_str().unwrap(), &deaths_info, &metadata).unwrap();
+
+    let connection = Connection::open(&database).unwrap();
+    assert_eq!(
+        count(&connection, "SELECT COUNT(*) FROM players"),
+        expected.player_kills.len() as i64
+    );
+    assert_eq!(
+        count(&connection, "SELECT SUM(deaths) FROM players"),
+        expected.player_kills.values().sum::<u32>() as i64
+    );
+    assert_eq!(
+        count(&connection, "SELECT SUM(deaths) FROM player_weapons"),
+        expected.player_kills.values().sum::<u32>() as i64
+    );
+    assert_eq!(
+        count(&connection, "SELECT COUNT(*) FROM weapons"),
+        expected.weapons.len() as i64
+    );
+    assert_eq!(
+        count(&connection, "SELECT SUM(deaths) FROM weapons"),
+        expected.total_deaths() as i64
+    );
+    for (player, stats) in &summary.top_killers {
+        let query = format!(
+            "SELECT deaths FROM players WHERE name = '{}'",
+            player.replace('\'', "''")
+        );
+        assert_eq!(
+            count(&connection, &query),
+            stats.deaths as i64,
+            "{}",
+            player
+        );
+    }
+    assert_eq!(
+        count(
+            &connection,
+            "SELECT COUNT(*) FROM metadata WHERE key = 'schema_version'"
+        ),
+        1
+    );
+    drop(connection);
+    remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn database_errors_keep_the_sqlite_message() {
+    let dir = test_dir("sqlite-export-error");
+    let database = dir.join("missing").join("deaths.sqlite");
+
+    let result = create_sqlite_file(
+        database.to_str().unwrap(),
+        &Default::default(),
+        &OutputMetadata::new(
+            DEFAULT_PRODUCER_ID,
+            1,
+            ActiveFilters::default(),
+            InputFingerprint::default(),
+            None,
+        ),
+    );
+
+    match result {
+        Err(FileCreationError::Database(message)) => assert!(!message.is_empty()),
+        other => panic!("Expected a database error, got {:?}", other),
+    }
+}
+
+#[test]
+fn failed_exports_leave_no_temporary_database() {
+    let dir = test_dir("sqlite-export-temp");
+    let database = dir.join("deaths.sqlite");
+    // A file cannot be renamed over a directory, so the export fails after writing the temporary database.
+    create_dir_all(&database).unwrap();
+
+    let result = create_sqlite_file(
+        database.to_str().unwrap(),
+        &Default::default(),
+        &OutputMetadata::new(
+            DEFAULT_PRODUCER_ID,
+            1,
+            ActiveFilters::default(),
+            InputFingerprint::default(),
+            None,
+        ),
+    );
+    let entries: Vec<_> = read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    remove_dir_all(&dir).unwrap();
+
+    assert!(result.is_err());
+    assert_eq!(entries, vec!["deaths.sqlite"]);
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::{test_dir, write_rows};
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::Path;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::file_creation::file_creator::create_json_file;
+use tp1::metadata::active_filters::ActiveFilters;
+use tp1::metadata::input_fingerprint::InputFingerprint;
+use tp1::metadata::output_metadata::{OutputMetadata, DEFAULT_PRODUCER_ID};
+use tp1::summary_diff::diff_error::DiffError;
+use tp1::summary_diff::diff_report::{RankChange, RankedEntry, ValueChange};
+use tp1::summary_diff::summary_differ::diff_files;
+
+/// Returns a row of a kill with the given weapon and killer, whose positions are `distance` apart.
+fn row(weapon: &str, killer: &str, distance: f64) -> String {
+    format!(
+        "{},{},1,100.0,100.0,ERANGEL,m1,10,victim,2,{},100.0\n",
+        weapon,
+        killer,
+        100.0 + distance
+    )
+}
+
+/// Processes the rows in the `input` subdirectory of `dir` and writes the summary to `summary_path`.
+fn write_summary(dir: &Path, summary_path: &Path, rows: &[String]) {
+    let input_dir = dir.join("input");
+    write_rows(&input_dir.join("deaths.csv"), rows);
+    let summary = process_data_in_parallel(
+        input_dir.to_str().unwrap(),
+        1,
+        &ProcessingConfig::default(),
+        &RunProgress::default(),
+    );
+    let metadata = OutputMetadata::new(
+        DEFAULT_PRODUCER_ID,
+        1,
+        ActiveFilters::default(),
+        InputFingerprint::default(),
+        None,
+    );
+    create_json_file(summary_path.to_str().unwrap(), summary, metadata).unwrap();
+    remove_dir_all(&input_dir).unwrap();
+}
+
+/// Returns a `RankedEntry` with the given name and rank.
+fn ranked(name: &str, rank: usize) -> RankedEntry {
+    RankedEntry {
+        name: name.to_owned(),
+        rank,
+    }
+}
+
+/// Returns the change of the entry with the given name.
+fn change_of(value_changes: &[ValueChange], name: &str) -> Option<f64> {
+    value_changes
+        .iter()
+        .find(|value_change| value_change.name == name)
+        .unwrap()
+        .change
+}
+
+#[test]
+fn runs_are_compared_by_rank_and_by_value() {
+    let dir = test_dir("diff");
+    create_dir_all(&dir).unwrap();
+    let old_path = dir.join("old.json");
+    let new_path = dir.join("new.json");
+    write_summary(
+        &dir,
+        &old_path,
+        &[
+            row("M416", "alice", 100.0),
+            row("M416", "alice", 100.0),
+            row("AKM", "alice", 100.0),
+            row("AKM", "bob", 100.0),
+            row("AKM", "bob", 100.0),
+            row("Kar98k", "carl", 100.0),
+        ],
+    );
+    write_summary(
+        &dir,
+        &new_path,
+        &[
+            row("M416", "alice", 300.0),
+            row("AKM", "alice", 100.0),
+            row("AKM", "alice", 100.0),
+            row("AKM", "bob", 100.0),
+            row("Kar98k", "carl", 100.0),
+            row("Kar98k", "carl", 100.0),
+            row("Kar98k", "carl", 100.0),
+            row("Kar98k", "carl", 100.0),
+            row("M416", "dan", 300.0),
+            row("Pan", "dan", 1.0),
+        ],
+    );
+    let report = diff_files(old_path.to_str().unwrap(), new_path.to_str().unwrap(), 2).unwrap();
+    remove_dir_all(&dir).unwrap();
+
+    // Players: alice and bob were the top 2, now carl and alice are.
+    assert_eq!(report.players.entered, vec![ranked("carl", 1)]);
+    assert_eq!(report.players.left, vec![ranked("bob", 2)]);
+    assert_eq!(
+        report.players.rank_changes,
+        vec![RankChange {
+            name: "alice".to_owned(),
+            old_rank: 1,
+            new_rank: 2,
+            change: -1,
+        }]
+    );
+
+    // Weapons: AKM and M416 were the top 2, now Kar98k and AKM are, AKM winning the tie with M416 by name.
+    assert_eq!(report.weapons.entered, vec![ranked("Kar98k", 1)]);
+    assert_eq!(report.weapons.left, vec![ranked("M416", 2)]);
+    assert_eq!(report.weapons.rank_changes[0].change, -1);
+
+    assert_eq!(change_of(&report.deaths_percentage, "AKM"), Some(-20.0));
+    assert_eq!(change_of(&report.deaths_percentage, "Kar98k"), Some(23.33));
+    assert_eq!(change_of(&report.deaths_percentage, "M416"), Some(-13.33));
+    // A weapon only used in one of the runs has no change.
+    let pan = &report.deaths_percentage[3];
+    assert_eq!((pan.old, pan.new, pan.change), (None, Some(10.0), None));
+    assert_eq!(change_of(&report.average_distance, "M416"), Some(200.0));
+
+    // Only the players of both runs are compared.
+    let players: Vec<_> = report
+        .player_weapons_percentage
+        .iter()
+        .map(|player_diff| player_diff.player.as_str())
+        .collect();
+    assert_eq!(players, vec!["alice", "bob", "carl"]);
+    let alice = &report.player_weapons_percentage[0].weapons_percentage;
+    assert_eq!(change_of(alice, "AKM"), Some(33.34));
+    assert_eq!(change_of(alice, "M416"), Some(-33.34));
+
+    let text = report.to_string();
+    assert!(text.contains("  + carl entered at #1\n"));
+    assert!(text.contains("  - bob left (was #2)\n"));
+    assert!(text.contains("  alice #1 -> #2 (-1)\n"));
+    assert!(text.contains("  Pan: - -> 10 (-)\n"));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["players"]["entered"][0]["name"], "carl");
+    assert_eq!(
+        json["deaths_percentage"][3]["change"],
+        serde_json::Value::Null
+    );
+}
+
+#[test]
+fn unreadable_and_invalid_files_are_reported() {
+    let dir = test_dir("diff-errors");
+    create_dir_all(&dir).unwrap();
+    let invalid_path = dir.join("invalid.json");
+    write(&invalid_path, "{\"top_killers\": []}").unwrap();
+    let missing_path = dir.join("missing.json");
+    let invalid = invalid_path.to_str().unwrap();
+    let missing = missing_path.to_str().unwrap();
+
+    let missing_result = diff_files(missing, invalid, 10);
+    let invalid_result = diff_files(invalid, invalid, 10);
+    remove_dir_all(&dir).unwrap();
+
+    assert!(matches!(missing_result, Err(DiffError::FileRead(path)) if path == missing));
+    assert!(matches!(invalid_result, Err(DiffError::InvalidFormat(path)) if path == invalid));
+}
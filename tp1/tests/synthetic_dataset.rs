@@ -0,0 +1,152 @@
+mod common;
+
+use common::test_dir;
+use sha2::{Digest, Sha256};
+use std::fs::{read, read_to_string, remove_dir_all};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+use tp1::synthetic_data::expected_aggregates::ExpectedAggregates;
+use tp1::synthetic_data::synthetic_dataset::{SyntheticDataset, EXPECTED_AGGREGATES_FILE};
+
+/// Largest difference allowed between the expected and the summarized values, which are rounded to two decimals.
+const TOLERANCE: f64 = 0.01;
+
+/// Checks that a summary matches the aggregates expected from its dataset.
+fn assert_summary_matches(summary: &DeathsInfoSummary, expected: &ExpectedAggregates) {
+    let mut top_kill_counts: Vec<u32> = summary
+        .top_killers
+        .values()
+        .map(|stats| stats.deaths)
+        .collect();
+    top_kill_counts.sort_unstable_by(|a, b| b.cmp(a));
+    assert_eq!(top_kill_counts, expected.top_kill_counts(10));
+    for (player, stats) in &summary.top_killers {
+        assert_eq!(
+            Some(&stats.deaths),
+            expected.player_kills.get(player),
+            "Kills of {}",
+            player
+        );
+    }
+
+    let total_deaths = expected.total_deaths() as f64;
+    for (weapon, stats) in &summary.top_weapons {
+        let expected_stats = &expected.weapons[weapon];
+        let percentage = expected_stats.deaths as f64 / total_deaths * 100.0;
+        assert!(
+            (stats.deaths_percentage - percentage).abs() <= TOLERANCE,
+            "Percentage of {}",
+            weapon
+        );
+        assert!(
+            (stats.average_distance - expected_stats.average_distance()).abs() <= TOLERANCE,
+            "Average distance of {}",
+            weapon
+        );
+    }
+
+    let special_deaths = &summary.special_deaths;
+    assert_eq!(
+        special_deaths.environment.total,
+        expected.environment_deaths
+    );
+    assert_eq!(special_deaths.suicides, expected.suicides);
+    assert_eq!(special_deaths.team_kills, expected.team_kills);
+}
+
+#[test]
+fn summary_matches_the_expected_aggregates() {
+    let datasets = [
+        SyntheticDataset {
+            seed: 43,
+            players: 500,
+            file_rows: vec![3_000, 1_000, 2_200],
+            ..SyntheticDataset::default()
+        },
+        SyntheticDataset {
+            seed: 44,
+            players: 2_000,
+            weapons: 6,
+            maps: 2,
+            deaths_per_match: 50,
+            file_rows: vec![5_000, 0, 800, 2_500],
+            malformed_rate: 0.02,
+            special_death_rate: 0.1,
+            skew: 1.2,
+        },
+    ];
+
+    for (index, dataset) in datasets.iter().enumerate() {
+        let dir_path = test_dir(&format!("synthetic-{}", index));
+        let expected = dataset.write_to(&dir_path).unwrap();
+        let written: ExpectedAggregates =
+            serde_json::from_str(&read_to_string(dir_path.join(EXPECTED_AGGREGATES_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(written.player_kills, expected.player_kills);
+        assert_eq!(written.total_deaths(), expected.total_deaths());
+
+        for threads in [1, 3] {
+            let summary = process_data_in_parallel(
+                dir_path.to_str().unwrap(),
+                threads,
+                &ProcessingConfig::default(),
+                &RunProgress::default(),
+            );
+            assert_summary_matches(&summary, &expected);
+        }
+
+        remove_dir_all(dir_path).unwrap();
+    }
+}
+
+#[test]
+fn same_seed_writes_the_same_dataset() {
+    let dataset = SyntheticDataset {
+        seed: 45,
+        file_rows: vec![1_000],
+        malformed_rate: 0.05,
+        special_death_rate: 0.1,
+        skew: 0.8,
+        ..SyntheticDataset::default()
+    };
+    let (first_dir, second_dir) = (test_dir("seed-first"), test_dir("seed-second"));
+    dataset.write_to(&first_dir).unwrap();
+    dataset.write_to(&second_dir).unwrap();
+
+    assert_eq!(
+        read_to_string(first_dir.join("deaths_0.csv")).unwrap(),
+        read_to_string(second_dir.join("deaths_0.csv")).unwrap()
+    );
+
+    remove_dir_all(first_dir).unwrap();
+    remove_dir_all(second_dir).unwrap();
+}
+
+#[test]
+fn seed_writes_a_known_dataset() {
+    let dataset = SyntheticDataset {
+        seed: 46,
+        players: 100,
+        file_rows: vec![500],
+        malformed_rate: 0.05,
+        special_death_rate: 0.1,
+        skew: 0.8,
+        ..SyntheticDataset::default()
+    };
+    let dir = test_dir("seed-known");
+    dataset.write_to(&dir).unwrap();
+    let content = read(dir.join("deaths_0.csv")).unwrap();
+    remove_dir_all(dir).unwrap();
+
+    let hash: String = Sha256::digest(&content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    // Changes if the generator or the random number generator changes, which also changes the benchmark datasets.
+    assert_eq!(
+        hash,
+        "34cf2a2bc2e1fefa292e0be68fa49a2be40139d2c23477d7135dd2784ffc3e0a"
+    );
+}
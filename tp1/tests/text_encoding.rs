@@ -0,0 +1,71 @@
+mod common;
+
+use common::{config_from_args, test_dir};
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::config::processing_strategy::ProcessingStrategy;
+use tp1::data_processing::data_processor::process_data_in_parallel;
+use tp1::data_processing::run_progress::RunProgress;
+use tp1::synthetic_data::synthetic_dataset::CSV_HEADER;
+
+/// Writes the same player's kills in a UTF-8 file with CRLF line ends and a BOM, and in a Latin-1 file, to a
+/// directory only used by this test.
+fn write_mixed_encodings(name: &str) -> PathBuf {
+    let dir_path = test_dir(name);
+    create_dir_all(&dir_path).unwrap();
+
+    let row = "M416,José,1.0,100.0,100.0,ERANGEL,m1,10,victim,2.0,200.0,200.0";
+    let utf8 = format!("\u{feff}{}\r\n{}\r\n\u{feff}{}\r\n", CSV_HEADER, row, row);
+    write(dir_path.join("utf8.csv"), utf8).unwrap();
+
+    let mut latin1 = format!("{}\n", CSV_HEADER).into_bytes();
+    latin1.extend(row.chars().map(|c| c as u8));
+    latin1.push(b'\n');
+    write(dir_path.join("latin1.csv"), latin1).unwrap();
+    dir_path
+}
+
+/// Processes a directory, returning the kills of every top killer.
+fn top_killer_kills(dir_path: &str, config: &ProcessingConfig) -> Vec<(String, u32)> {
+    let summary = process_data_in_parallel(dir_path, 2, config, &RunProgress::default());
+    let mut kills: Vec<_> = summary
+        .top_killers
+        .into_iter()
+        .map(|(player, stats)| (player, stats.deaths))
+        .collect();
+    kills.sort();
+    kills
+}
+
+#[test]
+fn latin1_and_bom_rows_match_the_utf8_player() {
+    let dir_path = write_mixed_encodings("encoding-auto");
+    let dir = dir_path.to_str().unwrap();
+
+    for strategy in [ProcessingStrategy::Reduce, ProcessingStrategy::Pipeline] {
+        let mut config = ProcessingConfig::default();
+        config.strategy = strategy;
+        assert_eq!(
+            top_killer_kills(dir, &config),
+            vec![("José".to_owned(), 3)],
+            "Strategy {:?}",
+            strategy
+        );
+    }
+    remove_dir_all(&dir_path).unwrap();
+}
+
+#[test]
+fn utf8_lossy_keeps_invalid_rows_under_a_replaced_name() {
+    let dir_path = write_mixed_encodings("encoding-lossy");
+    let dir = dir_path.to_str().unwrap();
+
+    let kills = top_killer_kills(dir, &config_from_args(&["--encoding", "utf8-lossy"]));
+    remove_dir_all(&dir_path).unwrap();
+
+    assert_eq!(
+        kills,
+        vec![("José".to_owned(), 2), ("Jos\u{fffd}".to_owned(), 1)]
+    );
+}
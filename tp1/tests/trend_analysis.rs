@@ -0,0 +1,106 @@
+mod common;
+
+use common::{test_dir, write_rows};
+use std::fs::{create_dir_all, remove_dir_all};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::trend_analysis::trend_analyzer::{analyze_trends, trend_of};
+use tp1::trend_analysis::trend_error::TrendError;
+use tp1::trend_analysis::trend_report::{SeriesTrend, Trend};
+
+/// Returns a row of a kill with the given weapon and killer.
+fn row(weapon: &str, killer: &str) -> String {
+    format!(
+        "{},{},1,100.0,100.0,ERANGEL,m1,10,victim,2,200.0,200.0\n",
+        weapon, killer
+    )
+}
+
+/// Returns the name, values and trend of every series.
+fn series(series: &[SeriesTrend]) -> Vec<(&str, Vec<f64>, Trend)> {
+    series
+        .iter()
+        .map(|series| (series.name.as_str(), series.values.clone(), series.trend))
+        .collect()
+}
+
+#[test]
+fn trends_follow_the_relative_slope() {
+    assert_eq!(trend_of(&[1.0, 2.0, 3.0]), Trend::Rising);
+    assert_eq!(trend_of(&[3.0, 2.0, 1.0]), Trend::Falling);
+    assert_eq!(trend_of(&[2.0, 2.0, 2.0]), Trend::Stable);
+
+    // The slope is relative to the mean, so the same absolute change is a trend only for small values.
+    assert_eq!(trend_of(&[10.0, 11.0, 12.0]), Trend::Rising);
+    assert_eq!(trend_of(&[1000.0, 1001.0, 1002.0]), Trend::Stable);
+    assert_eq!(trend_of(&[1000.0, 1100.0, 1200.0]), Trend::Rising);
+
+    // Noise around a flat series is not a trend.
+    assert_eq!(trend_of(&[10.0, 10.3, 9.8, 10.1]), Trend::Stable);
+
+    assert_eq!(trend_of(&[5.0]), Trend::Stable);
+    assert_eq!(trend_of(&[0.0, 0.0, 0.0]), Trend::Stable);
+}
+
+#[test]
+fn each_period_is_summarized_on_its_own() {
+    let dir = test_dir("trends");
+    // Every period has 7 deaths. alice goes from 1 to 3 kills with the M416, bob from 3 to 1 with the AKM, carl
+    // keeps 2 with the Kar98k and dan, with 1 M416 kill per period, is never in the top 2.
+    let periods = [
+        ("2024-05-03", [("M416", "alice", 3), ("AKM", "bob", 1)]),
+        ("2024-05-01", [("M416", "alice", 1), ("AKM", "bob", 3)]),
+        ("2024-05-02", [("M416", "alice", 2), ("AKM", "bob", 2)]),
+    ];
+    for (period, kills) in periods {
+        let mut rows: Vec<_> = kills
+            .iter()
+            .flat_map(|(weapon, killer, count)| vec![row(weapon, killer); *count])
+            .collect();
+        rows.extend([
+            row("Kar98k", "carl"),
+            row("Kar98k", "carl"),
+            row("M416", "dan"),
+        ]);
+        write_rows(&dir.join(period).join("deaths.csv"), &rows);
+    }
+    // Subdirectories without input files are not periods.
+    create_dir_all(dir.join("notes")).unwrap();
+
+    let report = analyze_trends(dir.to_str().unwrap(), 2, &ProcessingConfig::default(), 2).unwrap();
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        report.periods,
+        vec!["2024-05-01", "2024-05-02", "2024-05-03"]
+    );
+    assert_eq!(
+        series(&report.weapons_deaths_percentage),
+        vec![
+            ("AKM", vec![42.86, 28.57, 14.29], Trend::Falling),
+            ("Kar98k", vec![28.57, 28.57, 28.57], Trend::Stable),
+            ("M416", vec![28.57, 42.86, 57.14], Trend::Rising),
+        ]
+    );
+    assert_eq!(
+        series(&report.players_kills),
+        vec![
+            ("alice", vec![1.0, 2.0, 3.0], Trend::Rising),
+            ("bob", vec![3.0, 2.0, 1.0], Trend::Falling),
+            ("carl", vec![2.0, 2.0, 2.0], Trend::Stable),
+        ]
+    );
+}
+
+#[test]
+fn directories_without_periods_are_rejected() {
+    let dir = test_dir("trends-no-periods");
+    write_rows(&dir.join("deaths.csv"), &[row("M416", "alice")]);
+    let dir_path = dir.to_str().unwrap();
+
+    let no_periods = analyze_trends(dir_path, 1, &ProcessingConfig::default(), 10);
+    remove_dir_all(&dir).unwrap();
+    let missing = analyze_trends(dir_path, 1, &ProcessingConfig::default(), 10);
+
+    assert!(matches!(no_periods, Err(TrendError::NoPeriods(path)) if path == dir_path));
+    assert!(matches!(missing, Err(TrendError::DirectoryRead(path)) if path == dir_path));
+}
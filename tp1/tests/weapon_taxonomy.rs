@@ -0,0 +1,111 @@
+mod common;
+
+use common::{config_with_file, summarize_rows, test_dir, try_config_with_file};
+use std::fs::{create_dir_all, remove_dir_all, write};
+use tp1::config::processing_config::ProcessingConfig;
+use tp1::config::weapon_taxonomy::{WeaponTaxonomy, UNCATEGORIZED};
+use tp1::data_summary::deaths_info_summary::DeathsInfoSummary;
+
+const MAPPING: &str = r#"{
+    "M416": { "category": "AR", "aliases": ["M-416"] },
+    "AKM": { "category": "AR" },
+    "Kar98k": { "category": "SR" },
+    "Punch": { "category": "melee", "aliases": ["Fist"] }
+}"#;
+
+/// Returns a row of a kill with the given weapon and killer, whose positions are `distance` apart.
+fn row(weapon: &str, killer: &str, distance: f64) -> String {
+    format!(
+        "{},{},1,100.0,100.0,ERANGEL,m1,10,victim,2,{},100.0\n",
+        weapon,
+        killer,
+        100.0 + distance
+    )
+}
+
+/// Processes the rows with `MAPPING` in a directory only used by the test with the given name.
+fn summarize_mapped(name: &str, rows: &[String]) -> DeathsInfoSummary {
+    let config = config_with_file(name, "--weapon-mapping", MAPPING, &[]);
+    summarize_rows(name, rows, &config)
+}
+
+#[test]
+fn aliases_and_casing_resolve_to_the_canonical_weapon() {
+    let dir = test_dir("taxonomy-lookup");
+    create_dir_all(&dir).unwrap();
+    let mapping_path = dir.join("weapons.json");
+    write(&mapping_path, MAPPING).unwrap();
+    let taxonomy = WeaponTaxonomy::from_file(mapping_path.to_str().unwrap()).unwrap();
+    remove_dir_all(&dir).unwrap();
+
+    assert_eq!(taxonomy.canonical_name("M416"), "M416");
+    assert_eq!(taxonomy.canonical_name("m416"), "M416");
+    assert_eq!(taxonomy.canonical_name("m-416"), "M416");
+    assert_eq!(taxonomy.canonical_name("fist"), "Punch");
+    assert_eq!(taxonomy.canonical_name("Crowbar"), "Crowbar");
+    assert_eq!(taxonomy.category_of("Kar98k"), "SR");
+    assert_eq!(taxonomy.category_of("Crowbar"), UNCATEGORIZED);
+}
+
+#[test]
+fn stats_roll_up_per_weapon_and_per_category() {
+    let summary = summarize_mapped(
+        "taxonomy-rollup",
+        &[
+            row("M416", "alice", 100.0),
+            row("m416", "alice", 100.0),
+            row("M-416", "bob", 100.0),
+            row("AKM", "bob", 500.0),
+            row("Kar98k", "carl", 800.0),
+            row("Fist", "dan", 1.0),
+            row("Punch", "dan", 3.0),
+            row("Crowbar", "erin", 2.0),
+        ],
+    );
+
+    let mut weapons: Vec<_> = summary.top_weapons.keys().map(String::as_str).collect();
+    weapons.sort();
+    assert_eq!(weapons, vec!["AKM", "Crowbar", "Kar98k", "M416", "Punch"]);
+    assert_eq!(summary.top_weapons["M416"].deaths_percentage, 37.5);
+    assert_eq!(summary.top_weapons["M416"].average_distance, 100.0);
+    assert_eq!(summary.top_weapons["Punch"].deaths_percentage, 25.0);
+    assert_eq!(summary.top_killers["bob"].weapons_percentage["M416"], 50.0);
+
+    let categories = summary
+        .weapon_categories
+        .expect("A mapping file adds the categories");
+    let mut names: Vec<_> = categories.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(names, vec!["AR", "SR", "melee", UNCATEGORIZED]);
+    assert_eq!(categories["AR"].deaths_percentage, 50.0);
+    assert_eq!(categories["AR"].average_distance, 200.0);
+    assert_eq!(categories["SR"].deaths_percentage, 12.5);
+    assert_eq!(categories["melee"].average_distance, 2.0);
+    assert_eq!(categories[UNCATEGORIZED].deaths_percentage, 12.5);
+}
+
+#[test]
+fn summaries_without_a_mapping_have_no_categories() {
+    let summary = summarize_rows(
+        "taxonomy-none",
+        &[row("M416", "alice", 100.0)],
+        &ProcessingConfig::default(),
+    );
+
+    assert!(summary.weapon_categories.is_none());
+}
+
+#[test]
+fn an_alias_of_two_weapons_is_rejected() {
+    let result = try_config_with_file(
+        "taxonomy-conflict",
+        "--weapon-mapping",
+        r#"{
+            "M416": { "category": "AR", "aliases": ["Rifle"] },
+            "AKM": { "category": "AR", "aliases": ["rifle"] }
+        }"#,
+        &[],
+    );
+
+    assert!(result.is_err());
+}